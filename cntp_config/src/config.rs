@@ -24,8 +24,16 @@ pub struct ContemporaryConfigDeploymentDef {
     pub contemporary_base_icon: Option<String>,
     pub handled_url_schemes: Option<Vec<String>>,
 
+    // Publishing
+    pub bin_chicken_url: Option<String>,
+    pub bin_chicken_repository: Option<String>,
+    pub bin_chicken_uuid: Option<String>,
+
     // Linux only
     pub desktop_entry_categories: Option<Vec<String>>,
+    pub dbus_activatable: Option<bool>,
+    pub autostart: Option<bool>,
+    pub autostart_arguments: Option<Vec<String>>,
 
     // macOS only
     pub apple_localisation_dir: Option<String>,
@@ -48,8 +56,16 @@ pub struct ContemporaryConfigDeployment {
     pub contemporary_base_icon: Option<String>,
     pub handled_url_schemes: Option<Vec<String>>,
 
+    // Publishing
+    pub bin_chicken_url: Option<String>,
+    pub bin_chicken_repository: Option<String>,
+    pub bin_chicken_uuid: Option<String>,
+
     // Linux only
     pub desktop_entry_categories: Option<Vec<String>>,
+    pub dbus_activatable: bool,
+    pub autostart: bool,
+    pub autostart_arguments: Vec<String>,
 
     // macOS only
     pub apple_localisation_dir: Option<LocalisedString>,
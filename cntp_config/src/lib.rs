@@ -169,10 +169,33 @@ impl ContemporaryConfig {
                 .handled_url_schemes
                 .or(deployment.handled_url_schemes),
 
+            // Publishing
+            bin_chicken_url: specific_deployment
+                .bin_chicken_url
+                .or(deployment.bin_chicken_url),
+            bin_chicken_repository: specific_deployment
+                .bin_chicken_repository
+                .or(deployment.bin_chicken_repository),
+            bin_chicken_uuid: specific_deployment
+                .bin_chicken_uuid
+                .or(deployment.bin_chicken_uuid),
+
             // Linux only
             desktop_entry_categories: specific_deployment
                 .desktop_entry_categories
                 .or(deployment.desktop_entry_categories),
+            dbus_activatable: specific_deployment
+                .dbus_activatable
+                .or(deployment.dbus_activatable)
+                .unwrap_or(false),
+            autostart: specific_deployment
+                .autostart
+                .or(deployment.autostart)
+                .unwrap_or(false),
+            autostart_arguments: specific_deployment
+                .autostart_arguments
+                .or(deployment.autostart_arguments)
+                .unwrap_or_default(),
 
             // macOS only
             apple_localisation_dir: self.resolve_localised_string(
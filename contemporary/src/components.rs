@@ -13,11 +13,13 @@ pub mod grandstand;
 pub mod icon;
 pub mod icon_text;
 pub mod interstitial;
+pub mod language_picker;
 pub mod layer;
 pub mod pager;
 pub mod popover;
 pub mod progress_bar;
 pub mod raised;
+pub mod rich_text;
 pub mod scrim;
 pub mod scroll_area;
 pub mod scrollbar;
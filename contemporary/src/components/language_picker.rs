@@ -0,0 +1,60 @@
+use crate::components::button::button;
+use crate::components::context_menu::ContextMenuItem;
+use crate::language::{chosen_language, set_language};
+use cntp_i18n::{Locale, i18n_manager, tr};
+use gpui::prelude::FluentBuilder;
+use gpui::{App, ElementId, IntoElement, ParentElement, RenderOnce, Window};
+
+/// A button that opens a menu of the languages the application is translated into.
+///
+/// Each language is listed by its name in that language, so that users can find their own
+/// language no matter which language the application is currently displayed in. The chosen
+/// language is saved and applied the next time the application starts.
+#[derive(IntoElement)]
+pub struct LanguagePicker {
+    id: ElementId,
+}
+
+pub fn language_picker(id: impl Into<ElementId>) -> LanguagePicker {
+    LanguagePicker { id: id.into() }
+}
+
+fn language_name(language: &str) -> String {
+    let locale = Locale::new_from_locale_identifier(language);
+    locale.human_readable_locale_name_in(&locale)
+}
+
+impl RenderOnce for LanguagePicker {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let chosen_language = chosen_language(cx);
+        let available_languages = i18n_manager!().available_languages();
+
+        let system_language_item = ContextMenuItem::menu_item()
+            .label(tr!("LANGUAGE_PICKER_SYSTEM", "System Language"))
+            .when(chosen_language.is_none(), |item| item.icon("dialog-ok"))
+            .on_triggered(|_, _, cx| set_language(None, cx))
+            .build();
+
+        let menu_items = [system_language_item, ContextMenuItem::separator().build()]
+            .into_iter()
+            .chain(available_languages.into_iter().map(|language| {
+                ContextMenuItem::menu_item()
+                    .label(language_name(&language))
+                    .when(chosen_language.as_ref() == Some(&language), |item| {
+                        item.icon("dialog-ok")
+                    })
+                    .on_triggered(move |_, _, cx| set_language(Some(language.clone()), cx))
+                    .build()
+            }))
+            .collect();
+
+        let current_language_name = match &chosen_language {
+            Some(language) => language_name(language),
+            None => tr!("LANGUAGE_PICKER_SYSTEM").into(),
+        };
+
+        button(self.id)
+            .child(current_language_name)
+            .with_menu(menu_items)
+    }
+}
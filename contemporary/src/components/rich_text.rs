@@ -0,0 +1,92 @@
+use crate::styling::theme::ThemeStorage;
+use cntp_i18n::markup::parse_markup;
+use gpui::{
+    App, ElementId, FontStyle, FontWeight, HighlightStyle, InteractiveText, IntoElement,
+    RenderOnce, SharedString, StyledText, UnderlineStyle, Window, px,
+};
+use std::rc::Rc;
+use tracing::warn;
+
+type LinkHandler = Rc<Box<dyn Fn(&mut Window, &mut App)>>;
+
+/// Text containing rich text markup, such as the output of `tr!` with `#markup = true`.
+///
+/// Bold and italic markup is rendered with the matching font style, and links are underlined
+/// and call the handler registered for their name with [`on_link`](RichText::on_link).
+#[derive(IntoElement)]
+pub struct RichText {
+    id: ElementId,
+    markup: SharedString,
+    link_handlers: Vec<(SharedString, LinkHandler)>,
+}
+
+pub fn rich_text(id: impl Into<ElementId>, markup: impl Into<SharedString>) -> RichText {
+    RichText {
+        id: id.into(),
+        markup: markup.into(),
+        link_handlers: Vec::new(),
+    }
+}
+
+impl RichText {
+    /// Call a handler when a link with the given name (`<a href="name">`) is clicked.
+    pub fn on_link(
+        mut self,
+        name: impl Into<SharedString>,
+        handler: impl Fn(&mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.link_handlers
+            .push((name.into(), Rc::new(Box::new(handler))));
+        self
+    }
+}
+
+impl RenderOnce for RichText {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = cx.theme();
+        let markup = parse_markup(&self.markup);
+
+        let highlights = markup
+            .runs
+            .iter()
+            .filter(|run| run.bold || run.italic || run.link.is_some())
+            .map(|run| {
+                (
+                    run.range.clone(),
+                    HighlightStyle {
+                        font_weight: run.bold.then_some(FontWeight::BOLD),
+                        font_style: run.italic.then_some(FontStyle::Italic),
+                        underline: run.link.is_some().then(|| UnderlineStyle {
+                            thickness: px(1.),
+                            color: Some(theme.foreground.into()),
+                            wavy: false,
+                        }),
+                        ..Default::default()
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let (link_ranges, link_names): (Vec<_>, Vec<_>) = markup
+            .runs
+            .iter()
+            .filter_map(|run| Some((run.range.clone(), run.link.clone()?)))
+            .unzip();
+
+        let link_handlers = self.link_handlers;
+        InteractiveText::new(
+            self.id,
+            StyledText::new(markup.text).with_highlights(highlights),
+        )
+        .on_click(link_ranges, move |index, window, cx| {
+            let name = &link_names[index];
+            match link_handlers
+                .iter()
+                .find(|(handler_name, _)| handler_name == name)
+            {
+                Some((_, handler)) => handler(window, cx),
+                None => warn!("No handler for rich text link {name}"),
+            }
+        })
+    }
+}
@@ -0,0 +1,179 @@
+use crate::application::Details;
+use cntp_i18n::{I18N_MANAGER, Locale};
+use gpui::{App, Global};
+use std::fs;
+use std::path::PathBuf;
+use tracing::{error, info};
+
+fn language_file(details: &Details) -> Option<PathBuf> {
+    Some(details.standard_dirs()?.config_dir().join("language"))
+}
+
+/// Reads the language saved by [`set_language`], or `None` if the system language is used.
+pub(crate) fn saved_language(details: &Details) -> Option<String> {
+    let language = fs::read_to_string(language_file(details)?).ok()?;
+    let language = language.trim();
+    (!language.is_empty()).then(|| language.to_string())
+}
+
+/// The language chosen by the user, or `None` if the system language is used.
+///
+/// This is read from the saved choice when the application starts, and updated by
+/// [`set_language`].
+pub(crate) struct ChosenLanguage(pub(crate) Option<String>);
+
+impl Global for ChosenLanguage {}
+
+/// Gets the language chosen by the user, or `None` if the system language is used.
+pub fn chosen_language(cx: &App) -> Option<String> {
+    cx.global::<ChosenLanguage>().0.clone()
+}
+
+/// Sets the language of the application, or follows the system language if `language` is
+/// `None`.
+///
+/// The choice is saved and applied again the next time the application starts. All windows
+/// are refreshed to show the new translations.
+pub fn set_language(language: Option<String>, cx: &mut App) {
+    if let Some(file) = language_file(cx.global::<Details>()) {
+        let result = match &language {
+            Some(language) => file
+                .parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|_| fs::write(&file, language)),
+            None if file.exists() => fs::remove_file(&file),
+            None => Ok(()),
+        };
+        if let Err(e) = result {
+            error!("Unable to save the chosen language: {:?}", e);
+        }
+    }
+
+    apply_language(language.as_deref(), cx.global::<SystemLocale>());
+    cx.set_global(ChosenLanguage(language));
+    cx.refresh_windows();
+}
+
+/// The locale preferences of the operating system.
+///
+/// This is read when the application starts, and updated by the platform listeners when the
+/// user changes their language or region settings while the application is running.
+#[derive(PartialEq, Clone, Debug)]
+pub struct SystemLocale {
+    pub messages: Vec<String>,
+    pub numeric: Vec<String>,
+    pub time: Vec<String>,
+    pub collate: Vec<String>,
+}
+
+impl SystemLocale {
+    pub fn current() -> Self {
+        let locale = Locale::current();
+        Self {
+            messages: locale.messages,
+            numeric: locale.numeric,
+            time: locale.time,
+            collate: locale.collate,
+        }
+    }
+
+    /// Creates the preferences for a user who uses `messages` for the language of the interface
+    /// and `formats` for everything else. Each identifier is expanded into a fallback chain.
+    pub fn from_identifiers<'a>(
+        messages: impl IntoIterator<Item = &'a str>,
+        formats: &str,
+    ) -> Self {
+        let mut formats = language_chain(formats);
+        if formats.is_empty() {
+            formats = vec!["en".to_string()];
+        }
+
+        let mut messages = messages
+            .into_iter()
+            .flat_map(language_chain)
+            .collect::<Vec<_>>();
+        if messages.is_empty() {
+            messages = formats.clone();
+        }
+
+        Self {
+            messages,
+            numeric: formats.clone(),
+            time: formats.clone(),
+            collate: formats,
+        }
+    }
+}
+
+impl Global for SystemLocale {}
+
+/// Updates the locale preferences of the operating system, and applies them if they have
+/// changed.
+///
+/// A language chosen by the user with [`set_language`] takes precedence over the system
+/// language, but the system's formatting preferences are still followed.
+pub(crate) fn system_locale_changed(system_locale: SystemLocale, cx: &mut App) {
+    if *cx.global::<SystemLocale>() == system_locale {
+        return;
+    }
+
+    info!("System locale changed to {:?}", system_locale.messages);
+    apply_language(chosen_language(cx).as_deref(), &system_locale);
+    cx.set_global(system_locale);
+    cx.refresh_windows();
+}
+
+/// Applies a language to the global i18n manager, or the system language if `language` is
+/// `None`.
+///
+/// The system language is matched against the available translations, so that a user who
+/// prefers a regional variant of a language (e.g. "pt-BR") still sees a translation for
+/// another variant (e.g. "pt-PT") if there is no better match. The rest of the system's
+/// preferred languages follow it, for strings that are missing from its translation.
+pub(crate) fn apply_language(language: Option<&str>, system_locale: &SystemLocale) {
+    let mut manager = I18N_MANAGER.write().unwrap();
+
+    let language = language
+        .map(str::to_string)
+        .or_else(|| manager.negotiate_language(&system_locale.messages));
+    let messages = match language {
+        Some(language) => {
+            let mut messages = language_chain(&language);
+            for message in &system_locale.messages {
+                if !messages.contains(message) {
+                    messages.push(message.clone());
+                }
+            }
+            messages
+        }
+        None => system_locale.messages.clone(),
+    };
+
+    // Keep the user's formatting preferences
+    manager.locale = Locale::new_from_parts(
+        messages,
+        system_locale.numeric.clone(),
+        system_locale.time.clone(),
+        system_locale.collate.clone(),
+    );
+    manager.clear_cache();
+}
+
+/// Builds a fallback chain for a language, e.g. `["zh-Hant-TW", "zh-Hant", "zh"]`.
+///
+/// POSIX style identifiers such as `de_DE.UTF-8@euro` are accepted as well.
+fn language_chain(language: &str) -> Vec<String> {
+    let language = language
+        .split(['.', '@'])
+        .next()
+        .unwrap_or_default()
+        .replace('_', "-");
+    let subtags = language
+        .split('-')
+        .filter(|subtag| !subtag.is_empty())
+        .collect::<Vec<_>>();
+    (1..=subtags.len())
+        .rev()
+        .map(|len| subtags[..len].join("-"))
+        .collect()
+}
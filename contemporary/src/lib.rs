@@ -6,6 +6,7 @@ pub mod easing;
 pub mod hsv;
 pub mod icon_tool;
 pub mod jobs;
+pub mod language;
 pub mod lerp;
 pub mod macros;
 pub mod notification;
@@ -5,5 +5,6 @@ mod kde;
 pub mod notification;
 pub mod platform_settings;
 pub mod setup;
+mod setup_locale1_locale_changed_listener;
 mod setup_xdg_portal_interface_theme_changed_listener;
 pub mod theme;
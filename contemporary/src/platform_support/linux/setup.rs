@@ -2,11 +2,13 @@ use crate::platform_support::linux::desktop_environment::DesktopEnvironment;
 use crate::platform_support::linux::gnome::setup::setup_gnome;
 use crate::platform_support::linux::kde::setup::setup_kde;
 use crate::platform_support::linux::notification::setup_linux_notifications;
+use crate::platform_support::linux::setup_locale1_locale_changed_listener::setup_locale1_locale_changed_listener;
 use crate::platform_support::linux::setup_xdg_portal_interface_theme_changed_listener::setup_xdg_portal_interface_theme_changed_listener;
 use gpui::App;
 
 pub fn setup_linux(cx: &mut App) {
     setup_xdg_portal_interface_theme_changed_listener(cx);
+    setup_locale1_locale_changed_listener(cx);
     setup_linux_notifications(cx);
 
     match DesktopEnvironment::current() {
@@ -0,0 +1,69 @@
+use crate::language::{SystemLocale, system_locale_changed};
+use gpui::{App, AsyncApp};
+use smol::stream::StreamExt;
+use std::collections::HashMap;
+use zbus::{Connection, Proxy};
+
+/// Convert the `Locale` property of `org.freedesktop.locale1` (e.g. `["LANG=de_DE.UTF-8",
+/// "LC_TIME=en_GB.UTF-8"]`) into locale preferences.
+fn system_locale_from_locale1(variables: &[String]) -> SystemLocale {
+    let variables = variables
+        .iter()
+        .filter_map(|variable| variable.split_once('='))
+        .collect::<HashMap<_, _>>();
+    let lang = variables.get("LANG").copied().unwrap_or("en");
+    let category = |name: &str| variables.get(name).copied().unwrap_or(lang);
+
+    let chain = |name: &str| SystemLocale::from_identifiers([category(name)], lang).messages;
+
+    SystemLocale {
+        messages: chain("LC_MESSAGES"),
+        numeric: chain("LC_NUMERIC"),
+        time: chain("LC_TIME"),
+        collate: chain("LC_COLLATE"),
+    }
+}
+
+pub fn setup_locale1_locale_changed_listener(cx: &mut App) {
+    cx.spawn(async move |cx: &mut AsyncApp| {
+        let Ok(connection) = Connection::system().await else {
+            return;
+        };
+
+        let Ok(proxy) = Proxy::new(
+            &connection,
+            "org.freedesktop.locale1",
+            "/org/freedesktop/locale1",
+            "org.freedesktop.locale1",
+        )
+        .await
+        else {
+            return;
+        };
+
+        // The application starts with the locale of the session, which may be different from
+        // the system locale, so only react when the system locale changes
+        let mut current_variables = proxy
+            .get_property::<Vec<String>>("Locale")
+            .await
+            .unwrap_or_default();
+        let mut locale_changed = proxy
+            .receive_property_changed::<Vec<String>>("Locale")
+            .await;
+
+        while let Some(change) = locale_changed.next().await {
+            let Ok(variables) = change.get().await else {
+                continue;
+            };
+            if variables == current_variables {
+                continue;
+            }
+            current_variables = variables.clone();
+
+            cx.update(|cx| {
+                system_locale_changed(system_locale_from_locale1(&variables), cx);
+            });
+        }
+    })
+    .detach()
+}
@@ -1,3 +1,4 @@
+mod apple_current_locale_changed_listener;
 mod apple_interface_theme_changed_listener;
 mod apple_workspace_a11y_options_changed_listener;
 mod cx_platform_mac_extensions;
@@ -0,0 +1,89 @@
+use crate::language::{SystemLocale, system_locale_changed};
+use async_channel::Sender;
+use gpui::{App, AsyncApp, Global};
+use objc2::__framework_prelude::Retained;
+use objc2::runtime::{NSObject, NSObjectProtocol};
+use objc2::{DefinedClass, MainThreadMarker, MainThreadOnly, define_class, msg_send, sel};
+use objc2_foundation::{
+    NSCurrentLocaleDidChangeNotification, NSLocale, NSNotification, NSNotificationCenter,
+};
+
+struct AppleCurrentLocaleChangedListenerFields {
+    tx: Sender<()>,
+}
+
+define_class!(
+    #[unsafe(super(NSObject))]
+    #[thread_kind = MainThreadOnly]
+
+    #[ivars = AppleCurrentLocaleChangedListenerFields]
+    struct AppleCurrentLocaleChangedListener;
+
+    impl AppleCurrentLocaleChangedListener {
+        #[unsafe(method(currentLocaleDidChange:))]
+        fn __current_locale_did_change(&self, _: &NSNotification) -> u8 {
+            _ = smol::block_on(self.ivars().tx.send(()));
+            0
+        }
+    }
+
+    unsafe impl NSObjectProtocol for AppleCurrentLocaleChangedListener {}
+);
+
+impl AppleCurrentLocaleChangedListener {
+    fn new(tx: Sender<()>) -> Retained<Self> {
+        let this = Self::alloc(MainThreadMarker::new().unwrap())
+            .set_ivars(AppleCurrentLocaleChangedListenerFields { tx });
+        unsafe { msg_send![super(this), init] }
+    }
+}
+
+struct AppleCurrentLocaleChangedListenerGlobalableWrapperThing {
+    globalable_item: Retained<AppleCurrentLocaleChangedListener>,
+}
+
+impl Global for AppleCurrentLocaleChangedListenerGlobalableWrapperThing {}
+
+/// Read the locale preferences from `NSLocale`, which is kept up to date by the system.
+fn apple_system_locale() -> SystemLocale {
+    let preferred_languages = NSLocale::preferredLanguages()
+        .iter()
+        .map(|language| language.to_string())
+        .collect::<Vec<_>>();
+    let current_locale = NSLocale::currentLocale().localeIdentifier().to_string();
+
+    SystemLocale::from_identifiers(
+        preferred_languages.iter().map(String::as_str),
+        &current_locale,
+    )
+}
+
+pub fn setup_apple_current_locale_changed_listener(cx: &mut App) {
+    let (tx, rx) = async_channel::bounded(3);
+
+    unsafe {
+        cx.set_global(AppleCurrentLocaleChangedListenerGlobalableWrapperThing {
+            globalable_item: AppleCurrentLocaleChangedListener::new(tx),
+        });
+
+        let listener_wrapper =
+            cx.global::<AppleCurrentLocaleChangedListenerGlobalableWrapperThing>();
+
+        let notification_center = NSNotificationCenter::defaultCenter();
+        notification_center.addObserver_selector_name_object(
+            &listener_wrapper.globalable_item,
+            sel!(currentLocaleDidChange:),
+            Some(NSCurrentLocaleDidChangeNotification),
+            None,
+        )
+    }
+
+    cx.spawn(async move |cx: &mut AsyncApp| {
+        loop {
+            _ = rx.recv().await;
+
+            cx.update(|cx| system_locale_changed(apple_system_locale(), cx));
+        }
+    })
+    .detach()
+}
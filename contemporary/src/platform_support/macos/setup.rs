@@ -1,3 +1,4 @@
+use crate::platform_support::macos::apple_current_locale_changed_listener::setup_apple_current_locale_changed_listener;
 use crate::platform_support::macos::apple_interface_theme_changed_listener::setup_apple_interface_theme_changed_listener;
 use crate::platform_support::macos::apple_workspace_a11y_options_changed_listener::setup_apple_workspace_a11y_options_changed_listener;
 use crate::platform_support::macos::notification::setup_apple_notifications;
@@ -6,6 +7,7 @@ use gpui::App;
 pub fn setup_macos(cx: &mut App) {
     setup_apple_interface_theme_changed_listener(cx);
     setup_apple_workspace_a11y_options_changed_listener(cx);
+    setup_apple_current_locale_changed_listener(cx);
 
     setup_apple_notifications(cx);
 }
@@ -2,4 +2,5 @@ mod cx_platform_windows_extensions;
 pub mod platform_settings;
 pub mod setup;
 mod setup_windows_color_values_changed_listener;
+mod setup_windows_locale_changed_listener;
 pub mod theme;
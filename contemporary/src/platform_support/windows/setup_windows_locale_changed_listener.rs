@@ -0,0 +1,138 @@
+use crate::language::{SystemLocale, system_locale_changed};
+use async_channel::Sender;
+use gpui::{App, AsyncApp};
+use std::sync::OnceLock;
+use std::thread;
+use tracing::error;
+use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+use windows::Win32::Globalization::{
+    GetUserDefaultLocaleName, GetUserPreferredUILanguages, MUI_LANGUAGE_NAME,
+};
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::UI::WindowsAndMessaging::{
+    CreateWindowExW, DefWindowProcW, DispatchMessageW, GetMessageW, MSG, RegisterClassW,
+    WINDOW_EX_STYLE, WINDOW_STYLE, WM_SETTINGCHANGE, WNDCLASSW,
+};
+use windows::core::{PCWSTR, PWSTR, w};
+
+const LOCALE_NAME_MAX_LENGTH: usize = 85;
+
+static LOCALE_CHANGED_TX: OnceLock<Sender<()>> = OnceLock::new();
+
+extern "system" fn window_proc(hwnd: HWND, msg: u32, wparam: WPARAM, lparam: LPARAM) -> LRESULT {
+    // The international settings are broadcast with the "intl" section name
+    if msg == WM_SETTINGCHANGE
+        && lparam.0 != 0
+        && unsafe { PCWSTR(lparam.0 as *const u16).to_string() }.is_ok_and(|area| area == "intl")
+        && let Some(tx) = LOCALE_CHANGED_TX.get()
+    {
+        _ = tx.try_send(());
+    }
+
+    unsafe { DefWindowProcW(hwnd, msg, wparam, lparam) }
+}
+
+/// Read the locale preferences of the user from Windows.
+fn windows_system_locale() -> SystemLocale {
+    let mut language_count = 0;
+    let mut buffer_length = 0;
+    let mut preferred_languages = Vec::new();
+    unsafe {
+        if GetUserPreferredUILanguages(
+            MUI_LANGUAGE_NAME,
+            &mut language_count,
+            None,
+            &mut buffer_length,
+        )
+        .is_ok()
+        {
+            let mut buffer = vec![0u16; buffer_length as usize];
+            if GetUserPreferredUILanguages(
+                MUI_LANGUAGE_NAME,
+                &mut language_count,
+                Some(PWSTR(buffer.as_mut_ptr())),
+                &mut buffer_length,
+            )
+            .is_ok()
+            {
+                // The languages are separated by null characters
+                preferred_languages = buffer
+                    .split(|&c| c == 0)
+                    .filter(|language| !language.is_empty())
+                    .map(String::from_utf16_lossy)
+                    .collect();
+            }
+        }
+    }
+
+    let mut locale_name = [0u16; LOCALE_NAME_MAX_LENGTH];
+    let locale_name_length = unsafe { GetUserDefaultLocaleName(&mut locale_name) };
+    // The length includes the null terminator, and is 0 on failure
+    let current_locale =
+        String::from_utf16_lossy(&locale_name[..(locale_name_length as usize).saturating_sub(1)]);
+
+    SystemLocale::from_identifiers(
+        preferred_languages.iter().map(String::as_str),
+        &current_locale,
+    )
+}
+
+pub fn setup_windows_locale_changed_listener(cx: &mut App) {
+    let (tx, rx) = async_channel::bounded(3);
+    if LOCALE_CHANGED_TX.set(tx).is_err() {
+        return;
+    }
+
+    // Settings changes are only broadcast to top level windows, so create a hidden window on
+    // its own thread to receive them
+    let spawn_result = thread::Builder::new()
+        .name("contemporary-locale-listener".into())
+        .spawn(|| unsafe {
+            let Ok(instance) = GetModuleHandleW(None) else {
+                return;
+            };
+
+            let class_name = w!("ContemporaryLocaleChangedListener");
+            RegisterClassW(&WNDCLASSW {
+                lpfnWndProc: Some(window_proc),
+                hInstance: instance.into(),
+                lpszClassName: class_name,
+                ..Default::default()
+            });
+
+            if let Err(e) = CreateWindowExW(
+                WINDOW_EX_STYLE::default(),
+                class_name,
+                w!(""),
+                WINDOW_STYLE::default(),
+                0,
+                0,
+                0,
+                0,
+                None,
+                None,
+                Some(instance.into()),
+                None,
+            ) {
+                error!("Unable to listen for locale changes: {:?}", e);
+                return;
+            }
+
+            let mut message = MSG::default();
+            while GetMessageW(&mut message, None, 0, 0).as_bool() {
+                DispatchMessageW(&message);
+            }
+        });
+    if let Err(e) = spawn_result {
+        error!("Unable to listen for locale changes: {:?}", e);
+    }
+
+    cx.spawn(async move |cx: &mut AsyncApp| {
+        loop {
+            _ = rx.recv().await;
+
+            cx.update(|cx| system_locale_changed(windows_system_locale(), cx));
+        }
+    })
+    .detach()
+}
@@ -2,13 +2,14 @@ use crate::application::{ApplicationLink, Details, Versions};
 use crate::components::context_menu::bind_context_menu_keys;
 use crate::components::text_field::bind_text_field_keys;
 use crate::jobs::job_manager::JobManager;
+use crate::language::{ChosenLanguage, SystemLocale, apply_language, saved_language};
 use crate::platform_support::platform_settings::PlatformSettings;
 use crate::platform_support::setup_platform;
 use crate::styling::theme::Theme;
 use crate::tokio::tokio_helper::TokioHelper;
 use crate::tracing::application_log::ApplicationLog;
 use crate::window::window_globals::WindowGlobals;
-use cntp_i18n::{I18N_MANAGER, i18n_manager, tr, tr_load};
+use cntp_i18n::{I18N_MANAGER, i18n_manager, missing_translations, tr, tr_load, tr_watch};
 use gpui::{Action, App, AsyncApp, Global, KeyBinding, Menu, MenuItem, SystemMenuType, actions};
 use schemars::JsonSchema;
 use serde::Deserialize;
@@ -93,6 +94,13 @@ pub fn setup_contemporary(cx: &mut App, mut application: Contemporary) {
     }
 
     I18N_MANAGER.write().unwrap().load_source(tr_load!());
+    tr_watch!();
+    missing_translations::install_if_enabled();
+    let system_locale = SystemLocale::current();
+    let chosen_language = saved_language(&application.details);
+    apply_language(chosen_language.as_deref(), &system_locale);
+    cx.set_global(system_locale);
+    cx.set_global(ChosenLanguage(chosen_language));
 
     let (i18n_cache_eviction_tx, i18n_cache_eviction_rx) = async_channel::bounded(1);
     cx.spawn(async move |cx: &mut AsyncApp| {
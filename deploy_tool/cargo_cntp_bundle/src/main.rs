@@ -11,7 +11,7 @@ use std::process::exit;
 use tracing::{error, info};
 
 #[cfg(target_os = "linux")]
-use cntp_bundle_lib::linux::bundle_linux;
+use cntp_bundle_lib::linux::{bundle_linux, validate::validate_linux_bundle};
 
 #[cfg(target_os = "macos")]
 use cntp_bundle_lib::macos::bundle::bundle_macos;
@@ -45,6 +45,10 @@ struct Args {
     /// Don't open the output directory after deployment is complete
     #[clap(long, default_value_t = false)]
     no_open: bool,
+
+    /// Treat validation warnings as errors
+    #[clap(long, default_value_t = false)]
+    strict: bool,
 }
 
 fn main() {
@@ -104,7 +108,15 @@ fn main() {
     match setup_data.deployment_type {
         DeploymentType::Linux => {
             #[cfg(target_os = "linux")]
-            bundle_linux(&setup_data, bin_targets);
+            {
+                bundle_linux(&setup_data, bin_targets);
+
+                info!("Validating bundle");
+                if !validate_linux_bundle(&setup_data).report(args.strict) {
+                    error!("Bundle validation failed");
+                    exit(1);
+                }
+            }
 
             #[cfg(not(target_os = "linux"))]
             {
@@ -8,7 +8,7 @@ use std::process::exit;
 use tracing::{Level, error, info};
 
 #[cfg(target_os = "linux")]
-use cntp_bundle_lib::linux::deploy::deploy_linux;
+use cntp_bundle_lib::linux::deploy::{deploy_linux, uninstall_linux};
 
 #[cfg(target_os = "macos")]
 use cntp_bundle_lib::macos::deploy::deploy_macos;
@@ -45,8 +45,18 @@ struct Args {
     #[clap(short = 's', long)]
     platform_subtype: Option<String>,
 
+    /// The file to write the deployment to. For rootdir deployments, this is the staging directory
+    /// (DESTDIR) that the install prefix is created under.
     #[arg(short, long)]
     output_file: String,
+
+    /// The install prefix for rootdir deployments, e.g. /usr or /opt/<app>
+    #[clap(long)]
+    prefix: Option<String>,
+
+    /// Remove a previous rootdir deployment using its install manifest
+    #[clap(long, default_value_t = false)]
+    uninstall: bool,
 }
 
 fn main() {
@@ -74,10 +84,40 @@ fn main() {
     info!("Target:  {}", setup_data.targets.join(";"));
     info!("Output:  {}", args.output_file);
 
+    if args.uninstall {
+        match setup_data.deployment_type {
+            DeploymentType::Linux => {
+                #[cfg(target_os = "linux")]
+                uninstall_linux(
+                    &setup_data,
+                    &args.platform_subtype,
+                    &args.output_file,
+                    &args.prefix,
+                );
+
+                #[cfg(not(target_os = "linux"))]
+                {
+                    error!("Unable to uninstall for Linux on non-Linux platform");
+                    exit(1);
+                }
+            }
+            _ => {
+                error!("Uninstalling is only supported for Linux rootdir deployments");
+                exit(1);
+            }
+        }
+        return;
+    }
+
     match setup_data.deployment_type {
         DeploymentType::Linux => {
             #[cfg(target_os = "linux")]
-            deploy_linux(&setup_data, &args.platform_subtype, &args.output_file);
+            deploy_linux(
+                &setup_data,
+                &args.platform_subtype,
+                &args.output_file,
+                &args.prefix,
+            );
 
             #[cfg(not(target_os = "linux"))]
             {
@@ -0,0 +1,259 @@
+//! # cargo-cntp-new
+//!
+//! A Cargo subcommand for creating new Contemporary applications.
+//!
+//! ```bash
+//! cargo cntp-new my-app
+//! cargo cntp-new --init
+//! ```
+//!
+//! The generated project contains a `Contemporary.toml`, an `i18n.toml`, a `build.rs` that
+//! generates translation files, a base icon and a window set up with `setup_contemporary`. It
+//! builds and bundles without any further edits.
+//!
+//! Any details not passed on the command line are prompted for, unless `--yes` is passed or
+//! standard input is not a terminal, in which case defaults are used.
+
+mod project;
+
+use crate::project::{
+    ContemporarySource, ProjectDetails, default_application_name, default_desktop_entry,
+    validate_desktop_entry, validate_package_name, validate_theme_color,
+};
+use chrono::Datelike;
+use clap::Parser;
+use clap_cargo::style::CLAP_STYLING;
+use clap_verbosity_flag::InfoLevel;
+use std::fs::{canonicalize, read_dir};
+use std::io::{IsTerminal, Write, stdin, stdout};
+use std::path::PathBuf;
+use std::process::exit;
+use tracing::{error, info};
+
+#[derive(Parser, Debug)]
+#[command(name = "cargo cntp-new")] // all of this is necessary so things work as expected wrt. cargo
+#[command(bin_name = "cargo")]
+#[command(styles = CLAP_STYLING)]
+enum Command {
+    CntpNew(Args),
+}
+
+#[derive(Parser, Debug)]
+struct Args {
+    /// The directory to create the project in
+    path: Option<PathBuf>,
+
+    /// Create the project in an existing directory
+    #[arg(long)]
+    init: bool,
+
+    /// How loud should we be?
+    #[clap(flatten)]
+    verbosity: clap_verbosity_flag::Verbosity<InfoLevel>,
+
+    /// The package name. Defaults to the name of the directory
+    #[arg(long)]
+    package_name: Option<String>,
+
+    /// The human readable name of the application
+    #[arg(short, long)]
+    name: Option<String>,
+
+    /// The organization that develops the application
+    #[arg(short, long)]
+    organization: Option<String>,
+
+    /// The desktop entry ID of the application, in reverse DNS format
+    #[arg(short, long)]
+    desktop_entry: Option<String>,
+
+    /// The two theme colors of the application, in the format #rrggbb
+    #[arg(short, long, num_args = 2, value_names = ["COLOR_1", "COLOR_2"])]
+    theme_colors: Option<Vec<String>>,
+
+    /// Depend on a local checkout of Contemporary instead of the Git repository
+    #[arg(long)]
+    contemporary_path: Option<PathBuf>,
+
+    /// Use defaults for anything not passed on the command line instead of prompting
+    #[arg(short, long)]
+    yes: bool,
+}
+
+fn main() {
+    let Command::CntpNew(args) = Command::parse();
+
+    tracing_subscriber::fmt()
+        .with_target(false)
+        .without_time()
+        .with_max_level(args.verbosity.tracing_level())
+        .init();
+
+    let path = match (&args.path, args.init) {
+        (Some(path), _) => path.clone(),
+        (None, true) => PathBuf::from("."),
+        (None, false) => {
+            error!("No path specified. Pass --init to create a project in the current directory");
+            exit(1);
+        }
+    };
+
+    if !args.init
+        && let Ok(mut entries) = read_dir(&path)
+        && entries.next().is_some()
+    {
+        error!(
+            "{} already exists and is not empty. Pass --init to create a project in an existing directory",
+            path.display()
+        );
+        exit(1);
+    }
+
+    let contemporary_source = match args.contemporary_path {
+        Some(contemporary_path) => {
+            let Ok(contemporary_path) = canonicalize(&contemporary_path) else {
+                error!(
+                    "Unable to find Contemporary checkout at {}",
+                    contemporary_path.display()
+                );
+                exit(1);
+            };
+            ContemporarySource::Path(contemporary_path)
+        }
+        None => ContemporarySource::Git,
+    };
+
+    let interactive = !args.yes && stdin().is_terminal();
+
+    let directory_name = canonicalize(&path)
+        .unwrap_or_else(|_| path.clone())
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let package_name = ask(
+        interactive,
+        "Package name",
+        args.package_name,
+        directory_name,
+        validate_package_name,
+    );
+
+    let application_name = ask(
+        interactive,
+        "Application name",
+        args.name,
+        default_application_name(&package_name),
+        |name| {
+            if name.trim().is_empty() {
+                Err("The application name cannot be empty".into())
+            } else {
+                Ok(())
+            }
+        },
+    );
+
+    let organization_name = ask(
+        interactive,
+        "Organization",
+        args.organization,
+        application_name.clone(),
+        |_| Ok(()),
+    );
+
+    let desktop_entry = ask(
+        interactive,
+        "Desktop entry ID",
+        args.desktop_entry,
+        default_desktop_entry(&organization_name, &package_name),
+        validate_desktop_entry,
+    );
+
+    let (theme_color_1, theme_color_2) = match args.theme_colors.as_deref() {
+        Some([theme_color_1, theme_color_2]) => {
+            (Some(theme_color_1.clone()), Some(theme_color_2.clone()))
+        }
+        _ => (None, None),
+    };
+    let theme_colors = [
+        ask(
+            interactive,
+            "First theme color",
+            theme_color_1,
+            "#6400ff".into(),
+            validate_theme_color,
+        ),
+        ask(
+            interactive,
+            "Second theme color",
+            theme_color_2,
+            "#c800ff".into(),
+            validate_theme_color,
+        ),
+    ];
+
+    let project = ProjectDetails {
+        package_name,
+        application_name,
+        organization_name,
+        desktop_entry,
+        theme_colors,
+        contemporary_source,
+        copyright_year: chrono::Local::now().year().to_string(),
+    };
+
+    if let Err(e) = project.write(&path) {
+        error!("Failed to create project: {}", e);
+        exit(1);
+    }
+
+    info!("Created {} in {}", project.application_name, path.display());
+    info!("Run `cargo run` to start it, or `cargo cntp-bundle` to bundle it");
+}
+
+/// Returns the value passed on the command line, or prompts for one if running interactively.
+fn ask(
+    interactive: bool,
+    prompt: &str,
+    value: Option<String>,
+    default: String,
+    validate: impl Fn(&str) -> Result<(), String>,
+) -> String {
+    if let Some(value) = value {
+        if let Err(e) = validate(&value) {
+            error!("{}", e);
+            exit(1);
+        }
+        return value;
+    }
+
+    if !interactive {
+        if let Err(e) = validate(&default) {
+            error!("{}", e);
+            exit(1);
+        }
+        return default;
+    }
+
+    loop {
+        print!("{prompt} [{default}]: ");
+        stdout().flush().unwrap();
+
+        let mut line = String::new();
+        if stdin().read_line(&mut line).unwrap_or(0) == 0 {
+            error!("No input received");
+            exit(1);
+        }
+
+        let line = line.trim();
+        let value = if line.is_empty() {
+            default.clone()
+        } else {
+            line.to_string()
+        };
+
+        match validate(&value) {
+            Ok(_) => return value,
+            Err(e) => println!("{e}"),
+        }
+    }
+}
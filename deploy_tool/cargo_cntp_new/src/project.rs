@@ -0,0 +1,309 @@
+use std::fs::{create_dir_all, write};
+use std::io;
+use std::path::{Path, PathBuf};
+
+const CONTEMPORARY_GIT_URL: &str = "https://github.com/vicr123/contemporary-rs";
+
+/// The files that make up a new project, relative to the project root
+const TEMPLATES: &[(&str, &str)] = &[
+    (
+        "Cargo.toml",
+        include_str!("../templates/Cargo.toml.template"),
+    ),
+    (
+        "Contemporary.toml",
+        include_str!("../templates/Contemporary.toml"),
+    ),
+    ("i18n.toml", include_str!("../templates/i18n.toml")),
+    ("build.rs", include_str!("../templates/build.rs")),
+    (".gitignore", include_str!("../templates/gitignore")),
+    (
+        "dist/baseicon.svg",
+        include_str!("../templates/dist/baseicon.svg"),
+    ),
+    ("src/main.rs", include_str!("../templates/src/main.rs")),
+    (
+        "src/main_window.rs",
+        include_str!("../templates/src/main_window.rs"),
+    ),
+];
+
+pub enum ContemporarySource {
+    Git,
+    /// A local checkout of the Contemporary repository
+    Path(PathBuf),
+}
+
+impl ContemporarySource {
+    fn dependency(&self, crate_path: &str) -> String {
+        match self {
+            ContemporarySource::Git => format!("git = \"{CONTEMPORARY_GIT_URL}\""),
+            ContemporarySource::Path(path) => {
+                format!(
+                    "path = \"{}\"",
+                    escape(&path.join(crate_path).to_string_lossy())
+                )
+            }
+        }
+    }
+}
+
+pub struct ProjectDetails {
+    pub package_name: String,
+    pub application_name: String,
+    pub organization_name: String,
+    pub desktop_entry: String,
+    pub theme_colors: [String; 2],
+    pub contemporary_source: ContemporarySource,
+    pub copyright_year: String,
+}
+
+impl ProjectDetails {
+    /// Renders every template, returning the relative path and contents of each file.
+    pub fn files(&self) -> Vec<(&'static str, String)> {
+        let replacements = [
+            ("{{PACKAGE_NAME}}", escape(&self.package_name)),
+            ("{{APPLICATION_NAME}}", escape(&self.application_name)),
+            ("{{ORGANIZATION_NAME}}", escape(&self.organization_name)),
+            ("{{DESKTOP_ENTRY}}", escape(&self.desktop_entry)),
+            ("{{THEME_COLOR_1}}", escape(&self.theme_colors[0])),
+            ("{{THEME_COLOR_2}}", escape(&self.theme_colors[1])),
+            ("{{COPYRIGHT_YEAR}}", escape(&self.copyright_year)),
+            (
+                "{{CONTEMPORARY_SOURCE}}",
+                self.contemporary_source.dependency("contemporary"),
+            ),
+            (
+                "{{CNTP_I18N_SOURCE}}",
+                self.contemporary_source.dependency("i18n/cntp_i18n"),
+            ),
+            (
+                "{{CNTP_I18N_GEN_SOURCE}}",
+                self.contemporary_source.dependency("i18n/cntp_i18n_gen"),
+            ),
+            (
+                "{{CNTP_ICON_TOOL_MACROS_SOURCE}}",
+                self.contemporary_source
+                    .dependency("icon_tool/cntp_icon_tool_macros"),
+            ),
+        ];
+
+        TEMPLATES
+            .iter()
+            .map(|(path, template)| {
+                let contents = replacements
+                    .iter()
+                    .fold(template.to_string(), |contents, (placeholder, value)| {
+                        contents.replace(placeholder, value)
+                    });
+                (*path, contents)
+            })
+            .collect()
+    }
+
+    /// Writes the project into root, refusing to overwrite any existing files.
+    pub fn write(&self, root: &Path) -> io::Result<()> {
+        let files = self.files();
+
+        let existing_files: Vec<_> = files
+            .iter()
+            .map(|(path, _)| *path)
+            .filter(|path| root.join(path).exists())
+            .collect();
+        if !existing_files.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                format!(
+                    "the following files already exist: {}",
+                    existing_files.join(", ")
+                ),
+            ));
+        }
+
+        for (path, contents) in files {
+            let path = root.join(path);
+            create_dir_all(path.parent().unwrap())?;
+            write(path, contents)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Escapes a value so that it can be placed inside both Rust and TOML string literals.
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+pub fn validate_package_name(name: &str) -> Result<(), String> {
+    let Some(first) = name.chars().next() else {
+        return Err("The package name cannot be empty".into());
+    };
+
+    if !first.is_ascii_alphabetic() && first != '_' {
+        return Err("The package name must start with a letter or underscore".into());
+    }
+
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        return Err(
+            "The package name can only contain letters, numbers, underscores and hyphens".into(),
+        );
+    }
+
+    Ok(())
+}
+
+pub fn validate_desktop_entry(desktop_entry: &str) -> Result<(), String> {
+    let elements: Vec<_> = desktop_entry.split('.').collect();
+    if elements.len() < 2 {
+        return Err(
+            "The desktop entry ID must be in reverse DNS format, e.g. com.example.MyApp".into(),
+        );
+    }
+
+    if desktop_entry.len() > 255 {
+        return Err("The desktop entry ID must not be longer than 255 characters".into());
+    }
+
+    for element in elements {
+        let Some(first) = element.chars().next() else {
+            return Err("The desktop entry ID cannot contain empty elements".into());
+        };
+
+        if first.is_ascii_digit() {
+            return Err(format!(
+                "The desktop entry ID element \"{element}\" must not start with a digit"
+            ));
+        }
+
+        if !element
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+        {
+            return Err(format!(
+                "The desktop entry ID element \"{element}\" can only contain letters, numbers, underscores and hyphens"
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+pub fn validate_theme_color(color: &str) -> Result<(), String> {
+    let is_valid = color
+        .strip_prefix('#')
+        .is_some_and(|hex| hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()));
+    if !is_valid {
+        return Err(format!(
+            "The theme color \"{color}\" must be in the format #rrggbb"
+        ));
+    }
+
+    Ok(())
+}
+
+/// Turns a package name such as `my-app` into an application name such as `My App`.
+pub fn default_application_name(package_name: &str) -> String {
+    package_name
+        .split(['-', '_'])
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut chars = word.chars();
+            let first = chars.next().unwrap();
+            first.to_uppercase().chain(chars).collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Derives a desktop entry ID such as `com.theorganization.my_app`.
+pub fn default_desktop_entry(organization_name: &str, package_name: &str) -> String {
+    format!(
+        "com.{}.{}",
+        desktop_entry_element(organization_name),
+        desktop_entry_element(package_name)
+    )
+}
+
+fn desktop_entry_element(value: &str) -> String {
+    let element: String = value
+        .chars()
+        .filter_map(|c| match c {
+            c if c.is_ascii_alphanumeric() => Some(c.to_ascii_lowercase()),
+            '-' | '_' => Some('_'),
+            _ => None,
+        })
+        .collect();
+
+    if element.is_empty() || element.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{element}")
+    } else {
+        element
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::project::{
+        ContemporarySource, ProjectDetails, default_application_name, default_desktop_entry,
+        validate_desktop_entry,
+    };
+    use tempfile::TempDir;
+
+    fn details() -> ProjectDetails {
+        ProjectDetails {
+            package_name: "my-app".into(),
+            application_name: "My \"Quoted\" App".into(),
+            organization_name: "The Organization".into(),
+            desktop_entry: "com.theorganization.my_app".into(),
+            theme_colors: ["#6400ff".into(), "#c800ff".into()],
+            contemporary_source: ContemporarySource::Git,
+            copyright_year: "2026".into(),
+        }
+    }
+
+    #[test]
+    fn rendered_files_have_no_placeholders() {
+        for (path, contents) in details().files() {
+            assert!(!contents.contains("{{"), "{path} has a placeholder left");
+        }
+    }
+
+    #[test]
+    fn rendered_manifests_are_valid_toml() {
+        let files = details().files();
+        for (path, contents) in files.iter().filter(|(path, _)| path.ends_with(".toml")) {
+            let value: toml::Table = toml::from_str(contents)
+                .unwrap_or_else(|e| panic!("{path} is not valid TOML: {e}"));
+            if *path == "Contemporary.toml" {
+                assert_eq!(
+                    value["deployment"]["application_name"].as_str(),
+                    Some("My \"Quoted\" App")
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn write_refuses_to_overwrite() {
+        let temp_dir = TempDir::new().unwrap();
+        details().write(temp_dir.path()).unwrap();
+        assert!(temp_dir.path().join("dist/baseicon.svg").exists());
+        assert!(details().write(temp_dir.path()).is_err());
+    }
+
+    #[test]
+    fn defaults() {
+        assert_eq!(default_application_name("my-cool_app"), "My Cool App");
+
+        let desktop_entry = default_desktop_entry("The Organization", "my-app");
+        assert_eq!(desktop_entry, "com.theorganization.my_app");
+        assert!(validate_desktop_entry(&desktop_entry).is_ok());
+        assert!(validate_desktop_entry(&default_desktop_entry("3M", "app")).is_ok());
+        assert!(validate_desktop_entry("com.example.1app").is_err());
+        assert!(validate_desktop_entry("myapp").is_err());
+    }
+}
@@ -0,0 +1,84 @@
+// On Windows do NOT show a console window when opening the app
+#![cfg_attr(all(not(test), target_os = "windows"), windows_subsystem = "windows")]
+
+use crate::main_window::MainWindow;
+use cntp_i18n::{I18N_MANAGER, tr_load, tr_watch};
+use cntp_icon_tool_macros::application_icon;
+use contemporary::application::new_contemporary_application;
+use contemporary::macros::application_details;
+use contemporary::{
+    application::{Details, License},
+    setup::{Contemporary, ContemporaryMenus, setup_contemporary},
+    window::contemporary_window_options,
+};
+use gpui::{App, Bounds, WeakEntity, WindowBounds, WindowOptions, px, size};
+use indexmap::IndexMap;
+use smol_macros::main;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+mod main_window;
+
+fn mane() {
+    application_icon!("../dist/baseicon.svg");
+    new_contemporary_application().run(|cx: &mut App| {
+        I18N_MANAGER.write().unwrap().load_source(tr_load!());
+        tr_watch!();
+        let bounds = Bounds::centered(None, size(px(800.0), px(600.0)), cx);
+
+        let outer_window: Rc<RefCell<WeakEntity<MainWindow>>> =
+            Rc::new(RefCell::new(WeakEntity::new_invalid()));
+
+        setup_contemporary(
+            cx,
+            Contemporary {
+                details: Details {
+                    generatable: application_details!(),
+                    copyright_holder: "{{ORGANIZATION_NAME}}",
+                    copyright_year: "{{COPYRIGHT_YEAR}}",
+                    application_version: "0.1.0",
+                    license: License::Gpl3OrLater,
+                    links: IndexMap::new(),
+                },
+                menus: ContemporaryMenus {
+                    menus: vec![],
+                    on_about: Rc::new({
+                        let outer_window = outer_window.clone();
+                        move |cx| {
+                            outer_window
+                                .borrow()
+                                .upgrade()
+                                .unwrap()
+                                .update(cx, |window, cx| {
+                                    window.about_surface_open(true);
+                                    cx.notify()
+                                })
+                        }
+                    }),
+                    on_settings: None,
+                },
+            },
+        );
+
+        let default_window_options = contemporary_window_options(cx, "{{APPLICATION_NAME}}");
+        cx.open_window(
+            WindowOptions {
+                window_bounds: Some(WindowBounds::Windowed(bounds)),
+                ..default_window_options
+            },
+            |_, cx| {
+                let window = MainWindow::new(cx);
+                *outer_window.borrow_mut() = window.downgrade();
+                window
+            },
+        )
+        .unwrap();
+        cx.activate(true);
+    });
+}
+
+main! {
+    async fn main() {
+        mane()
+    }
+}
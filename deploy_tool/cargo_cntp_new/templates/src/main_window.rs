@@ -0,0 +1,57 @@
+use cntp_i18n::tr;
+use contemporary::about_surface::about_surface;
+use contemporary::components::grandstand::grandstand;
+use contemporary::components::pager::lift_animation::LiftAnimation;
+use contemporary::components::pager::pager;
+use contemporary::surface::surface;
+use contemporary::window::contemporary_window;
+use gpui::{
+    App, AppContext, Context, Entity, IntoElement, ParentElement, Render, Styled, Window, div, px,
+};
+
+pub struct MainWindow {
+    is_about_surface_open: bool,
+}
+
+impl MainWindow {
+    pub fn new(cx: &mut App) -> Entity<MainWindow> {
+        cx.new(|_| MainWindow {
+            is_about_surface_open: false,
+        })
+    }
+
+    pub fn about_surface_open(&mut self, is_open: bool) -> &Self {
+        self.is_about_surface_open = is_open;
+        self
+    }
+}
+
+impl Render for MainWindow {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        contemporary_window().child(
+            pager("main-pager", if self.is_about_surface_open { 1 } else { 0 })
+                .w_full()
+                .h_full()
+                .animation(LiftAnimation::new())
+                .page(
+                    surface()
+                        .child(
+                            div().flex().flex_col().w_full().h_full().child(
+                                grandstand("main-grandstand")
+                                    .text(tr!("MAIN_WINDOW_TITLE", "{{APPLICATION_NAME}}"))
+                                    .pt(px(36.)),
+                            ),
+                        )
+                        .into_any_element(),
+                )
+                .page(
+                    about_surface()
+                        .on_back_click(cx.listener(|this, _, _, cx| {
+                            this.is_about_surface_open = false;
+                            cx.notify();
+                        }))
+                        .into_any_element(),
+                ),
+        )
+    }
+}
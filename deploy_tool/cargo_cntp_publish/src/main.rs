@@ -0,0 +1,164 @@
+use clap::Parser;
+use clap_cargo::style::CLAP_STYLING;
+use clap_verbosity_flag::InfoLevel;
+
+use cntp_bundle_lib::publish::BinChickenUpload;
+use cntp_bundle_lib::tool_setup::{DeploymentType, setup_tool};
+use std::path::PathBuf;
+use std::process::exit;
+use tracing::{Level, error, info};
+
+#[derive(Parser, Debug)]
+#[command(name = "cargo cntp-publish")] // all of this is necessary so things work as expected wrt. cargo
+#[command(bin_name = "cargo")]
+#[command(styles = CLAP_STYLING)]
+enum Command {
+    CntpPublish(Args),
+}
+
+#[derive(Parser, Debug)]
+struct Args {
+    /// The profile that was built
+    #[arg(short, long)]
+    profile: Option<String>,
+
+    /// The targets that were built
+    #[arg(short, long)]
+    target: Vec<String>,
+
+    /// How loud should we be?
+    #[clap(flatten)]
+    verbosity: clap_verbosity_flag::Verbosity<InfoLevel>,
+
+    /// The deployed artifact to upload
+    #[arg(short, long)]
+    artifact: PathBuf,
+
+    /// The minisign signature of the artifact. Defaults to the artifact path with .minisig appended
+    #[arg(short, long)]
+    signature: Option<PathBuf>,
+
+    /// The channel to publish to. Defaults to "blueprint" for blueprint builds and "stable" otherwise
+    #[arg(short, long)]
+    channel: Option<String>,
+
+    /// The version to publish this artifact as. Defaults to the package version for non-blueprint builds
+    #[arg(long)]
+    artifact_version: Option<String>,
+
+    /// The Bin Chicken target to publish this artifact as. Defaults to the target triple
+    #[arg(long)]
+    publish_target: Option<String>,
+
+    /// Override the platform UUID declared in Contemporary.toml
+    #[arg(long)]
+    uuid: Option<String>,
+
+    /// Override the Bin Chicken URL declared in Contemporary.toml
+    #[arg(long)]
+    url: Option<String>,
+
+    /// Override the Bin Chicken repository declared in Contemporary.toml
+    #[arg(long)]
+    repository: Option<String>,
+}
+
+fn main() {
+    let Command::CntpPublish(args) = Command::parse();
+
+    tracing_subscriber::fmt()
+        .with_target(false)
+        .without_time()
+        .with_max_level(args.verbosity.tracing_level().or_else(|| {
+            if std::env::var("RUNNER_DEBUG").is_ok_and(|runner_debug| runner_debug == "1") {
+                Some(Level::DEBUG)
+            } else {
+                None
+            }
+        }))
+        .init();
+
+    let setup_data = setup_tool(args.profile, args.target, "bundle");
+    let deployment = setup_data
+        .contemporary_config
+        .deployment(setup_data.targets.first().unwrap());
+
+    let Some(url) = args.url.or(deployment.bin_chicken_url) else {
+        error!("No Bin Chicken URL specified in config");
+        exit(1);
+    };
+
+    let Some(repository) = args.repository.or(deployment.bin_chicken_repository) else {
+        error!("No Bin Chicken repository specified in config");
+        exit(1);
+    };
+
+    let Some(uuid) = args.uuid.or(deployment.bin_chicken_uuid) else {
+        error!("No Bin Chicken UUID specified in config");
+        exit(1);
+    };
+
+    let is_blueprint = setup_data.contemporary_config.is_blueprint();
+    let channel = args.channel.unwrap_or_else(|| {
+        if is_blueprint {
+            "blueprint".into()
+        } else {
+            "stable".into()
+        }
+    });
+
+    let version = args.artifact_version.or_else(|| {
+        if is_blueprint {
+            None
+        } else {
+            let (major, minor, patch) = setup_data.version;
+            Some(format!("{major}.{minor}.{patch}"))
+        }
+    });
+
+    let target = args.publish_target.unwrap_or_else(|| {
+        if matches!(setup_data.deployment_type, DeploymentType::MacOS)
+            && setup_data.targets.len() > 1
+        {
+            "universal-apple-darwin".into()
+        } else {
+            setup_data.targets.join(";")
+        }
+    });
+
+    let signature = args.signature.unwrap_or_else(|| {
+        let mut signature = args.artifact.clone().into_os_string();
+        signature.push(".minisig");
+        signature.into()
+    });
+
+    info!(
+        "Publishing {}",
+        setup_data.cargo_metadata.root_package().unwrap().name
+    );
+    info!("Artifact:   {}", args.artifact.display());
+    info!("Repository: {}", repository);
+    info!("Channel:    {}", channel);
+    info!("Target:     {}", target);
+    info!("Version:    {}", version.as_deref().unwrap_or("(none)"));
+
+    let upload = BinChickenUpload {
+        url,
+        repository,
+        uuid,
+        channel,
+        target,
+        version,
+        // Read from the environment so that the token doesn't show up in the process list
+        token: std::env::var("BIN_CHICKEN_TOKEN").ok(),
+        artifact: args.artifact,
+        signature,
+    };
+
+    if let Err(e) = upload.upload() {
+        error!("Failed to publish artifact: {}", e);
+        exit(1);
+    }
+
+    info!("Artifact published successfully");
+}
@@ -7,7 +7,9 @@ pub mod linux;
 #[cfg(target_os = "macos")]
 pub mod macos;
 
+pub mod publish;
 pub mod tool_setup;
+pub mod validation;
 #[cfg(target_os = "windows")]
 pub mod windows;
 
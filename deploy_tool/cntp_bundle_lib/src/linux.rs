@@ -1,6 +1,7 @@
 mod appimage;
 pub mod deploy;
 mod rootdir;
+pub mod validate;
 
 use crate::icon::get_svg_icon_contents;
 use crate::tool_setup::ToolSetup;
@@ -99,6 +100,7 @@ pub fn bundle_linux(setup_data: &ToolSetup, executable_path: HashMap<String, Pat
         target_triple,
         executable_path,
         &setup_data.contemporary_config,
+        None,
     ) else {
         error!("Failed to generate desktop entry");
         exit(1);
@@ -119,6 +121,49 @@ pub fn bundle_linux(setup_data: &ToolSetup, executable_path: HashMap<String, Pat
         exit(1);
     };
 
+    if deployment.dbus_activatable {
+        let appdir_dbus_services = appdir_share.join("dbus-1").join("services");
+        let Ok(_) = create_dir_all(&appdir_dbus_services) else {
+            error!("Failed to create appdir D-Bus services folder");
+            exit(1);
+        };
+
+        let Ok(_) = write(
+            appdir_dbus_services.join(desktop_entry.clone() + ".service"),
+            generate_dbus_service(&desktop_entry, executable_path),
+        ) else {
+            error!("Failed to write D-Bus service file");
+            exit(1);
+        };
+    }
+
+    if deployment.autostart {
+        // Autostart entries live in the system configuration directory rather than the prefix
+        let appdir_autostart = appdir_root.join("etc").join("xdg").join("autostart");
+        let Ok(_) = create_dir_all(&appdir_autostart) else {
+            error!("Failed to create appdir autostart folder");
+            exit(1);
+        };
+
+        let Ok(autostart_entry_contents) = generate_desktop_entry(
+            target_triple,
+            executable_path,
+            &setup_data.contemporary_config,
+            Some(&deployment.autostart_arguments),
+        ) else {
+            error!("Failed to generate autostart entry");
+            exit(1);
+        };
+
+        let Ok(_) = write(
+            appdir_autostart.join(&desktop_entry_with_desktop_extension),
+            autostart_entry_contents,
+        ) else {
+            error!("Failed to write autostart entry");
+            exit(1);
+        };
+    }
+
     let icon_svg = get_svg_icon_contents(
         target_triple,
         &setup_data.base_path,
@@ -157,10 +202,15 @@ pub fn bundle_linux(setup_data: &ToolSetup, executable_path: HashMap<String, Pat
     };
 }
 
+/// Generates the contents of the application's desktop entry.
+///
+/// If `autostart_arguments` is provided, an XDG autostart entry is generated instead, launching
+/// the application with the given arguments.
 fn generate_desktop_entry(
     target_triple: &str,
     executable_path: &Path,
     contemporary_config: &ContemporaryConfig,
+    autostart_arguments: Option<&[String]>,
 ) -> Result<String, Error> {
     let deployment = contemporary_config.deployment(target_triple);
 
@@ -182,10 +232,19 @@ fn generate_desktop_entry(
     let mut entry = DesktopEntry::new();
     entry.push_line_invariant("Type", "Application")?;
     entry.push_line_invariant("Version", "1.0")?;
-    entry.push_line_invariant(
-        "Exec",
-        executable_path.file_name().unwrap().to_str().unwrap(),
-    )?;
+    let exec = std::iter::once(executable_path.file_name().unwrap().to_str().unwrap())
+        .chain(
+            autostart_arguments
+                .unwrap_or_default()
+                .iter()
+                .map(String::as_str),
+        )
+        .map(quote_exec_argument)
+        .collect::<Vec<_>>()
+        .join(" ")
+        // Backslashes are escaped again when the value is read as a string
+        .replace('\\', "\\\\");
+    entry.push_line_invariant("Exec", &exec)?;
     entry.push_line_invariant("Icon", &desktop_entry)?;
     entry.push_line("Name", &application_name)?;
 
@@ -205,9 +264,54 @@ fn generate_desktop_entry(
         entry.push_line_invariant("MimeType", &(mime_types.join(";") + ";"))?;
     }
 
+    match autostart_arguments {
+        None => {
+            if deployment.dbus_activatable {
+                entry.push_line_invariant("DBusActivatable", "true")?;
+            }
+        }
+        Some(_) => {
+            entry.push_line_invariant("X-GNOME-Autostart-enabled", "true")?;
+        }
+    }
+
     Ok(entry.contents)
 }
 
+/// Generates the contents of the D-Bus service file that activates the application.
+///
+/// D-Bus requires an absolute path, so the service points at /usr/bin; rootdir deployments
+/// rewrite it to the install prefix.
+fn generate_dbus_service(desktop_entry: &str, executable_path: &Path) -> String {
+    format!(
+        "[D-BUS Service]\nName={}\nExec={}\n",
+        desktop_entry,
+        PathBuf::from("/usr/bin")
+            .join(executable_path.file_name().unwrap())
+            .display()
+    )
+}
+
+/// Quotes an argument for use in the Exec key of a desktop entry.
+fn quote_exec_argument(argument: &str) -> String {
+    const RESERVED_CHARACTERS: &str = " \t\n\"'\\><~|&;$*?#()`";
+
+    let argument = argument.replace('%', "%%");
+    if !argument.contains(|c| RESERVED_CHARACTERS.contains(c)) {
+        return argument;
+    }
+
+    let mut quoted = String::from("\"");
+    for c in argument.chars() {
+        if matches!(c, '"' | '`' | '$' | '\\') {
+            quoted.push('\\');
+        }
+        quoted.push(c);
+    }
+    quoted.push('"');
+    quoted
+}
+
 struct DesktopEntry {
     pub contents: String,
 }
@@ -241,3 +345,78 @@ impl DesktopEntry {
         self.push_line(key, &LocalisedString::Hardcoded(value.into()))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::linux::{generate_dbus_service, generate_desktop_entry, quote_exec_argument};
+    use cntp_config::ContemporaryConfig;
+    use std::fs::write;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    const TARGET: &str = "x86_64-unknown-linux-gnu";
+
+    fn config(deployment: &str) -> ContemporaryConfig {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("Contemporary.toml");
+        write(
+            &path,
+            format!(
+                "[application]\ntheme_colors = []\n\n[deployment]\napplication_name = \"App\"\ndesktop_entry = \"com.example.app\"\ndesktop_entry_categories = [\"Utility\"]\n{deployment}"
+            ),
+        )
+        .unwrap();
+        ContemporaryConfig::new_from_path(path).unwrap()
+    }
+
+    #[test]
+    fn exec_arguments() {
+        assert_eq!(quote_exec_argument("app"), "app");
+        assert_eq!(quote_exec_argument("--minimised"), "--minimised");
+        assert_eq!(quote_exec_argument("My Folder"), "\"My Folder\"");
+        assert_eq!(quote_exec_argument("100%"), "100%%");
+        assert_eq!(quote_exec_argument("say \"hi\""), "\"say \\\"hi\\\"\"");
+        assert_eq!(quote_exec_argument("a\\b"), "\"a\\\\b\"");
+        assert_eq!(quote_exec_argument("$HOME"), "\"\\$HOME\"");
+    }
+
+    #[test]
+    fn desktop_entry_for_dbus_activation() {
+        let entry = generate_desktop_entry(
+            TARGET,
+            Path::new("target/release/app"),
+            &config("dbus_activatable = true\n"),
+            None,
+        )
+        .unwrap();
+        assert!(entry.contains("\nExec=app\n"));
+        assert!(entry.contains("\nDBusActivatable=true\n"));
+        assert!(!entry.contains("X-GNOME-Autostart-enabled"));
+    }
+
+    #[test]
+    fn autostart_entry() {
+        let config = config(
+            "dbus_activatable = true\nautostart = true\nautostart_arguments = [\"--minimised\", \"My Folder\", \"C:\\\\\"]\n",
+        );
+        let arguments = config.deployment(TARGET).autostart_arguments;
+        let entry = generate_desktop_entry(
+            TARGET,
+            Path::new("target/release/app"),
+            &config,
+            Some(arguments.as_slice()),
+        )
+        .unwrap();
+        assert!(entry.contains("\nExec=app --minimised \"My Folder\" \"C:\\\\\\\\\"\n"));
+        assert!(entry.contains("\nX-GNOME-Autostart-enabled=true\n"));
+        assert!(!entry.contains("DBusActivatable"));
+    }
+
+    #[test]
+    fn dbus_service() {
+        assert_eq!(
+            generate_dbus_service("com.example.app", Path::new("target/release/app")),
+            "[D-BUS Service]\nName=com.example.app\nExec=/usr/bin/app\n"
+        );
+    }
+}
@@ -1,17 +1,22 @@
 use crate::linux::appimage::deploy_appimage;
-use crate::linux::rootdir::deploy_rootdir;
+use crate::linux::rootdir::{deploy_rootdir, uninstall_rootdir};
 use crate::tool_setup::ToolSetup;
 use std::process::exit;
 use tracing::error;
 
-pub fn deploy_linux(setup_data: &ToolSetup, platform_subtype: &Option<String>, output_file: &str) {
+pub fn deploy_linux(
+    setup_data: &ToolSetup,
+    platform_subtype: &Option<String>,
+    output_file: &str,
+    prefix: &Option<String>,
+) {
     let subtype = platform_subtype.clone().unwrap_or("appimage".into());
     match subtype.as_str() {
         "appimage" => {
             deploy_appimage(setup_data, output_file);
         }
         "rootdir" => {
-            deploy_rootdir(setup_data, output_file);
+            deploy_rootdir(setup_data, output_file, prefix.as_deref().unwrap_or("/usr"));
         }
         _ => {
             error!("Unsupported platform subtype: {}", subtype);
@@ -20,3 +25,22 @@ pub fn deploy_linux(setup_data: &ToolSetup, platform_subtype: &Option<String>, o
         }
     }
 }
+
+pub fn uninstall_linux(
+    setup_data: &ToolSetup,
+    platform_subtype: &Option<String>,
+    output_file: &str,
+    prefix: &Option<String>,
+) {
+    let subtype = platform_subtype.clone().unwrap_or("appimage".into());
+    match subtype.as_str() {
+        "rootdir" => {
+            uninstall_rootdir(setup_data, output_file, prefix.as_deref().unwrap_or("/usr"));
+        }
+        _ => {
+            error!("Unable to uninstall platform subtype: {}", subtype);
+            error!("Supported platform subtypes: rootdir");
+            exit(1);
+        }
+    }
+}
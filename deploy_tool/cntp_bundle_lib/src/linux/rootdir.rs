@@ -1,32 +1,44 @@
-use crate::copy_dir_all::copy_dir_all;
 use crate::tool_setup::ToolSetup;
-use std::fs::exists;
-use std::path::PathBuf;
+use anyhow::Context;
+use std::fs::{
+    copy, create_dir_all, exists, read_dir, read_link, read_to_string, remove_dir, remove_file,
+    write,
+};
+use std::io;
+use std::io::ErrorKind;
+use std::os::unix::fs::symlink;
+use std::path::{Component, Path, PathBuf};
 use std::process::exit;
-use tracing::{error, info};
+use tracing::{debug, error, info, warn};
 
-pub fn deploy_rootdir(setup_data: &ToolSetup, output_file: &str) {
+/// Prefixes whose data directories are searched by default, so icons can be referred to by name.
+const STANDARD_PREFIXES: &[&str] = &["/usr", "/usr/local"];
+
+/// Configuration files such as autostart entries are installed here regardless of the prefix.
+const SYSCONFDIR: &str = "/etc";
+
+pub fn deploy_rootdir(setup_data: &ToolSetup, output_file: &str, prefix: &str) {
     let appdir_root = setup_data.output_directory.join("appdir");
     if !appdir_root.exists() {
         error!("AppDir does not exist. Please bundle first.");
         exit(1);
     }
 
-    let output_file = PathBuf::from(output_file);
-    match exists(&output_file) {
+    let destdir = PathBuf::from(output_file);
+    match exists(&destdir) {
         Ok(true) => {
             // Ensure it is a directory
-            if !output_file.is_dir() {
+            if !destdir.is_dir() {
                 error!(
                     "Output file exists, but is not a directory: {}",
-                    output_file.display()
+                    destdir.display()
                 );
                 exit(1);
             }
         }
         Ok(false) => {
             // Create the directory
-            if let Err(e) = std::fs::create_dir(&output_file) {
+            if let Err(e) = create_dir_all(&destdir) {
                 error!("Failed to create output directory: {}", e);
                 exit(1);
             }
@@ -37,18 +49,433 @@ pub fn deploy_rootdir(setup_data: &ToolSetup, output_file: &str) {
         }
     }
 
-    // Recursively walk the appdir_root and copy each file to the output directory, preserving the directory structure and permissions
-    if let Err(e) = copy_dir_all(&appdir_root, &output_file, |entry| {
-        // Avoid copying the root AppImage files (AppRun, the icon and the desktop file) to the directory because it is not required during rootdir packaging
-        if entry.path().parent() == Some(&appdir_root) && entry.path().is_file() {
-            return false;
+    let prefix = validate_prefix(prefix);
+    let manifest_path = install_manifest_path(setup_data, &prefix);
+
+    let installed_files = match install_rootdir(&appdir_root, &destdir, &prefix, &manifest_path) {
+        Ok(installed_files) => installed_files,
+        Err(e) => {
+            error!("{:#}", e);
+            exit(1);
+        }
+    };
+
+    info!(
+        "Installed {} files to {} with prefix {}.",
+        installed_files,
+        destdir.display(),
+        prefix.display()
+    );
+    info!("Install manifest written to {}.", manifest_path.display());
+}
+
+pub fn uninstall_rootdir(setup_data: &ToolSetup, output_file: &str, prefix: &str) {
+    let destdir = PathBuf::from(output_file);
+    let prefix = validate_prefix(prefix);
+    let manifest_path = install_manifest_path(setup_data, &prefix);
+
+    let removed_files = match remove_rootdir(&destdir, &prefix, &manifest_path) {
+        Ok(removed_files) => removed_files,
+        Err(e) => {
+            error!("{:#}", e);
+            exit(1);
         }
+    };
+
+    info!(
+        "Removed {} files from {}.",
+        removed_files,
+        staged_path(&destdir, &prefix).display()
+    );
+}
+
+/// Installs the AppDir into the staging directory and writes the install manifest, returning
+/// the number of files installed.
+fn install_rootdir(
+    appdir_root: &Path,
+    destdir: &Path,
+    prefix: &Path,
+    manifest_path: &Path,
+) -> anyhow::Result<usize> {
+    // Only the contents of usr and etc are installed; the root AppImage files (AppRun, the icon
+    // and the desktop file) are not required during rootdir packaging
+    let mut installed_files = Vec::new();
+    install_dir(
+        &appdir_root.join("usr"),
+        destdir,
+        prefix,
+        &mut installed_files,
+    )
+    .context("Failed to copy deployment files")?;
+
+    let appdir_etc = appdir_root.join("etc");
+    if appdir_etc.exists() {
+        install_dir(
+            &appdir_etc,
+            destdir,
+            Path::new(SYSCONFDIR),
+            &mut installed_files,
+        )
+        .context("Failed to copy deployment configuration files")?;
+    }
+
+    // Point the desktop entries and D-Bus services at the installed locations
+    for installed_file in &installed_files {
+        if installed_file
+            .extension()
+            .is_none_or(|ext| ext != "desktop" && ext != "service")
+        {
+            continue;
+        }
+
+        let path = staged_path(destdir, installed_file);
+        if path.is_symlink() {
+            continue;
+        }
+
+        rewrite_desktop_entry(&path, prefix).with_context(|| {
+            format!(
+                "Failed to rewrite desktop entry {}",
+                installed_file.display()
+            )
+        })?;
+    }
 
-        true
-    }) {
-        error!("Failed to copy deployment files: {}", e);
+    installed_files.push(manifest_path.to_path_buf());
+    installed_files.sort();
+
+    let manifest_contents = installed_files
+        .iter()
+        .map(|path| path.to_string_lossy() + "\n")
+        .collect::<String>();
+
+    let staged_manifest_path = staged_path(destdir, manifest_path);
+    create_dir_all(staged_manifest_path.parent().unwrap())
+        .and_then(|_| write(&staged_manifest_path, manifest_contents))
+        .context("Failed to write install manifest")?;
+
+    Ok(installed_files.len())
+}
+
+/// Removes the files listed in the install manifest from the staging directory, returning the
+/// number of files removed.
+fn remove_rootdir(destdir: &Path, prefix: &Path, manifest_path: &Path) -> anyhow::Result<usize> {
+    let staged_manifest_path = staged_path(destdir, manifest_path);
+    let manifest_contents = read_to_string(&staged_manifest_path).with_context(|| {
+        format!(
+            "Unable to read install manifest at {}",
+            staged_manifest_path.display()
+        )
+    })?;
+
+    let mut removed_files = 0;
+    for installed_file in manifest_contents.lines().filter(|line| !line.is_empty()) {
+        let installed_file = PathBuf::from(installed_file);
+        if !is_manifest_path_allowed(&installed_file, prefix) {
+            warn!(
+                "Skipping {} as it is outside of the prefix",
+                installed_file.display()
+            );
+            continue;
+        }
+
+        let path = staged_path(destdir, &installed_file);
+        match remove_file(&path) {
+            Ok(_) => {
+                debug!("Removed {}", path.display());
+                removed_files += 1;
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                warn!("{} was already removed", path.display());
+            }
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to remove {}", path.display()));
+            }
+        }
+
+        // Clean up any directories that are now empty
+        let mut parent = path.parent();
+        while let Some(directory) = parent {
+            if directory == destdir || remove_dir(directory).is_err() {
+                break;
+            }
+            parent = directory.parent();
+        }
+    }
+
+    Ok(removed_files)
+}
+
+/// Whether a path is absolute and has no `..` components, so it can't escape the directory it
+/// starts with.
+fn is_normalized_absolute(path: &Path) -> bool {
+    path.is_absolute()
+        && !path
+            .components()
+            .any(|component| component == Component::ParentDir)
+}
+
+/// Whether a path listed in an install manifest may be removed when uninstalling.
+fn is_manifest_path_allowed(installed_file: &Path, prefix: &Path) -> bool {
+    is_normalized_absolute(installed_file)
+        && (installed_file.starts_with(prefix) || installed_file.starts_with(SYSCONFDIR))
+}
+
+fn validate_prefix(prefix: &str) -> PathBuf {
+    let prefix = PathBuf::from(prefix);
+    if !is_normalized_absolute(&prefix) {
+        error!(
+            "Install prefix must be absolute and must not contain '..': {}",
+            prefix.display()
+        );
+        exit(1);
+    }
+    prefix
+}
+
+fn install_manifest_path(setup_data: &ToolSetup, prefix: &Path) -> PathBuf {
+    let deployment = setup_data
+        .contemporary_config
+        .deployment(setup_data.targets.first().unwrap());
+
+    let Some(desktop_entry) = deployment.desktop_entry else {
+        error!("No desktop entry specified in config");
         exit(1);
+    };
+
+    prefix
+        .join("share")
+        .join(desktop_entry)
+        .join("install_manifest.txt")
+}
+
+/// Returns the location of an installed path inside the staging directory.
+fn staged_path(destdir: &Path, installed_path: &Path) -> PathBuf {
+    destdir.join(installed_path.strip_prefix("/").unwrap_or(installed_path))
+}
+
+/// Copies src into the prefix, recording the installed path of every file written.
+fn install_dir(
+    src: &Path,
+    destdir: &Path,
+    installed_dir: &Path,
+    installed_files: &mut Vec<PathBuf>,
+) -> io::Result<()> {
+    create_dir_all(staged_path(destdir, installed_dir))?;
+    for entry in read_dir(src)? {
+        let entry = entry?;
+        let installed_path = installed_dir.join(entry.file_name());
+        let staged = staged_path(destdir, &installed_path);
+
+        let ty = entry.file_type()?;
+        if ty.is_dir() {
+            install_dir(&entry.path(), destdir, &installed_path, installed_files)?;
+            continue;
+        }
+
+        if staged.is_symlink() || staged.exists() {
+            remove_file(&staged)?;
+        }
+
+        if ty.is_symlink() {
+            symlink(read_link(entry.path())?, &staged)?;
+        } else {
+            copy(entry.path(), &staged)?;
+        }
+        installed_files.push(installed_path);
+    }
+    Ok(())
+}
+
+fn rewrite_desktop_entry(path: &Path, prefix: &Path) -> io::Result<()> {
+    let contents = read_to_string(path)?;
+    let use_icon_paths = !STANDARD_PREFIXES
+        .iter()
+        .any(|standard_prefix| prefix == Path::new(standard_prefix));
+
+    let mut rewritten = String::new();
+    for line in contents.lines() {
+        // D-Bus services need an absolute path, so they are bundled pointing at /usr/bin
+        if let Some(exec) = line
+            .strip_prefix("Exec=")
+            .map(|exec| exec.strip_prefix("/usr/bin/").unwrap_or(exec))
+            .filter(|exec| !exec.starts_with('/') && !exec.starts_with('"'))
+        {
+            // The executable is the first field; keep any arguments that follow it
+            let (executable, arguments) = exec.split_once(' ').unwrap_or((exec, ""));
+            let executable = prefix.join("bin").join(executable);
+            let executable = executable.to_string_lossy();
+            if executable.contains(char::is_whitespace) {
+                rewritten.push_str(&format!("Exec=\"{executable}\""));
+            } else {
+                rewritten.push_str(&format!("Exec={executable}"));
+            }
+            if !arguments.is_empty() {
+                rewritten.push(' ');
+                rewritten.push_str(arguments);
+            }
+        } else if let Some(icon) = line
+            .strip_prefix("Icon=")
+            .filter(|icon| use_icon_paths && !icon.starts_with('/'))
+        {
+            let icon = prefix
+                .join("share/icons/hicolor/scalable/apps")
+                .join(format!("{icon}.svg"));
+            rewritten.push_str(&format!("Icon={}", icon.display()));
+        } else {
+            rewritten.push_str(line);
+        }
+        rewritten.push('\n');
+    }
+
+    write(path, rewritten)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::linux::rootdir::{
+        install_rootdir, is_manifest_path_allowed, is_normalized_absolute, remove_rootdir,
+        rewrite_desktop_entry,
+    };
+    use std::fs::{create_dir_all, read_to_string, write};
+    use std::os::unix::fs::symlink;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn rewritten(contents: &str, prefix: &str) -> String {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("com.example.app.desktop");
+        write(&path, contents).unwrap();
+        rewrite_desktop_entry(&path, Path::new(prefix)).unwrap();
+        read_to_string(&path).unwrap()
+    }
+
+    #[test]
+    fn prefix_validation() {
+        assert!(is_normalized_absolute(Path::new("/usr")));
+        assert!(is_normalized_absolute(Path::new("/opt/example")));
+        assert!(!is_normalized_absolute(Path::new("usr")));
+        assert!(!is_normalized_absolute(Path::new("/opt/../etc")));
+    }
+
+    #[test]
+    fn manifest_paths() {
+        let prefix = Path::new("/usr");
+        assert!(is_manifest_path_allowed(Path::new("/usr/bin/app"), prefix));
+        assert!(is_manifest_path_allowed(
+            Path::new("/etc/xdg/autostart/com.example.app.desktop"),
+            prefix
+        ));
+        assert!(!is_manifest_path_allowed(
+            Path::new("/usr/../../etc/x"),
+            prefix
+        ));
+        assert!(!is_manifest_path_allowed(
+            Path::new("/etc/../root/x"),
+            prefix
+        ));
+        assert!(!is_manifest_path_allowed(Path::new("usr/bin/app"), prefix));
+        assert!(!is_manifest_path_allowed(Path::new("/opt/bin/app"), prefix));
     }
 
-    info!("Copied deployment files to {}.", output_file.display());
+    #[test]
+    fn desktop_entry_in_standard_prefix() {
+        assert_eq!(
+            rewritten(
+                "[Desktop Entry]\nExec=app %U\nIcon=com.example.app\n",
+                "/usr"
+            ),
+            "[Desktop Entry]\nExec=/usr/bin/app %U\nIcon=com.example.app\n"
+        );
+    }
+
+    #[test]
+    fn desktop_entry_in_custom_prefix() {
+        assert_eq!(
+            rewritten(
+                "[Desktop Entry]\nExec=app %U\nIcon=com.example.app\n",
+                "/opt/Example App"
+            ),
+            "[Desktop Entry]\nExec=\"/opt/Example App/bin/app\" %U\nIcon=/opt/Example App/share/icons/hicolor/scalable/apps/com.example.app.svg\n"
+        );
+    }
+
+    #[test]
+    fn dbus_service_in_custom_prefix() {
+        assert_eq!(
+            rewritten(
+                "[D-BUS Service]\nName=com.example.app\nExec=/usr/bin/app --dbus\n",
+                "/opt/example"
+            ),
+            "[D-BUS Service]\nName=com.example.app\nExec=/opt/example/bin/app --dbus\n"
+        );
+    }
+
+    #[test]
+    fn install_and_uninstall() {
+        let temp_dir = TempDir::new().unwrap();
+        let appdir = temp_dir.path().join("appdir");
+        let destdir = temp_dir.path().join("destdir");
+        create_dir_all(appdir.join("usr/bin")).unwrap();
+        create_dir_all(appdir.join("usr/share/applications")).unwrap();
+        create_dir_all(appdir.join("etc/xdg/autostart")).unwrap();
+        write(appdir.join("usr/bin/app"), "binary").unwrap();
+        write(
+            appdir.join("usr/share/applications/com.example.app.desktop"),
+            "[Desktop Entry]\nExec=app\nIcon=com.example.app\n",
+        )
+        .unwrap();
+        symlink(
+            "../../share/applications/com.example.app.desktop",
+            appdir.join("etc/xdg/autostart/com.example.app.desktop"),
+        )
+        .unwrap();
+        create_dir_all(&destdir).unwrap();
+
+        let prefix = Path::new("/opt/example");
+        let manifest_path = Path::new("/opt/example/share/com.example.app/install_manifest.txt");
+        assert_eq!(
+            install_rootdir(&appdir, &destdir, prefix, manifest_path).unwrap(),
+            4
+        );
+
+        assert_eq!(
+            read_to_string(destdir.join("opt/example/bin/app")).unwrap(),
+            "binary"
+        );
+        assert_eq!(
+            read_to_string(destdir.join("opt/example/share/applications/com.example.app.desktop"))
+                .unwrap(),
+            "[Desktop Entry]\nExec=/opt/example/bin/app\nIcon=/opt/example/share/icons/hicolor/scalable/apps/com.example.app.svg\n"
+        );
+        assert!(
+            destdir
+                .join("etc/xdg/autostart/com.example.app.desktop")
+                .is_symlink()
+        );
+        assert_eq!(
+            read_to_string(destdir.join("opt/example/share/com.example.app/install_manifest.txt"))
+                .unwrap(),
+            "/etc/xdg/autostart/com.example.app.desktop\n\
+             /opt/example/bin/app\n\
+             /opt/example/share/applications/com.example.app.desktop\n\
+             /opt/example/share/com.example.app/install_manifest.txt\n"
+        );
+
+        // A tampered manifest must not be able to remove files outside of the prefix
+        write(destdir.join("outside"), "keep").unwrap();
+        let staged_manifest_path =
+            destdir.join("opt/example/share/com.example.app/install_manifest.txt");
+        let manifest = read_to_string(&staged_manifest_path).unwrap();
+        write(
+            &staged_manifest_path,
+            format!("/opt/example/../../outside\n{manifest}"),
+        )
+        .unwrap();
+
+        assert_eq!(remove_rootdir(&destdir, prefix, manifest_path).unwrap(), 4);
+        assert!(!destdir.join("opt").exists());
+        assert!(!destdir.join("etc").exists());
+        assert!(destdir.join("outside").exists());
+    }
 }
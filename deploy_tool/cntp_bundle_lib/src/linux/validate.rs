@@ -0,0 +1,757 @@
+use crate::tool_setup::ToolSetup;
+use crate::validation::ValidationReport;
+use std::collections::HashSet;
+use std::fs::{read_link, read_to_string};
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+/// Main categories registered in the freedesktop menu specification.
+const MAIN_CATEGORIES: &[&str] = &[
+    "AudioVideo",
+    "Audio",
+    "Video",
+    "Development",
+    "Education",
+    "Game",
+    "Graphics",
+    "Network",
+    "Office",
+    "Science",
+    "Settings",
+    "System",
+    "Utility",
+];
+
+/// Additional categories registered in the freedesktop menu specification.
+const ADDITIONAL_CATEGORIES: &[&str] = &[
+    "Building",
+    "Debugger",
+    "IDE",
+    "GUIDesigner",
+    "Profiling",
+    "RevisionControl",
+    "Translation",
+    "Calendar",
+    "ContactManagement",
+    "Database",
+    "Dictionary",
+    "Chart",
+    "Email",
+    "Finance",
+    "FlowChart",
+    "PDA",
+    "ProjectManagement",
+    "Presentation",
+    "Spreadsheet",
+    "WordProcessor",
+    "2DGraphics",
+    "VectorGraphics",
+    "RasterGraphics",
+    "3DGraphics",
+    "Scanning",
+    "OCR",
+    "Photography",
+    "Publishing",
+    "Viewer",
+    "TextTools",
+    "DesktopSettings",
+    "HardwareSettings",
+    "Printing",
+    "PackageManager",
+    "Dialup",
+    "InstantMessaging",
+    "Chat",
+    "IRCClient",
+    "Feed",
+    "FileTransfer",
+    "HamRadio",
+    "News",
+    "P2P",
+    "RemoteAccess",
+    "Telephony",
+    "TelephonyTools",
+    "VideoConference",
+    "WebBrowser",
+    "WebDevelopment",
+    "Midi",
+    "Mixer",
+    "Sequencer",
+    "Tuner",
+    "TV",
+    "AudioVideoEditing",
+    "Player",
+    "Recorder",
+    "DiscBurning",
+    "ActionGame",
+    "AdventureGame",
+    "ArcadeGame",
+    "BoardGame",
+    "BlocksGame",
+    "CardGame",
+    "KidsGame",
+    "LogicGame",
+    "RolePlaying",
+    "Shooter",
+    "Simulation",
+    "SportsGame",
+    "StrategyGame",
+    "Art",
+    "Construction",
+    "Music",
+    "Languages",
+    "ArtificialIntelligence",
+    "Astronomy",
+    "Biology",
+    "Chemistry",
+    "ComputerScience",
+    "DataVisualization",
+    "Economy",
+    "Electricity",
+    "Geography",
+    "Geology",
+    "Geoscience",
+    "History",
+    "Humanities",
+    "ImageProcessing",
+    "Literature",
+    "Maps",
+    "Math",
+    "NumericalAnalysis",
+    "MedicalSoftware",
+    "Physics",
+    "Robotics",
+    "Spirituality",
+    "Sports",
+    "ParallelComputing",
+    "Amusement",
+    "Archiving",
+    "Compression",
+    "Electronics",
+    "Emulator",
+    "Engineering",
+    "FileTools",
+    "FileManager",
+    "TerminalEmulator",
+    "Filesystem",
+    "Monitor",
+    "Security",
+    "Accessibility",
+    "Calculator",
+    "Clock",
+    "TextEditor",
+    "Documentation",
+    "Adult",
+    "Core",
+    "KDE",
+    "GNOME",
+    "XFCE",
+    "DDE",
+    "GTK",
+    "Qt",
+    "Motif",
+    "Java",
+    "ConsoleOnly",
+];
+
+/// Keys whose values are lists separated (and terminated) by semicolons.
+const LIST_KEYS: &[&str] = &[
+    "Categories",
+    "MimeType",
+    "Keywords",
+    "OnlyShowIn",
+    "NotShowIn",
+    "Actions",
+    "Implements",
+];
+
+/// Validates a bundled AppDir, including the desktop entry and D-Bus service it contains.
+pub fn validate_linux_bundle(setup_data: &ToolSetup) -> ValidationReport {
+    let mut report = ValidationReport::default();
+
+    let target_triple = setup_data.targets.first().unwrap();
+    let deployment = setup_data.contemporary_config.deployment(target_triple);
+    let Some(desktop_entry) = deployment.desktop_entry else {
+        report.error("Contemporary.toml", "No desktop entry specified in config");
+        return report;
+    };
+
+    report.extend(validate_appdir(
+        &setup_data.output_directory.join("appdir"),
+        &desktop_entry,
+        deployment.dbus_activatable,
+        deployment.autostart,
+    ));
+    report
+}
+
+/// Validates the contents of an AppDir for the desktop entry ID `desktop_entry`.
+fn validate_appdir(
+    appdir_root: &Path,
+    desktop_entry: &str,
+    dbus_activatable: bool,
+    autostart: bool,
+) -> ValidationReport {
+    let mut report = ValidationReport::default();
+
+    let desktop_entry_path =
+        PathBuf::from("usr/share/applications").join(format!("{desktop_entry}.desktop"));
+    let icon_path =
+        PathBuf::from("usr/share/icons/hicolor/scalable/apps").join(format!("{desktop_entry}.svg"));
+
+    validate_executable(appdir_root, Path::new("AppRun"), &mut report);
+    validate_file(appdir_root, Path::new(".DirIcon"), &mut report);
+    validate_file(appdir_root, &icon_path, &mut report);
+    validate_symlink(
+        appdir_root,
+        &PathBuf::from(format!("{desktop_entry}.desktop")),
+        &desktop_entry_path,
+        &mut report,
+    );
+    validate_symlink(
+        appdir_root,
+        &PathBuf::from(format!("{desktop_entry}.svg")),
+        &icon_path,
+        &mut report,
+    );
+
+    let location = desktop_entry_path.display().to_string();
+    let Ok(contents) = read_to_string(appdir_root.join(&desktop_entry_path)) else {
+        report.error(location, "Desktop entry does not exist");
+        return report;
+    };
+    report.extend(validate_desktop_entry(&location, &contents));
+
+    if let Some(exec) = desktop_entry_value(&contents, "Exec") {
+        validate_executable(
+            appdir_root,
+            &PathBuf::from("usr/bin").join(exec_program(&exec)),
+            &mut report,
+        );
+    }
+
+    if desktop_entry_value(&contents, "Icon").as_deref() != Some(desktop_entry) {
+        report.warning(
+            &location,
+            format!("Icon should be set to the desktop entry ID ({desktop_entry})"),
+        );
+    }
+
+    if dbus_activatable {
+        let service_path =
+            PathBuf::from("usr/share/dbus-1/services").join(format!("{desktop_entry}.service"));
+        validate_file(appdir_root, &service_path, &mut report);
+
+        if desktop_entry.split('.').count() < 2 || desktop_entry.contains('-') {
+            report.warning(
+                &location,
+                "D-Bus activatable desktop entry IDs should be reverse DNS names without dashes",
+            );
+        }
+    }
+
+    if autostart {
+        let autostart_path =
+            PathBuf::from("etc/xdg/autostart").join(format!("{desktop_entry}.desktop"));
+        let location = autostart_path.display().to_string();
+        match read_to_string(appdir_root.join(&autostart_path)) {
+            Ok(contents) => report.extend(validate_desktop_entry(&location, &contents)),
+            Err(_) => report.error(location, "Autostart entry does not exist"),
+        }
+    }
+
+    report
+}
+
+/// Validates the contents of a desktop entry against the freedesktop desktop entry specification.
+pub fn validate_desktop_entry(location: &str, contents: &str) -> ValidationReport {
+    let mut report = ValidationReport::default();
+    let mut seen_keys = HashSet::new();
+    let mut in_desktop_entry_group = false;
+    let mut seen_desktop_entry_group = false;
+
+    for (line_no, line) in contents.lines().enumerate() {
+        let location = format!("{}:{}", location, line_no + 1);
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(group) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            if !seen_desktop_entry_group && group != "Desktop Entry" {
+                report.error(&location, "The first group must be [Desktop Entry]");
+            }
+            in_desktop_entry_group = group == "Desktop Entry";
+            seen_desktop_entry_group = true;
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once('=') else {
+            report.error(&location, format!("Line is not a key-value pair: {line}"));
+            continue;
+        };
+
+        if !seen_desktop_entry_group {
+            report.error(&location, "Key appears before the [Desktop Entry] group");
+            continue;
+        }
+
+        if key.ends_with(' ') || value.starts_with(' ') {
+            report.warning(&location, "Whitespace around = is not portable");
+        }
+        let key = key.trim_end();
+        let value = value.trim_start();
+
+        let (base_key, locale) = match key.split_once('[') {
+            Some((base_key, locale)) => {
+                let Some(locale) = locale.strip_suffix(']') else {
+                    report.error(&location, format!("Malformed localised key: {key}"));
+                    continue;
+                };
+                (base_key, Some(locale))
+            }
+            None => (key, None),
+        };
+
+        if base_key.is_empty()
+            || !base_key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-')
+        {
+            report.error(&location, format!("Invalid key name: {base_key}"));
+        }
+
+        if let Some(locale) = locale
+            && !is_valid_locale(locale)
+        {
+            report.error(
+                &location,
+                format!("Invalid locale in key {key}; expected lang_COUNTRY.ENCODING@MODIFIER"),
+            );
+        }
+
+        if in_desktop_entry_group && !seen_keys.insert(key.to_string()) {
+            report.error(&location, format!("Duplicate key: {key}"));
+        }
+
+        if let Some(message) = invalid_escape_sequence(value) {
+            report.error(&location, message);
+        }
+
+        if LIST_KEYS.contains(&base_key) && !value.is_empty() && !value.ends_with(';') {
+            report.error(&location, format!("{base_key} must be terminated by ;"));
+        }
+
+        if in_desktop_entry_group && base_key == "Categories" {
+            validate_categories(&location, value, &mut report);
+        }
+
+        if in_desktop_entry_group && base_key == "Exec" {
+            validate_exec(&location, value, &mut report);
+        }
+    }
+
+    for required_key in ["Type", "Name"] {
+        if !seen_keys.contains(required_key) {
+            report.error(location, format!("Missing required key: {required_key}"));
+        }
+    }
+
+    if !seen_keys.contains("Exec") && !seen_keys.contains("DBusActivatable") {
+        report.error(location, "Application entries must have an Exec key");
+    }
+
+    report
+}
+
+fn validate_categories(location: &str, value: &str, report: &mut ValidationReport) {
+    let categories = value
+        .split(';')
+        .filter(|category| !category.is_empty())
+        .collect::<Vec<_>>();
+
+    let main_categories = categories
+        .iter()
+        .filter(|category| MAIN_CATEGORIES.contains(category))
+        .count();
+    if main_categories == 0 {
+        report.warning(
+            location,
+            format!(
+                "Categories should contain a main category ({})",
+                MAIN_CATEGORIES.join(", ")
+            ),
+        );
+    }
+
+    for category in &categories {
+        if category.starts_with("X-")
+            || MAIN_CATEGORIES.contains(category)
+            || ADDITIONAL_CATEGORIES.contains(category)
+        {
+            continue;
+        }
+
+        report.error(
+            location,
+            format!("{category} is not a registered category; prefix it with X- if intentional"),
+        );
+    }
+
+    if categories
+        .iter()
+        .any(|category| *category == "Audio" || *category == "Video")
+        && !categories.contains(&"AudioVideo")
+    {
+        report.error(location, "Audio and Video categories require AudioVideo");
+    }
+}
+
+fn validate_exec(location: &str, value: &str, report: &mut ValidationReport) {
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            continue;
+        }
+
+        match chars.next() {
+            Some('f' | 'F' | 'u' | 'U' | 'i' | 'c' | 'k' | '%') => {}
+            Some(code @ ('d' | 'D' | 'n' | 'N' | 'v' | 'm')) => {
+                report.warning(location, format!("Field code %{code} is deprecated"));
+            }
+            Some(code) => {
+                report.error(location, format!("Invalid field code %{code} in Exec"));
+            }
+            None => {
+                report.error(location, "Exec ends with an unterminated %");
+            }
+        }
+    }
+}
+
+fn invalid_escape_sequence(value: &str) -> Option<String> {
+    if value.chars().any(|c| c.is_control()) {
+        return Some("Value contains control characters".into());
+    }
+
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            continue;
+        }
+
+        match chars.next() {
+            Some('s' | 'n' | 't' | 'r' | '\\' | ';') => {}
+            Some(escape) => return Some(format!("Invalid escape sequence \\{escape}")),
+            None => return Some("Value ends with an unterminated escape sequence".into()),
+        }
+    }
+
+    None
+}
+
+/// Checks the lang_COUNTRY.ENCODING@MODIFIER syntax of locale keys.
+fn is_valid_locale(locale: &str) -> bool {
+    let (locale, modifier) = locale.split_once('@').unwrap_or((locale, ""));
+    let (locale, encoding) = locale.split_once('.').unwrap_or((locale, ""));
+    let (lang, country) = locale.split_once('_').unwrap_or((locale, ""));
+
+    let lang_valid = (2..=3).contains(&lang.len()) && lang.chars().all(|c| c.is_ascii_lowercase());
+    let country_valid = country.is_empty()
+        || (country.len() == 2 && country.chars().all(|c| c.is_ascii_uppercase()))
+        || (country.len() == 3 && country.chars().all(|c| c.is_ascii_digit()));
+    let encoding_valid = !locale.is_empty()
+        && encoding
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-');
+    let modifier_valid = modifier.chars().all(|c| c.is_ascii_alphanumeric());
+
+    lang_valid && country_valid && encoding_valid && modifier_valid
+}
+
+fn desktop_entry_value(contents: &str, key: &str) -> Option<String> {
+    contents
+        .lines()
+        .find_map(|line| line.strip_prefix(key)?.strip_prefix('='))
+        .map(String::from)
+}
+
+/// The program an Exec value runs, with the string escapes and argument quoting undone.
+fn exec_program(exec: &str) -> String {
+    let mut unescaped = String::new();
+    let mut chars = exec.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('s') => unescaped.push(' '),
+            Some('n') => unescaped.push('\n'),
+            Some('t') => unescaped.push('\t'),
+            Some('r') => unescaped.push('\r'),
+            Some(escape) => unescaped.push(escape),
+            None => {}
+        }
+    }
+
+    let unescaped = unescaped.trim_start();
+    let mut program = String::new();
+    if let Some(quoted) = unescaped.strip_prefix('"') {
+        let mut chars = quoted.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' => break,
+                '\\' => program.extend(chars.next()),
+                c => program.push(c),
+            }
+        }
+    } else {
+        program.extend(unescaped.chars().take_while(|c| *c != ' '));
+    }
+
+    program.replace("%%", "%")
+}
+
+fn validate_file(appdir_root: &Path, path: &Path, report: &mut ValidationReport) {
+    if !appdir_root.join(path).is_file() {
+        report.error(path.display().to_string(), "Expected file does not exist");
+    }
+}
+
+fn validate_executable(appdir_root: &Path, path: &Path, report: &mut ValidationReport) {
+    let Ok(metadata) = appdir_root.join(path).metadata() else {
+        report.error(
+            path.display().to_string(),
+            "Expected executable does not exist",
+        );
+        return;
+    };
+
+    if metadata.permissions().mode() & 0o111 == 0 {
+        report.error(path.display().to_string(), "File is not executable");
+    }
+}
+
+fn validate_symlink(appdir_root: &Path, path: &Path, target: &Path, report: &mut ValidationReport) {
+    let location = path.display().to_string();
+    let Ok(link_target) = read_link(appdir_root.join(path)) else {
+        report.error(location, "Expected symlink does not exist");
+        return;
+    };
+
+    if link_target != target {
+        report.error(
+            location,
+            format!(
+                "Symlink points to {}, expected {}",
+                link_target.display(),
+                target.display()
+            ),
+        );
+    } else if !appdir_root.join(path).exists() {
+        report.error(location, "Symlink is dangling");
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::linux::validate::{exec_program, validate_appdir, validate_desktop_entry};
+    use crate::validation::ValidationReport;
+    use std::fs::{Permissions, create_dir_all, remove_file, set_permissions, write};
+    use std::os::unix::fs::{PermissionsExt, symlink};
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    const DESKTOP_ENTRY_ID: &str = "com.example.app";
+
+    /// Creates an AppDir that passes validation.
+    fn appdir(desktop_entry: &str) -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        let executable = Permissions::from_mode(0o755);
+
+        create_dir_all(root.join("usr/bin")).unwrap();
+        create_dir_all(root.join("usr/share/applications")).unwrap();
+        create_dir_all(root.join("usr/share/icons/hicolor/scalable/apps")).unwrap();
+
+        write(root.join("AppRun"), "#!/bin/sh\n").unwrap();
+        set_permissions(root.join("AppRun"), executable.clone()).unwrap();
+        write(root.join("usr/bin/app"), "binary").unwrap();
+        set_permissions(root.join("usr/bin/app"), executable).unwrap();
+        write(root.join(".DirIcon"), "icon").unwrap();
+        write(
+            root.join("usr/share/icons/hicolor/scalable/apps/com.example.app.svg"),
+            "<svg/>",
+        )
+        .unwrap();
+        write(
+            root.join("usr/share/applications/com.example.app.desktop"),
+            desktop_entry,
+        )
+        .unwrap();
+        symlink(
+            "usr/share/applications/com.example.app.desktop",
+            root.join("com.example.app.desktop"),
+        )
+        .unwrap();
+        symlink(
+            "usr/share/icons/hicolor/scalable/apps/com.example.app.svg",
+            root.join("com.example.app.svg"),
+        )
+        .unwrap();
+
+        temp_dir
+    }
+
+    fn validate(appdir_root: &Path) -> ValidationReport {
+        validate_appdir(appdir_root, DESKTOP_ENTRY_ID, false, false)
+    }
+
+    fn messages(contents: &str) -> Vec<String> {
+        validate_desktop_entry("test.desktop", contents)
+            .issues
+            .into_iter()
+            .map(|issue| issue.message)
+            .collect()
+    }
+
+    #[test]
+    fn valid_desktop_entry() {
+        let issues = messages(
+            "#!/usr/bin/env xdg-open\n[Desktop Entry]\nType=Application\nExec=app %U\nIcon=com.example.app\nName=App\nName[ta_IN]=App\nCategories=Development;IDE;\n",
+        );
+        assert!(issues.is_empty(), "{issues:?}");
+    }
+
+    #[test]
+    fn invalid_categories() {
+        let issues = messages(
+            "[Desktop Entry]\nType=Application\nExec=app\nName=App\nCategories=Developer;Player\n",
+        );
+        assert!(issues.iter().any(|issue| issue.contains("terminated by ;")));
+        assert!(issues.iter().any(|issue| issue.contains("main category")));
+        assert!(issues.iter().any(|issue| issue.contains("Developer")));
+
+        let issues = messages(
+            "[Desktop Entry]\nType=Application\nExec=app\nName=App\nCategories=Video;Player;\n",
+        );
+        assert!(issues.iter().any(|issue| issue.contains("AudioVideo")));
+    }
+
+    #[test]
+    fn invalid_locale_keys_and_escapes() {
+        let issues = messages(
+            "[Desktop Entry]\nType=Application\nExec=app\nName=App\nName[zh-Hans]=App\nComment=A \\q\n",
+        );
+        assert!(issues.iter().any(|issue| issue.contains("Invalid locale")));
+        assert!(issues.iter().any(|issue| issue.contains("escape sequence")));
+    }
+
+    #[test]
+    fn valid_appdir() {
+        let appdir = appdir(
+            "[Desktop Entry]\nType=Application\nExec=app %U\nIcon=com.example.app\nName=App\n",
+        );
+        let report = validate(appdir.path());
+        assert!(report.issues.is_empty(), "{:?}", report.issues);
+        assert!(report.report(true));
+    }
+
+    #[test]
+    fn quoted_exec() {
+        assert_eq!(exec_program("app %U"), "app");
+        assert_eq!(exec_program(r#""my app" %U"#), "my app");
+        assert_eq!(exec_program(r#""my \\"app\\"""#), r#"my "app""#);
+        assert_eq!(exec_program(r#""100%% app""#), "100% app");
+        assert_eq!(exec_program(r#""my\sapp"\s%U"#), "my app");
+
+        let appdir = appdir(
+            "[Desktop Entry]\nType=Application\nExec=\"my app\" %U\nIcon=com.example.app\nName=App\n",
+        );
+        let report = validate(appdir.path());
+        assert!(
+            report
+                .errors()
+                .any(|issue| issue.location == "usr/bin/my app"
+                    && issue.message == "Expected executable does not exist")
+        );
+
+        write(appdir.path().join("usr/bin/my app"), "binary").unwrap();
+        set_permissions(
+            appdir.path().join("usr/bin/my app"),
+            Permissions::from_mode(0o755),
+        )
+        .unwrap();
+        let report = validate(appdir.path());
+        assert!(report.issues.is_empty(), "{:?}", report.issues);
+    }
+
+    #[test]
+    fn missing_apprun() {
+        let appdir = appdir(
+            "[Desktop Entry]\nType=Application\nExec=app %U\nIcon=com.example.app\nName=App\n",
+        );
+        remove_file(appdir.path().join("AppRun")).unwrap();
+
+        let report = validate(appdir.path());
+        assert!(report.errors().any(|issue| issue.location == "AppRun"
+            && issue.message == "Expected executable does not exist"));
+        assert!(!report.report(false));
+    }
+
+    #[test]
+    fn non_executable_apprun() {
+        let appdir = appdir(
+            "[Desktop Entry]\nType=Application\nExec=app %U\nIcon=com.example.app\nName=App\n",
+        );
+        set_permissions(appdir.path().join("AppRun"), Permissions::from_mode(0o644)).unwrap();
+
+        let report = validate(appdir.path());
+        assert!(
+            report.errors().any(
+                |issue| issue.location == "AppRun" && issue.message == "File is not executable"
+            )
+        );
+    }
+
+    #[test]
+    fn missing_icon() {
+        let appdir = appdir(
+            "[Desktop Entry]\nType=Application\nExec=app %U\nIcon=com.example.app\nName=App\n",
+        );
+        remove_file(
+            appdir
+                .path()
+                .join("usr/share/icons/hicolor/scalable/apps/com.example.app.svg"),
+        )
+        .unwrap();
+
+        let report = validate(appdir.path());
+        assert!(report.errors().any(|issue| issue.location
+            == "usr/share/icons/hicolor/scalable/apps/com.example.app.svg"
+            && issue.message == "Expected file does not exist"));
+        assert!(
+            report
+                .errors()
+                .any(|issue| issue.location == "com.example.app.svg"
+                    && issue.message == "Symlink is dangling")
+        );
+        assert!(!report.report(false));
+    }
+
+    #[test]
+    fn strict_mode_fails_on_warnings() {
+        let appdir = appdir("[Desktop Entry]\nType=Application\nExec=app %U\nIcon=app\nName=App\n");
+
+        let report = validate(appdir.path());
+        assert_eq!(report.errors().count(), 0, "{:?}", report.issues);
+        assert!(
+            report
+                .warnings()
+                .any(|issue| issue.message.contains("desktop entry ID"))
+        );
+        assert!(report.report(false));
+        assert!(!report.report(true));
+    }
+}
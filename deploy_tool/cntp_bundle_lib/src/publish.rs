@@ -0,0 +1,227 @@
+use anyhow::anyhow;
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use isahc::config::{Configurable, RedirectPolicy};
+use isahc::{Body, ReadResponseExt, Request, RequestExt};
+use std::fs::{File, read};
+use std::path::PathBuf;
+use tracing::{debug, info};
+
+/// An artifact to be uploaded to a Bin Chicken repository.
+pub struct BinChickenUpload {
+    /// The base URL of the Bin Chicken server, e.g. `https://binchicken.vicr123.com/`
+    pub url: String,
+    pub repository: String,
+    /// The UUID identifying the platform this artifact is built for
+    pub uuid: String,
+    pub channel: String,
+    /// The target this artifact is built for, e.g. `x86_64-unknown-linux-gnu`
+    pub target: String,
+    pub version: Option<String>,
+    pub token: Option<String>,
+
+    pub artifact: PathBuf,
+    /// The minisign signature of the artifact
+    pub signature: PathBuf,
+}
+
+impl BinChickenUpload {
+    pub fn upload_url(&self) -> String {
+        let mut url = self.url.clone();
+        if !url.ends_with('/') {
+            url.push('/');
+        }
+
+        format!("{}api/repositories/{}", url, self.repository)
+    }
+
+    pub fn upload(&self) -> anyhow::Result<()> {
+        let original_filename = self
+            .artifact
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .ok_or_else(|| anyhow!("Artifact path does not name a file"))?;
+
+        let signature = read(&self.signature).map_err(|e| {
+            anyhow!(
+                "Unable to read signature at {}: {}",
+                self.signature.display(),
+                e
+            )
+        })?;
+
+        let artifact = File::open(&self.artifact).map_err(|e| {
+            anyhow!(
+                "Unable to open artifact at {}: {}",
+                self.artifact.display(),
+                e
+            )
+        })?;
+        let artifact_length = artifact.metadata()?.len();
+
+        let url = self.upload_url();
+        info!("Uploading {} bytes to {}", artifact_length, url);
+
+        let mut request = Request::put(&url)
+            .redirect_policy(RedirectPolicy::Follow)
+            .header("Content-Type", "application/octet-stream")
+            .header("X-Bin-Chicken-Channel", &self.channel)
+            .header("X-Bin-Chicken-Target", &self.target)
+            .header("X-Bin-Chicken-Original-Filename", original_filename)
+            .header("X-Bin-Chicken-UUID", &self.uuid)
+            .header("X-Bin-Chicken-Signature", BASE64_STANDARD.encode(signature));
+
+        if let Some(version) = &self.version {
+            request = request.header("X-Bin-Chicken-Version", version);
+        }
+
+        if let Some(token) = &self.token {
+            request = request.header("Authorization", format!("Bearer {token}"));
+        }
+
+        let mut response = request
+            .body(Body::from_reader_sized(artifact, artifact_length))?
+            .send()?;
+
+        let response_body = response.text().unwrap_or_default();
+        debug!("Bin Chicken responded with: {}", response_body);
+
+        if !response.status().is_success() {
+            return Err(anyhow!(
+                "The server returned the status: {} {}",
+                response.status().as_str(),
+                response.status().canonical_reason().unwrap_or("")
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::publish::BinChickenUpload;
+    use std::collections::HashMap;
+    use std::fs::write;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::sync::mpsc::channel;
+    use std::thread;
+    use tempfile::TempDir;
+
+    struct ReceivedRequest {
+        request_line: String,
+        headers: HashMap<String, String>,
+        body: Vec<u8>,
+    }
+
+    /// Accepts a single request on a local port and responds with the given status line.
+    fn stand_in_server(
+        status: &'static str,
+    ) -> (String, std::sync::mpsc::Receiver<ReceivedRequest>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let (tx, rx) = channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+
+            let mut headers = HashMap::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let line = line.trim_end();
+                if line.is_empty() {
+                    break;
+                }
+
+                let (key, value) = line.split_once(':').unwrap();
+                headers.insert(key.trim().to_lowercase(), value.trim().to_string());
+            }
+
+            let content_length = headers
+                .get("content-length")
+                .map(|length| length.parse().unwrap())
+                .unwrap_or(0);
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+
+            let mut stream = stream;
+            write!(stream, "HTTP/1.1 {status}\r\nContent-Length: 0\r\n\r\n").unwrap();
+
+            tx.send(ReceivedRequest {
+                request_line: request_line.trim_end().to_string(),
+                headers,
+                body,
+            })
+            .unwrap();
+        });
+
+        (url, rx)
+    }
+
+    fn upload_for(url: String, temp_dir: &TempDir) -> BinChickenUpload {
+        let artifact = temp_dir.path().join("Playground-x86_64.AppImage");
+        let signature = temp_dir.path().join("Playground-x86_64.AppImage.minisig");
+        write(&artifact, "artifact contents").unwrap();
+        write(&signature, "signature contents").unwrap();
+
+        BinChickenUpload {
+            url,
+            repository: "playground".into(),
+            uuid: "f9c1a2c4-3d4e-4f5a-8b6c-7d8e9f0a1b2c".into(),
+            channel: "blueprint".into(),
+            target: "x86_64-unknown-linux-gnu".into(),
+            version: Some("1.2.3".into()),
+            token: Some("token".into()),
+            artifact,
+            signature,
+        }
+    }
+
+    #[test]
+    fn upload_sends_artifact_and_headers() {
+        let temp_dir = TempDir::new().unwrap();
+        let (url, rx) = stand_in_server("201 Created");
+
+        upload_for(url, &temp_dir).upload().unwrap();
+
+        let request = rx.recv().unwrap();
+        assert_eq!(
+            request.request_line,
+            "PUT /api/repositories/playground HTTP/1.1"
+        );
+        assert_eq!(request.body, b"artifact contents");
+        assert_eq!(request.headers["authorization"], "Bearer token");
+        assert_eq!(request.headers["x-bin-chicken-channel"], "blueprint");
+        assert_eq!(
+            request.headers["x-bin-chicken-target"],
+            "x86_64-unknown-linux-gnu"
+        );
+        assert_eq!(
+            request.headers["x-bin-chicken-uuid"],
+            "f9c1a2c4-3d4e-4f5a-8b6c-7d8e9f0a1b2c"
+        );
+        assert_eq!(request.headers["x-bin-chicken-version"], "1.2.3");
+        assert_eq!(
+            request.headers["x-bin-chicken-original-filename"],
+            "Playground-x86_64.AppImage"
+        );
+        assert_eq!(
+            request.headers["x-bin-chicken-signature"],
+            "c2lnbmF0dXJlIGNvbnRlbnRz"
+        );
+    }
+
+    #[test]
+    fn upload_reports_server_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let (url, _rx) = stand_in_server("403 Forbidden");
+
+        assert!(upload_for(url, &temp_dir).upload().is_err());
+    }
+}
@@ -0,0 +1,80 @@
+use std::fmt::{Display, Formatter};
+use tracing::{error, warn};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationSeverity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone)]
+pub struct ValidationIssue {
+    pub severity: ValidationSeverity,
+    /// The file or component the issue was found in
+    pub location: String,
+    pub message: String,
+}
+
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.location, self.message)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ValidationReport {
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    pub fn warning(&mut self, location: impl Into<String>, message: impl Into<String>) {
+        self.issues.push(ValidationIssue {
+            severity: ValidationSeverity::Warning,
+            location: location.into(),
+            message: message.into(),
+        });
+    }
+
+    pub fn error(&mut self, location: impl Into<String>, message: impl Into<String>) {
+        self.issues.push(ValidationIssue {
+            severity: ValidationSeverity::Error,
+            location: location.into(),
+            message: message.into(),
+        });
+    }
+
+    pub fn extend(&mut self, other: ValidationReport) {
+        self.issues.extend(other.issues);
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == ValidationSeverity::Warning)
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == ValidationSeverity::Error)
+    }
+
+    /// Logs every issue and returns whether the bundle passed validation.
+    ///
+    /// In strict mode, warnings also cause validation to fail.
+    pub fn report(&self, strict: bool) -> bool {
+        for issue in &self.issues {
+            match (issue.severity, strict) {
+                (ValidationSeverity::Warning, false) => warn!("{}", issue),
+                _ => error!("{}", issue),
+            }
+        }
+
+        let failed = if strict {
+            !self.issues.is_empty()
+        } else {
+            self.errors().next().is_some()
+        };
+        !failed
+    }
+}
@@ -0,0 +1,42 @@
+use std::path::Path;
+
+use cntp_i18n_gen::check::{CheckReport, CheckSeverity};
+use tracing::{error, info, warn};
+
+use crate::OutputFormat;
+
+/// Checks every catalog, outputs the results and returns whether the check passed.
+pub fn check(manifest_directory: &Path, format: OutputFormat, strict: bool) -> bool {
+    let report = cntp_i18n_gen::check::check(manifest_directory);
+    let errors = report.count(CheckSeverity::Error);
+    let warnings = report.count(CheckSeverity::Warning);
+
+    match format {
+        OutputFormat::Text => print_text(&report),
+        OutputFormat::Json => match serde_json::to_string_pretty(&report) {
+            Ok(json) => println!("{json}"),
+            Err(e) => {
+                error!("failed to serialize check results: {}", e);
+                return false;
+            }
+        },
+    }
+
+    info!("{} error(s) and {} warning(s) found", errors, warnings);
+
+    errors == 0 && (!strict || warnings == 0)
+}
+
+fn print_text(report: &CheckReport) {
+    for issue in &report.issues {
+        let location = match &issue.key {
+            Some(key) => format!("{}: {}", issue.language, key),
+            None => issue.language.clone(),
+        };
+
+        match issue.severity {
+            CheckSeverity::Warning => warn!("{}: {}", location, issue.kind.description()),
+            CheckSeverity::Error => error!("{}: {}", location, issue.kind.description()),
+        }
+    }
+}
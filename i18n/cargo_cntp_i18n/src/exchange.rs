@@ -0,0 +1,93 @@
+use std::{fs, path::Path};
+
+use cntp_i18n_gen::exchange::ImportReport;
+use tracing::{error, info, warn};
+
+use crate::ExchangeFormat;
+
+/// Exports translations for translators and returns whether the export succeeded.
+pub fn export(
+    manifest_directory: &Path,
+    format: ExchangeFormat,
+    language: Option<&str>,
+    output: Option<&Path>,
+) -> bool {
+    let exported = match format {
+        ExchangeFormat::Po => cntp_i18n_gen::po::export(manifest_directory, language),
+        ExchangeFormat::Xliff => cntp_i18n_gen::xliff::export(manifest_directory, language),
+    };
+    let contents = match exported {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("failed to export translations: {}", e);
+            return false;
+        }
+    };
+
+    let Some(output) = output else {
+        print!("{contents}");
+        return true;
+    };
+
+    if let Err(e) = fs::write(output, contents) {
+        error!("failed to write {}: {}", output.display(), e);
+        return false;
+    }
+
+    info!("exported translations to {}", output.display());
+    true
+}
+
+/// Imports translations from a translator and returns whether the import succeeded.
+pub fn import(
+    manifest_directory: &Path,
+    file: &Path,
+    format: Option<ExchangeFormat>,
+    language: Option<&str>,
+) -> bool {
+    let Some(format) = format.or_else(|| ExchangeFormat::from_path(file)) else {
+        error!(
+            "unable to determine the format of {}, hint: pass --format",
+            file.display()
+        );
+        return false;
+    };
+
+    let contents = match fs::read_to_string(file) {
+        Ok(contents) => contents,
+        Err(e) => {
+            error!("failed to read {}: {}", file.display(), e);
+            return false;
+        }
+    };
+
+    let imported = match format {
+        ExchangeFormat::Po => cntp_i18n_gen::po::import(manifest_directory, &contents, language),
+        ExchangeFormat::Xliff => {
+            cntp_i18n_gen::xliff::import(manifest_directory, &contents, language)
+        }
+    };
+    match imported {
+        Ok(report) => {
+            print_report(&report);
+            true
+        }
+        Err(e) => {
+            error!("failed to import {}: {}", file.display(), e);
+            false
+        }
+    }
+}
+
+fn print_report(report: &ImportReport) {
+    for rejected in &report.rejected {
+        warn!("{}: {}", rejected.key, rejected.reason);
+    }
+
+    info!(
+        "imported {} translations into {} ({} rejected)",
+        report.imported,
+        report.language,
+        report.rejected.len()
+    );
+}
@@ -0,0 +1,71 @@
+use cargo_metadata::{MetadataCommand, Package};
+use cntp_i18n_gen::{GenerationResult, generate_with_cache};
+use tracing::{error, info, warn};
+
+/// Generates the catalogs of every workspace member that uses cntp_i18n and returns whether
+/// they were all generated without errors.
+pub fn generate_workspace(manifest: &clap_cargo::Manifest) -> bool {
+    // Use our own version of cargo_metadata rather than clap_cargo's, so that the packages
+    // have the same type as in `uses_cntp_i18n`
+    let mut command = MetadataCommand::new();
+    if let Some(manifest_path) = &manifest.manifest_path {
+        command.manifest_path(manifest_path);
+    }
+
+    let metadata = match command.exec() {
+        Ok(metadata) => metadata,
+        Err(e) => {
+            error!("failed to read cargo metadata: {}", e);
+            return false;
+        }
+    };
+
+    let packages = metadata
+        .workspace_packages()
+        .into_iter()
+        .filter(|&package| uses_cntp_i18n(package))
+        .collect::<Vec<_>>();
+    if packages.is_empty() {
+        warn!("no workspace members use cntp_i18n");
+        return true;
+    }
+
+    let cache_directory = metadata.target_directory.join("cntp-i18n");
+    let mut failed_packages = Vec::new();
+    for package in &packages {
+        let Some(manifest_directory) = package.manifest_path.parent() else {
+            error!("couldn't find parent of {}", package.manifest_path);
+            failed_packages.push(package.name.to_string());
+            continue;
+        };
+
+        info!("generating translations for {}", package.name);
+        let cache_file = cache_directory.join(format!("{}.json", package.name));
+        if let GenerationResult::ErrorsEncountered(_) = generate_with_cache(
+            manifest_directory.as_std_path(),
+            Some(cache_file.as_std_path()),
+        ) {
+            failed_packages.push(package.name.to_string());
+        }
+    }
+
+    if !failed_packages.is_empty() {
+        error!(
+            "errors encountered while generating translations for {}",
+            failed_packages.join(", ")
+        );
+        return false;
+    }
+
+    info!("generated translations for {} crate(s)", packages.len());
+    true
+}
+
+/// Whether a package depends on cntp_i18n or has its own i18n configuration.
+fn uses_cntp_i18n(package: &Package) -> bool {
+    package
+        .dependencies
+        .iter()
+        .any(|dependency| dependency.name == "cntp_i18n")
+        || package.manifest_path.with_file_name("i18n.toml").exists()
+}
@@ -0,0 +1,32 @@
+use std::path::Path;
+
+use tracing::{error, info, warn};
+
+use crate::OutputFormat;
+
+/// Lints every source file, outputs the results and returns whether no hardcoded text was found.
+pub fn lint(manifest_directory: &Path, format: OutputFormat) -> bool {
+    let report = cntp_i18n_gen::lint::lint(manifest_directory);
+
+    match format {
+        OutputFormat::Text => {
+            for issue in &report.issues {
+                warn!(
+                    "{}:{}:{}: {:?} passed to {} is not translated",
+                    issue.file, issue.line, issue.column, issue.text, issue.sink
+                );
+            }
+        }
+        OutputFormat::Json => match serde_json::to_string_pretty(&report) {
+            Ok(json) => println!("{json}"),
+            Err(e) => {
+                error!("failed to serialize lint results: {}", e);
+                return false;
+            }
+        },
+    }
+
+    info!("{} untranslated string(s) found", report.issues.len());
+
+    report.issues.is_empty()
+}
@@ -10,20 +10,108 @@
 //!
 //! ## Usage
 //!
-//! Currently, there is only one subcommand:
+//! ### Generating catalogs
 //!
 //! ```bash
 //! cargo cntp-i18n generate
 //! ```
 //!
-//! This command scans your `src` directory for `tr!` and `trn!` macro invocations
+//! This command scans your `src` directory for `tr!`, `trn!` and `trs!` macro invocations
 //! and generates the translation catalog files in your `translations` directory.
 //!
 //! The generated files should be committed to version control.
 //!
+//! In a workspace, pass `--workspace` to generate the catalogs of every member that
+//! depends on `cntp_i18n`, each using its own `i18n.toml`:
+//!
+//! ```bash
+//! cargo cntp-i18n generate --workspace
+//! ```
+//!
+//! The strings found in each source file are cached in the target directory, so
+//! only files that have changed since the last run are parsed again.
+//!
+//! ### Checking catalogs
+//!
+//! ```bash
+//! cargo cntp-i18n check
+//! cargo cntp-i18n check --format json
+//! ```
+//!
+//! This command checks every translation catalog for missing translations, keys that
+//! are no longer defined in `meta.json`, placeholders that differ from the source
+//! string and plural translations missing categories required by their language.
+//!
+//! The command exits with a non-zero status if any errors are found, making it
+//! suitable for use in CI. Pass `--strict` to also fail on warnings, such as
+//! missing translations.
+//!
+//! Translations made for an older version of their source string are reported as
+//! needing review. Once a translator has checked that a translation is still
+//! correct, mark it as reviewed so it is no longer reported:
+//!
+//! ```bash
+//! cargo cntp-i18n review --language de HELLO_WORLD
+//! cargo cntp-i18n review --language de
+//! ```
+//!
+//! Without any keys, every translation of the language that needs review is marked.
+//! Changing a translation also marks it as reviewed.
+//!
+//! ### Coverage statistics
+//!
+//! ```bash
+//! cargo cntp-i18n stats
+//! cargo cntp-i18n stats --format markdown
+//! cargo cntp-i18n stats --min-coverage 80
+//! ```
+//!
+//! This command reports, for each language, how many strings are translated, missing
+//! or empty, and how many words of source text are still untranslated. The output can
+//! be a table, JSON or Markdown.
+//!
+//! When `--min-coverage` is passed, the command exits with a non-zero status if any
+//! language is less than that percentage translated.
+//!
+//! ### Finding untranslated text
+//!
+//! ```bash
+//! cargo cntp-i18n lint
+//! cargo cntp-i18n lint --format json
+//! ```
+//!
+//! This command looks for string literals passed directly to methods and functions
+//! that display text, such as `.child("Save")`, which usually need to be wrapped in
+//! `tr!`. Intentionally untranslated text can be allowed with a
+//! `// cntp-i18n: allow-hardcoded` comment, or by marking the enclosing item with
+//! `#[cntp_i18n::allow_hardcoded]`.
+//!
+//! The command exits with a non-zero status if any untranslated text is found.
+//!
+//! ### Working with translators
+//!
+//! ```bash
+//! cargo cntp-i18n export --format po --output translations.pot
+//! cargo cntp-i18n export --format po --language de --output de.po
+//! cargo cntp-i18n import de.po
+//! cargo cntp-i18n export --format xliff --language de --output de.xlf
+//! cargo cntp-i18n import de.xlf
+//! ```
+//!
+//! The `export` command writes the source strings, along with the descriptions and
+//! locations from `meta.json`, in a format translation tools understand. Without
+//! `--language` a template is exported; otherwise the existing translations for that
+//! language are included. Gettext PO and XLIFF 2.0 are supported.
+//!
+//! The `import` command writes the translations in a file back into the language's
+//! catalog. The format is inferred from the file extension, and the language from
+//! the file itself unless `--language` is passed. Translations that can't be imported,
+//! such as fuzzy translations, translations that change the placeholders of the source
+//! string or translations of strings that no longer exist, are reported and skipped.
+//!
 //! ## When to Use
 //!
-//! You can use this command as an alternative to integrating `cntp_i18n_gen` into
+//! You can use the `generate` command as an alternative to integrating `cntp_i18n_gen` into
 //! your `build.rs`. This is useful when:
 //!
 //! - You want to manually control when translation files are regenerated
@@ -51,10 +139,20 @@
 //! translation_directory = "translations"
 //! ```
 
-use std::process::exit;
+mod check;
+mod exchange;
+mod generate;
+mod lint;
+mod review;
+mod stats;
+
+use std::{
+    path::{Path, PathBuf},
+    process::exit,
+};
 
 use cargo_metadata::camino::Utf8PathBuf;
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use clap_cargo::style::CLAP_STYLING;
 use clap_verbosity_flag::InfoLevel;
 use cntp_i18n_gen::generate;
@@ -82,7 +180,105 @@ struct CntpI18nArgs {
 
 #[derive(Subcommand, Debug)]
 enum CntpI18nSubCommand {
-    Generate,
+    /// Generate the translation catalog and metadata from the source code
+    Generate {
+        /// Generate the catalogs of every workspace member that uses cntp_i18n
+        #[arg(long)]
+        workspace: bool,
+    },
+    /// Check translation catalogs for consistency with the source strings
+    Check {
+        /// The format to output the results in
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+
+        /// Fail on warnings, such as missing translations, as well as errors
+        #[arg(long)]
+        strict: bool,
+    },
+    /// Mark translations whose source string has changed as reviewed
+    Review {
+        /// The language of the translations to mark
+        #[arg(short, long)]
+        language: String,
+
+        /// The keys to mark, or none to mark every translation that needs review
+        keys: Vec<String>,
+    },
+    /// Report how much of each language has been translated
+    Stats {
+        /// The format to output the statistics in
+        #[arg(long, value_enum, default_value_t = StatsFormat::Table)]
+        format: StatsFormat,
+
+        /// Fail if any language has less than this percentage of strings translated
+        #[arg(long, value_name = "PERCENT")]
+        min_coverage: Option<f64>,
+    },
+    /// Find user-visible text that hasn't been wrapped in a translation macro
+    Lint {
+        /// The format to output the results in
+        #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+        format: OutputFormat,
+    },
+    /// Export strings for translation in a translation tool
+    Export {
+        /// The format to export the strings in
+        #[arg(long, value_enum)]
+        format: ExchangeFormat,
+
+        /// The language to export existing translations for, or none to export a template
+        #[arg(short, long)]
+        language: Option<String>,
+
+        /// The file to write to, or standard output if not specified
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Import translations from a translation tool into a catalog
+    Import {
+        /// The file to import
+        file: PathBuf,
+
+        /// The format of the file, inferred from its extension if not specified
+        #[arg(long, value_enum)]
+        format: Option<ExchangeFormat>,
+
+        /// The language to import into, overriding the language specified in the file
+        #[arg(short, long)]
+        language: Option<String>,
+    },
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum OutputFormat {
+    Text,
+    Json,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum StatsFormat {
+    Table,
+    Json,
+    Markdown,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum ExchangeFormat {
+    /// Gettext PO, or POT when exporting a template
+    Po,
+    /// XLIFF 2.0
+    Xliff,
+}
+
+impl ExchangeFormat {
+    fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "po" | "pot" => Some(ExchangeFormat::Po),
+            "xlf" | "xliff" => Some(ExchangeFormat::Xliff),
+            _ => None,
+        }
+    }
 }
 
 fn get_manifest_path(args: &CntpI18nArgs) -> anyhow::Result<Utf8PathBuf> {
@@ -107,6 +303,14 @@ fn main() {
         .with_max_level(args.verbosity)
         .init();
 
+    // A workspace doesn't need a root package, so generate it before looking for one
+    if let CntpI18nSubCommand::Generate { workspace: true } = args.command {
+        if !generate::generate_workspace(&args.manifest) {
+            exit(1);
+        }
+        return;
+    }
+
     let path = get_manifest_path(&args);
 
     if let Err(reason) = path {
@@ -120,8 +324,59 @@ fn main() {
     }
 
     match args.command {
-        CntpI18nSubCommand::Generate => {
+        CntpI18nSubCommand::Generate { .. } => {
             generate(path.unwrap().as_std_path());
         }
+        CntpI18nSubCommand::Check { format, strict } => {
+            if !check::check(path.unwrap().as_std_path(), format, strict) {
+                exit(1);
+            }
+        }
+        CntpI18nSubCommand::Review { language, keys } => {
+            if !review::review(path.unwrap().as_std_path(), &language, &keys) {
+                exit(1);
+            }
+        }
+        CntpI18nSubCommand::Stats {
+            format,
+            min_coverage,
+        } => {
+            if !stats::stats(path.unwrap().as_std_path(), format, min_coverage) {
+                exit(1);
+            }
+        }
+        CntpI18nSubCommand::Lint { format } => {
+            if !lint::lint(path.unwrap().as_std_path(), format) {
+                exit(1);
+            }
+        }
+        CntpI18nSubCommand::Export {
+            format,
+            language,
+            output,
+        } => {
+            if !exchange::export(
+                path.unwrap().as_std_path(),
+                format,
+                language.as_deref(),
+                output.as_deref(),
+            ) {
+                exit(1);
+            }
+        }
+        CntpI18nSubCommand::Import {
+            file,
+            format,
+            language,
+        } => {
+            if !exchange::import(
+                path.unwrap().as_std_path(),
+                &file,
+                format,
+                language.as_deref(),
+            ) {
+                exit(1);
+            }
+        }
     }
 }
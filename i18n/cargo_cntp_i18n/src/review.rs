@@ -0,0 +1,20 @@
+use std::path::Path;
+
+use tracing::{error, info};
+
+/// Marks translations as reviewed and returns whether they were marked successfully.
+pub fn review(manifest_directory: &Path, language: &str, keys: &[String]) -> bool {
+    match cntp_i18n_gen::review::mark_reviewed(manifest_directory, language, keys) {
+        Ok(marked) => {
+            for key in &marked {
+                info!("{}: {}: marked as reviewed", language, key);
+            }
+            info!("{} translation(s) marked as reviewed", marked.len());
+            true
+        }
+        Err(e) => {
+            error!("failed to mark translations as reviewed: {}", e);
+            false
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use std::path::Path;
+
+use cntp_i18n_gen::stats::{LanguageStats, StatsReport};
+use tracing::{error, info};
+
+use crate::StatsFormat;
+
+const HEADERS: [&str; 6] = [
+    "Language",
+    "Translated",
+    "Missing",
+    "Empty",
+    "Coverage",
+    "Untranslated words",
+];
+
+/// Outputs translation statistics and returns whether every language meets the minimum coverage.
+pub fn stats(manifest_directory: &Path, format: StatsFormat, min_coverage: Option<f64>) -> bool {
+    let report = match cntp_i18n_gen::stats::stats(manifest_directory) {
+        Ok(report) => report,
+        Err(e) => {
+            error!("failed to calculate translation statistics: {}", e);
+            return false;
+        }
+    };
+
+    match format {
+        StatsFormat::Table => print_table(&report),
+        StatsFormat::Markdown => print_markdown(&report),
+        StatsFormat::Json => match serde_json::to_string_pretty(&report) {
+            Ok(json) => println!("{json}"),
+            Err(e) => {
+                error!("failed to serialize translation statistics: {}", e);
+                return false;
+            }
+        },
+    }
+
+    let Some(min_coverage) = min_coverage else {
+        return true;
+    };
+
+    let below_minimum = report
+        .languages
+        .iter()
+        .filter(|language| language.coverage < min_coverage)
+        .collect::<Vec<_>>();
+    for language in &below_minimum {
+        error!(
+            "{} is {:.1}% translated, below the minimum coverage of {:.1}%",
+            language.language, language.coverage, min_coverage
+        );
+    }
+
+    if below_minimum.is_empty() {
+        info!(
+            "all languages meet the minimum coverage of {:.1}%",
+            min_coverage
+        );
+    }
+    below_minimum.is_empty()
+}
+
+fn row(language: &LanguageStats) -> [String; 6] {
+    [
+        language.language.clone(),
+        format!("{}/{}", language.translated, language.total),
+        language.missing.to_string(),
+        language.empty.to_string(),
+        format!("{:.1}%", language.coverage),
+        language.untranslated_words.to_string(),
+    ]
+}
+
+fn print_table(report: &StatsReport) {
+    let rows = report.languages.iter().map(row).collect::<Vec<_>>();
+    let widths = HEADERS.map(str::len);
+    let widths = rows.iter().fold(widths, |mut widths, row| {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+        widths
+    });
+
+    let format_row = |cells: &[String]| {
+        cells
+            .iter()
+            .zip(widths)
+            .enumerate()
+            .map(|(column, (cell, width))| {
+                // Left align the language and right align the numbers
+                if column == 0 {
+                    format!("{cell:<width$}")
+                } else {
+                    format!("{cell:>width$}")
+                }
+            })
+            .collect::<Vec<_>>()
+            .join("  ")
+    };
+
+    println!("{}", format_row(&HEADERS.map(str::to_string)));
+    for row in rows {
+        println!("{}", format_row(&row));
+    }
+}
+
+fn print_markdown(report: &StatsReport) {
+    println!("| {} |", HEADERS.join(" | "));
+    println!("|---|---:|---:|---:|---:|---:|");
+    for language in &report.languages {
+        println!("| {} |", row(language).join(" | "));
+    }
+}
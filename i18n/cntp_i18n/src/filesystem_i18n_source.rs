@@ -0,0 +1,327 @@
+use crate::I18N_MANAGER;
+use cntp_i18n_core::string::I18nString;
+use cntp_i18n_core::{
+    I18nEntry, I18nPluralStringEntry, I18nSelectStringEntry, I18nSource, I18nStringPart,
+};
+use cntp_localesupport::Locale;
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::thread;
+use std::time::{Duration, SystemTime};
+use tracing::{error, info, warn};
+
+const WATCH_TRANSLATIONS_ENV_VAR: &str = "CNTP_I18N_WATCH_TRANSLATIONS";
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+struct LoadedEntry {
+    /// The entry as it appears in the catalog, used to detect changes.
+    raw: Value,
+    entry: &'static I18nEntry<'static>,
+}
+
+type Languages = HashMap<String, HashMap<String, LoadedEntry>>;
+
+/// A translation source that loads catalogs from a directory at runtime.
+///
+/// The directory is expected to contain catalogs in the same JSON format read by
+/// [`tr_load!`](crate::tr_load), named `<language>.json`. The directory is watched,
+/// and when a catalog changes, the affected keys are evicted from the
+/// [`I18N_MANAGER`] cache and the cache eviction subscribers are notified, so
+/// that the new translations are shown without rebuilding the application.
+///
+/// Only languages requested by the current locale are looked up. Strings that are
+/// missing or empty in the directory fall through to the next source, which is
+/// usually the one loaded with [`tr_load!`](crate::tr_load).
+///
+/// This source is intended for translators previewing their work, and leaks
+/// memory each time a translation changes.
+pub struct FilesystemI18nSource {
+    crate_name: String,
+    languages: Arc<RwLock<Languages>>,
+}
+
+impl FilesystemI18nSource {
+    /// Create a source that loads the catalogs in `directory` for the crate `crate_name`,
+    /// and starts watching the directory for changes.
+    pub fn new(directory: impl Into<PathBuf>, crate_name: impl Into<String>) -> Self {
+        let directory = directory.into();
+        let languages = Arc::new(RwLock::new(Languages::new()));
+        let mut modified_times = HashMap::new();
+        reload(&directory, &languages, &mut modified_times);
+
+        let weak_languages = Arc::downgrade(&languages);
+        let spawn_result = thread::Builder::new()
+            .name("cntp-i18n-watcher".into())
+            .spawn(move || {
+                loop {
+                    thread::sleep(POLL_INTERVAL);
+
+                    let Some(languages) = weak_languages.upgrade() else {
+                        return;
+                    };
+
+                    let changed_keys = reload(&directory, &languages, &mut modified_times);
+                    if !changed_keys.is_empty() {
+                        I18N_MANAGER
+                            .read()
+                            .unwrap()
+                            .evict_keys(changed_keys.iter().map(String::as_str));
+                    }
+                }
+            });
+        if let Err(e) = spawn_result {
+            error!("Unable to watch translation directory: {:?}", e);
+        }
+
+        Self {
+            crate_name: crate_name.into(),
+            languages,
+        }
+    }
+
+    /// Load a [`FilesystemI18nSource`] into the [`I18N_MANAGER`] if the
+    /// `CNTP_I18N_WATCH_TRANSLATIONS` environment variable is set.
+    ///
+    /// This is usually called through the [`tr_watch!`](crate::tr_watch) macro, which
+    /// passes the crate's translation directory.
+    pub fn install_if_enabled(directory: impl Into<PathBuf>, crate_name: impl Into<String>) {
+        if std::env::var(WATCH_TRANSLATIONS_ENV_VAR).is_err() {
+            return;
+        }
+
+        let directory = directory.into();
+        let crate_name = crate_name.into();
+        info!(
+            "Watching {} for translations of {}",
+            directory.display(),
+            crate_name
+        );
+        I18N_MANAGER
+            .write()
+            .unwrap()
+            .load_source(Self::new(directory, crate_name));
+    }
+}
+
+impl I18nSource for FilesystemI18nSource {
+    fn lookup(
+        &'_ self,
+        locale: &Locale,
+        id: &str,
+        lookup_crate: &str,
+    ) -> Option<&'_ I18nEntry<'_>> {
+        if self.crate_name != lookup_crate {
+            return None;
+        }
+
+        let languages = self.languages.read().unwrap();
+        locale
+            .messages
+            .iter()
+            .find_map(|language| languages.get(language)?.get(id))
+            .map(|loaded| loaded.entry)
+    }
+
+    fn available_languages(&self) -> Option<Vec<String>> {
+        Some(self.languages.read().unwrap().keys().cloned().collect())
+    }
+
+    fn lookup_language(&self, locale: &Locale, id: &str, lookup_crate: &str) -> Option<String> {
+        if self.crate_name != lookup_crate {
+            return None;
+        }
+
+        let languages = self.languages.read().unwrap();
+        locale
+            .messages
+            .iter()
+            .find(|language| {
+                languages
+                    .get(*language)
+                    .is_some_and(|entries| entries.contains_key(id))
+            })
+            .cloned()
+    }
+
+    fn name(&self) -> String {
+        format!("Translation directory for {}", self.crate_name)
+    }
+}
+
+/// Reload every catalog in `directory` that has changed since it was last loaded.
+///
+/// Returns the keys whose translations have changed in any language.
+fn reload(
+    directory: &Path,
+    languages: &RwLock<Languages>,
+    modified_times: &mut HashMap<PathBuf, SystemTime>,
+) -> HashSet<String> {
+    let mut changed_keys = HashSet::new();
+
+    let catalog_files = match fs::read_dir(directory) {
+        Ok(dir_contents) => dir_contents
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                let modified_time = fs::metadata(&path).ok()?.modified().ok()?;
+                (path.extension()? == "json" && path.file_name()? != "meta.json")
+                    .then_some((path, modified_time))
+            })
+            .collect::<HashMap<_, _>>(),
+        Err(_) => HashMap::new(),
+    };
+
+    // Catalogs that have been deleted
+    modified_times.retain(|path, _| {
+        if catalog_files.contains_key(path) {
+            return true;
+        }
+
+        if let Some(language) = catalog_language(path)
+            && let Some(entries) = languages.write().unwrap().remove(&language)
+        {
+            changed_keys.extend(entries.into_keys());
+        }
+        false
+    });
+
+    for (path, modified_time) in catalog_files {
+        if modified_times.get(&path) == Some(&modified_time) {
+            continue;
+        }
+        modified_times.insert(path.clone(), modified_time);
+
+        let Some(language) = catalog_language(&path) else {
+            continue;
+        };
+
+        let catalog = match fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|contents| {
+                serde_json::from_str::<Map<String, Value>>(&contents).map_err(|e| e.to_string())
+            }) {
+            Ok(catalog) => catalog,
+            Err(e) => {
+                warn!("Unable to load translations from {}: {}", path.display(), e);
+                continue;
+            }
+        };
+
+        let mut languages = languages.write().unwrap();
+        let mut old_entries = languages.remove(&language).unwrap_or_default();
+        let mut new_entries = HashMap::new();
+        for (key, raw) in catalog {
+            if let Some(old_entry) = old_entries.remove(&key)
+                && old_entry.raw == raw
+            {
+                new_entries.insert(key, old_entry);
+                continue;
+            }
+
+            changed_keys.insert(key.clone());
+            if let Some(entry) = parse_entry(&language, &raw) {
+                new_entries.insert(
+                    key,
+                    LoadedEntry {
+                        raw,
+                        entry: Box::leak(Box::new(entry)),
+                    },
+                );
+            }
+        }
+
+        // Keys that have been removed from the catalog
+        changed_keys.extend(old_entries.into_keys());
+        languages.insert(language, new_entries);
+    }
+
+    changed_keys
+}
+
+fn catalog_language(path: &Path) -> Option<String> {
+    Some(path.file_stem()?.to_str()?.to_string())
+}
+
+/// Convert a catalog entry into an [`I18nEntry`], or `None` if it has no translation.
+fn parse_entry(language: &str, raw: &Value) -> Option<I18nEntry<'static>> {
+    match raw {
+        Value::String(string) if !string.is_empty() => Some(I18nEntry::Entry(parse_parts(string))),
+        Value::Object(object) if object.contains_key("select") => {
+            let cases = object.get("select")?.as_object()?;
+            let case = |string: &Value| {
+                string
+                    .as_str()
+                    .filter(|string| !string.is_empty())
+                    .map(parse_parts)
+            };
+
+            Some(I18nEntry::SelectEntry(I18nSelectStringEntry {
+                cases: cases
+                    .iter()
+                    .filter(|(name, _)| *name != "other")
+                    .filter_map(|(name, string)| {
+                        Some((I18nString::from(name.clone()), case(string)?))
+                    })
+                    .collect::<Vec<_>>()
+                    .leak(),
+                other: case(cases.get("other")?)?,
+            }))
+        }
+        Value::Object(object) => {
+            let ordinal_forms = object.get("ordinal").and_then(Value::as_object);
+            let forms = ordinal_forms.unwrap_or(object);
+            let form = |category: &str| {
+                forms
+                    .get(category)
+                    .and_then(Value::as_str)
+                    .filter(|form| !form.is_empty())
+                    .map(parse_parts)
+            };
+
+            Some(I18nEntry::PluralEntry(I18nPluralStringEntry {
+                locale: I18nString::from(language.to_string()),
+                ordinal: ordinal_forms.is_some(),
+                zero: form("zero"),
+                one: form("one"),
+                two: form("two"),
+                few: form("few"),
+                many: form("many"),
+                other: form("other")?,
+            }))
+        }
+        _ => None,
+    }
+}
+
+/// Split a translation string into static text and `{{variables}}`.
+///
+/// Variables are given an index hint of 0, so they are resolved by name.
+fn parse_parts(string: &str) -> &'static [I18nStringPart] {
+    let mut parts = Vec::new();
+    let mut remaining = string;
+    while let Some(start) = remaining.find("{{") {
+        let Some(length) = remaining[start + 2..].find("}}") else {
+            break;
+        };
+
+        if start > 0 {
+            parts.push(I18nStringPart::Static(
+                remaining[..start].to_string().into(),
+            ));
+        }
+        let name = &remaining[start + 2..start + 2 + length];
+        parts.push(if name == "count" {
+            I18nStringPart::Count(0)
+        } else {
+            I18nStringPart::Variable(name.to_string().into(), 0)
+        });
+        remaining = &remaining[start + 2 + length + 2..];
+    }
+    if !remaining.is_empty() {
+        parts.push(I18nStringPart::Static(remaining.to_string().into()));
+    }
+    parts.leak()
+}
@@ -27,6 +27,47 @@
 //! }
 //! ```
 //!
+//! ### Previewing translations
+//!
+//! Translations loaded with [`tr_load!`] are embedded at compile time, so translators would
+//! need to rebuild the application to see their work. Calling [`tr_watch!`] after [`tr_load!`]
+//! loads a [`FilesystemI18nSource`] that reads the crate's translation directory at runtime
+//! and reloads translations as the files change, if the `CNTP_I18N_WATCH_TRANSLATIONS`
+//! environment variable is set:
+//!
+//! ```rust,ignore
+//! use cntp_i18n::{I18N_MANAGER, tr_load, tr_watch};
+//!
+//! fn main() {
+//!     I18N_MANAGER.write().unwrap().load_source(tr_load!());
+//!     tr_watch!();
+//! }
+//! ```
+//!
+//! ```bash
+//! CNTP_I18N_WATCH_TRANSLATIONS=1 cargo run
+//! ```
+//!
+//! ### Reporting missing translations
+//!
+//! [`I18nManager::subscribe_to_missing_translations`] reports every lookup that falls back
+//! to the default language or to the key. To collect them into a report file, call
+//! [`missing_translations::install_if_enabled`] at startup and run the application with
+//! the `CNTP_I18N_REPORT_MISSING_TRANSLATIONS` environment variable set to the path of the
+//! report:
+//!
+//! ```bash
+//! CNTP_I18N_REPORT_MISSING_TRANSLATIONS=missing-translations.json cargo run
+//! ```
+//!
+//! ### Library crates
+//!
+//! Library crates load their own translations with [`tr_load!`], and strings are always
+//! looked up in the translations of the crate that uses them. Applications can replace
+//! specific strings of a library with [`tr_override!`]. See [`I18nManager`] for the full
+//! lookup order, and [`I18nManager::translation_origins`] to find out where a string
+//! comes from.
+//!
 //! ## Translation files
 //! Translation files are stored in the `translations` directory (configurable via `i18n.toml`):
 //!
@@ -93,6 +134,54 @@
 //! );
 //! ```
 //!
+//! For ordinal numbers such as "1st" and "2nd", add `#ordinal = true` and provide a string for
+//! each ordinal category of the default language:
+//!
+//! ```rust,ignore
+//! trn!(
+//!     "FINISHED_PLACE",
+//!     "You finished in {{count}}st place",
+//!     "You finished in {{count}}nd place",
+//!     "You finished in {{count}}rd place",
+//!     "You finished in {{count}}th place",
+//!     count = place,
+//!     #ordinal = true
+//! );
+//! ```
+//!
+//! ### Select strings
+//!
+//! Use `trs!` for strings that vary based on the value of a variable, such as the grammatical
+//! gender of a person. The `other` case is required, and is used when no other case matches:
+//!
+//! ```rust,ignore
+//! trs!(
+//!     "PROFILE_UPDATED",
+//!     masculine => "{{name}} updated his profile.",
+//!     feminine => "{{name}} updated her profile.",
+//!     other => "{{name}} updated their profile.",
+//!     select = user.gender,
+//!     name = user.name
+//! );
+//! ```
+//!
+//! Translators can add cases that their language needs, and remove cases that it doesn't.
+//!
+//! ### Rich text
+//!
+//! Strings can contain `<b>`, `<i>` and `<a href="name">` markup. `tr!` removes markup by
+//! default; pass `#markup = true` to keep it, and resolve it into styled runs with
+//! [`markup::parse_markup`]:
+//!
+//! ```rust,ignore
+//! let accept = tr!(
+//!     "ACCEPT_TERMS",
+//!     "By continuing, you accept the <a href=\"terms\">terms of service</a>.",
+//!     #markup = true
+//! );
+//! let runs = markup::parse_markup(&accept).runs;
+//! ```
+//!
 //! ## Configuration
 //!
 //! Create an `i18n.toml` file in your project root to customize behavior:
@@ -102,19 +191,26 @@
 //! default_language = "en"
 //! translation_directory = "translations"
 //! match_line_endings = true
+//! placeholder_validation = "warn"
 //! ```
 //!
+//! `placeholder_validation` controls what happens when a translation references a variable that
+//! isn't passed to the macro, or leaves out one the source string uses. It can be `"allow"`,
+//! `"warn"` (the default) or `"deny"`, which fails compilation.
+//!
 //! ## Feature flags
 //!
 //! - **`gpui`** - Enables automatic conversion of translation results to GPUI's `SharedString`.
 //! This allows the text to be used as an child easily, without any additional function calls.
 //! - **`chrono`** - Enables `chrono` date/time type support in the [`Date`] modifier.
+//! - **`pseudotranslation`** - Enables [`pseudotranslation`], for testing an application's
+//!   readiness for translation.
 //!
 //! ## Architecture
 //!
 //! This crate re-exports types from several internal crates:
 //!
-//! - Macros ([`tr!`], [`trn!`], [`trf!`], [`tr_noop!`], [`trn_noop!`], [`tr_load!`]) from `cntp_i18n_macros`
+//! - Macros ([`tr!`], [`trn!`], [`trs!`], [`trf!`], [`tr_noop!`], [`trn_noop!`], [`tr_load!`], [`tr_watch!`], [`tr_override!`], [`macro@allow_hardcoded`]) from `cntp_i18n_macros`
 //! - Core types ([`I18nSource`], [`I18nEntry`], etc.) from `cntp_i18n_core`
 //! - Locale support ([`Locale`], [`LocaleFormattable`], modifiers) from `cntp_localesupport`
 //!
@@ -124,29 +220,45 @@
 
 #![warn(missing_docs)]
 
-pub use cntp_i18n_macros::{tr, tr_load, tr_noop, trf, trn, trn_noop};
+pub use cntp_i18n_macros::{
+    allow_hardcoded, tr, tr_load, tr_noop, tr_override, tr_watch, trf, trn, trn_noop, trs, trs_noop,
+};
 use cntp_localesupport::modifiers::ModifierVariable;
 use once_cell::sync::Lazy;
 use quick_cache::sync::Cache;
 use rustc_hash::FxHasher;
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
+use std::iter::once;
 use std::sync::RwLock;
 
+pub use crate::filesystem_i18n_source::FilesystemI18nSource;
 use crate::hardcoded_i18n_source::HardcodedI18nSource;
+#[cfg(feature = "pseudotranslation")]
+use cntp_i18n_core::markup::map_markup_text;
+use cntp_i18n_core::markup::{escape_markup, strip_markup};
 pub use cntp_i18n_core::{
-    I18nEntry, I18nPluralStringEntry, I18nSource, I18nStringEntry, I18nStringPart,
-    string::I18nString,
+    I18nEntry, I18nPluralStringEntry, I18nSelectStringEntry, I18nSource, I18nStringEntry,
+    I18nStringPart, markup, string::I18nString,
 };
 pub use cntp_localesupport::locale_formattable::LocaleFormattable;
 pub use cntp_localesupport::modifiers::{Date, Quote, StringModifier};
 pub use cntp_localesupport::{LayoutDirection, ListFunction, Locale};
 pub use phf;
 
+mod filesystem_i18n_source;
 mod hardcoded_i18n_source;
+pub mod missing_translations;
+mod negotiation;
+
+#[cfg(feature = "pseudotranslation")]
+pub mod pseudotranslation;
 
 #[cfg(feature = "pseudotranslation")]
-mod pseudotranslation;
+use crate::pseudotranslation::Pseudotranslation;
+#[cfg(feature = "pseudotranslation")]
+use cntp_localesupport::set_layout_direction_override;
 
 /// The global i18n manager instance.
 ///
@@ -211,11 +323,27 @@ macro_rules! i18n_manager {
 ///
 /// # Translation Lookup Order
 ///
-/// When looking up a translation, the manager searches through sources in reverse order based
-/// on when they were loaded. The first source that provides a non-empty translation wins.
-/// If no translation is found, the key itself is returned.
+/// Every translation belongs to the crate that calls the macro, and each crate usually loads
+/// its own translations with [`tr_load!`], including library crates such as widget toolkits.
+/// When looking up a translation, the manager searches:
+///
+/// 1. the overrides for the key registered with [`override_translations`](Self::override_translations),
+///    most recently registered first, looking up the same key as if it belonged to the
+///    overriding crate
+/// 2. the translations of the crate itself
+///
+/// At each step, sources are searched in reverse order based on when they were loaded. This
+/// is repeated for each language in the locale's message chain, in order of preference, and
+/// only then for the default languages the sources fall back to. An override in a language
+/// the user doesn't prefer therefore doesn't hide a translation of the crate itself in one
+/// they do. The first source that provides a non-empty translation wins. If no translation
+/// is found, the key itself is returned.
+///
+/// Use [`translation_origins`](Self::translation_origins) to find out which source a
+/// translation comes from.
 pub struct I18nManager {
     sources: Vec<Box<dyn I18nSource>>,
+    overrides: Vec<TranslationOverride>,
     /// The current locale used for translation lookups.
     ///
     /// This determines which language translations are retrieved in, as well as
@@ -225,6 +353,10 @@ pub struct I18nManager {
     key_hashes: RwLock<HashMap<String, Vec<u64>>>,
 
     cache_eviction_callbacks: Vec<Box<dyn Fn() + Send + Sync>>,
+    missing_translation_callbacks: Vec<MissingTranslationCallback>,
+
+    #[cfg(feature = "pseudotranslation")]
+    pseudotranslation: Option<Pseudotranslation>,
 }
 
 /// Internal trait for type-erased string modifier transformations.
@@ -307,6 +439,8 @@ pub enum Variable<'a> {
     String(String),
     /// A count variable for plural lookups.
     Count(isize),
+    /// A select variable for select lookups.
+    Select(String),
 }
 
 impl Variable<'_> {
@@ -317,12 +451,59 @@ impl Variable<'_> {
             }
             Variable::String(string) => string.hash(state),
             Variable::Count(count) => count.hash(state),
+            Variable::Select(value) => value.hash(state),
         }
     }
 }
 
 type LookupVariable<'a> = &'a (&'a str, Variable<'a>);
 
+type MissingTranslationCallback = Box<dyn Fn(&MissingTranslation) + Send + Sync>;
+
+/// Translations of one crate that are replaced by the translations of another crate.
+struct TranslationOverride {
+    overridden_crate: String,
+    overriding_crate: String,
+    keys: HashSet<String>,
+}
+
+/// A source that provides a translation, as reported by
+/// [`I18nManager::translation_origins`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranslationOrigin {
+    /// The name of the source (see [`I18nSource::name`]).
+    pub source: String,
+    /// The crate whose translations contain the string. This is a different crate to the
+    /// one that looked up the string if the string has been overridden.
+    pub crate_name: String,
+    /// Whether the translation is empty, in which case it is skipped during lookup.
+    pub empty: bool,
+}
+
+/// A translation that wasn't available in the language it was looked up in, as reported to
+/// the callbacks registered with [`I18nManager::subscribe_to_missing_translations`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MissingTranslation {
+    /// The most preferred language of the locale the translation was looked up in.
+    pub language: String,
+    /// The crate that looked up the translation.
+    pub crate_name: String,
+    /// The translation key.
+    pub key: String,
+    /// What was shown instead of the translation.
+    pub fallback: FallbackLevel,
+}
+
+/// What is shown in place of a missing translation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FallbackLevel {
+    /// The string in a language the locale doesn't request, usually the default language of
+    /// the crate.
+    DefaultLanguage,
+    /// The key itself, because no source has the string in any language.
+    Key,
+}
+
 impl I18nManager {
     /// Load a translation source into the manager.
     ///
@@ -361,6 +542,151 @@ impl I18nManager {
         self.clear_cache();
     }
 
+    /// List the languages that translations are available in, sorted alphabetically.
+    ///
+    /// This is the combination of the languages of every loaded source that reports its
+    /// languages (see [`I18nSource::available_languages`]). It can be used to list the
+    /// options in a language picker.
+    pub fn available_languages(&self) -> Vec<String> {
+        let mut languages = self
+            .sources
+            .iter()
+            .filter_map(|source| source.available_languages())
+            .flatten()
+            .collect::<Vec<_>>();
+        languages.sort();
+        languages.dedup();
+        languages
+    }
+
+    /// Pick the available language that best matches a list of requested languages.
+    ///
+    /// The requested languages are tried in order of preference. Each one matches an
+    /// available language with the same identifier, then one with subtags removed from the
+    /// end ("de-AT" matches "de"), then the same language in another region ("pt-BR" matches
+    /// "pt-PT"). Returns `None` if none of the requested languages are available.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use cntp_i18n::{I18N_MANAGER, Locale};
+    ///
+    /// let mut manager = I18N_MANAGER.write().unwrap();
+    /// let current = Locale::current();
+    /// if let Some(language) = manager.negotiate_language(&current.messages) {
+    ///     // Keep the user's formatting preferences
+    ///     manager.locale =
+    ///         Locale::new_from_parts(vec![language], current.numeric, current.time, current.collate);
+    /// }
+    /// ```
+    pub fn negotiate_language(&self, requested: &[String]) -> Option<String> {
+        negotiation::negotiate_language(requested, &self.available_languages())
+    }
+
+    /// Replace some translations of a crate with translations of another crate.
+    ///
+    /// When `overridden_crate` looks up one of `keys`, the translations of `overriding_crate`
+    /// are searched for the same key first. This lets an application replace specific
+    /// strings of a library crate that ships its own translations, by defining the same keys
+    /// in its own translation files (for example, with [`tr_noop!`]). Keys that the
+    /// overriding crate doesn't translate still use the library's translations.
+    ///
+    /// This is usually called through the [`tr_override!`] macro, which passes the current
+    /// crate as the overriding crate.
+    ///
+    /// Registering overrides will clear the translation cache.
+    pub fn override_translations<'a>(
+        &mut self,
+        overridden_crate: &str,
+        overriding_crate: &str,
+        keys: impl IntoIterator<Item = &'a str>,
+    ) {
+        self.overrides.push(TranslationOverride {
+            overridden_crate: overridden_crate.to_string(),
+            overriding_crate: overriding_crate.to_string(),
+            keys: keys.into_iter().map(str::to_string).collect(),
+        });
+        self.clear_cache();
+    }
+
+    /// List every source that provides a translation for a key in the current locale, in
+    /// the order they are searched.
+    ///
+    /// The first entry that is not [`empty`](TranslationOrigin::empty) is the one shown to
+    /// the user. This is intended for diagnosing why a string is displayed the way it is,
+    /// for example when overriding the translations of a library crate.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use cntp_i18n::i18n_manager;
+    ///
+    /// for origin in i18n_manager!().translation_origins("ABOUT_TITLE", "contemporary") {
+    ///     println!("{} ({})", origin.source, origin.crate_name);
+    /// }
+    /// ```
+    pub fn translation_origins(&self, key: &str, lookup_crate: &str) -> Vec<TranslationOrigin> {
+        self.entries(&self.locale, key, lookup_crate)
+            .map(|(source, crate_name, entry)| TranslationOrigin {
+                source: source.name(),
+                crate_name: crate_name.to_string(),
+                empty: is_empty_entry(entry),
+            })
+            .collect()
+    }
+
+    /// The crates whose translations are searched for a key, in order of precedence.
+    fn lookup_crates<'a>(
+        &'a self,
+        key: &'a str,
+        lookup_crate: &'a str,
+    ) -> impl Iterator<Item = &'a str> {
+        self.overrides
+            .iter()
+            .rev()
+            .filter(move |o| o.overridden_crate == lookup_crate && o.keys.contains(key))
+            .map(|o| o.overriding_crate.as_str())
+            .chain(once(lookup_crate))
+    }
+
+    /// Every entry for a key, in order of precedence, along with the source and crate it was
+    /// found in.
+    fn entries<'a>(
+        &'a self,
+        locale: &'a Locale,
+        key: &'a str,
+        lookup_crate: &'a str,
+    ) -> impl Iterator<Item = (&'a dyn I18nSource, &'a str, &'a I18nEntry<'a>)> {
+        let mut entries = self
+            .lookup_crates(key, lookup_crate)
+            .flat_map(move |crate_name| {
+                self.sources.iter().rev().filter_map(move |source| {
+                    let entry = source.lookup(locale, key, crate_name)?;
+                    Some((source.as_ref(), crate_name, entry))
+                })
+            })
+            .map(|(source, crate_name, entry)| {
+                // Sources that don't know the language of their entries are assumed to have
+                // the preferred language
+                let preference =
+                    source
+                        .lookup_language(locale, key, crate_name)
+                        .map_or(0, |language| {
+                            locale
+                                .messages
+                                .iter()
+                                .position(|preferred| *preferred == language)
+                                .unwrap_or(locale.messages.len())
+                        });
+                (preference, (source, crate_name, entry))
+            })
+            .collect::<Vec<_>>();
+
+        // The sort is stable, so the order of the crates and sources is kept for each language
+        entries.sort_by_key(|(preference, _)| *preference);
+        entries.into_iter().map(|(_, entry)| entry)
+    }
+
     /// Clear the translation cache.
     ///
     /// This can be used in the event that a dynamic translation provider is used, and the cache
@@ -375,9 +701,23 @@ impl I18nManager {
     /// This is intended to be used by translation providers to notify the
     /// translation system when translations for a key have changed or become invalid.
     pub fn evict_key(&self, key: &str) {
-        if let Some(hashes) = self.key_hashes.read().unwrap().get(key) {
-            for hash in hashes {
-                self.cache.remove(hash);
+        self.evict_keys([key]);
+    }
+
+    /// Evicts all translations for several keys from the cache
+    ///
+    /// This behaves like [`evict_key`](Self::evict_key), but only notifies the cache
+    /// eviction subscribers once, which avoids redundant work when many translations
+    /// change at the same time.
+    pub fn evict_keys<'a>(&self, keys: impl IntoIterator<Item = &'a str>) {
+        {
+            let key_hashes = self.key_hashes.read().unwrap();
+            for key in keys {
+                if let Some(hashes) = key_hashes.get(key) {
+                    for hash in hashes {
+                        self.cache.remove(hash);
+                    }
+                }
             }
         }
 
@@ -386,6 +726,33 @@ impl I18nManager {
         }
     }
 
+    /// Set the pseudotranslation modes to apply to every translation, or `None` to disable
+    /// pseudotranslation.
+    ///
+    /// By default, the modes are read from the `CNTP_I18N_ENABLE_PSEUDOTRANSLATION` environment
+    /// variable. If the right-to-left mode is enabled, the layout direction of every locale is
+    /// flipped to right-to-left. See [`pseudotranslation`] for the available modes.
+    ///
+    /// Setting the pseudotranslation modes will clear the translation cache.
+    #[cfg(feature = "pseudotranslation")]
+    pub fn set_pseudotranslation(&mut self, pseudotranslation: Option<Pseudotranslation>) {
+        set_layout_direction_override(
+            pseudotranslation
+                .as_ref()
+                .filter(|pseudotranslation| pseudotranslation.rtl)
+                .map(|_| LayoutDirection::RightToLeft),
+        );
+        self.pseudotranslation = pseudotranslation;
+        self.clear_cache();
+    }
+
+    /// Get the pseudotranslation modes applied to every translation, if pseudotranslation
+    /// is enabled.
+    #[cfg(feature = "pseudotranslation")]
+    pub fn pseudotranslation(&self) -> Option<&Pseudotranslation> {
+        self.pseudotranslation.as_ref()
+    }
+
     /// Subscribes a callback to be invoked during cache eviction events.
     ///
     /// This method allows users to register a callback function that will be executed
@@ -399,6 +766,64 @@ impl I18nManager {
         self.cache_eviction_callbacks.push(Box::new(callback));
     }
 
+    /// Subscribes a callback to be invoked when a translation is missing.
+    ///
+    /// The callback is invoked when a lookup falls back to a language the locale doesn't
+    /// request, or to the key itself. Lookups are cached, so the same missing translation
+    /// is usually reported once, but may be reported again after the cache is cleared.
+    ///
+    /// The callback is invoked while the [`I18N_MANAGER`] is locked for reading, so it must
+    /// not lock it for writing. See [`missing_translations`] for a ready-made callback that
+    /// writes a report of every missing translation to a file.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// use cntp_i18n::I18N_MANAGER;
+    ///
+    /// I18N_MANAGER
+    ///     .write()
+    ///     .unwrap()
+    ///     .subscribe_to_missing_translations(|missing| {
+    ///         eprintln!("{} is not translated into {}", missing.key, missing.language);
+    ///     });
+    /// ```
+    pub fn subscribe_to_missing_translations(
+        &mut self,
+        callback: impl Fn(&MissingTranslation) + Send + Sync + 'static,
+    ) {
+        self.missing_translation_callbacks.push(Box::new(callback));
+        self.clear_cache();
+    }
+
+    /// Report a lookup to the missing translation subscribers, if its translation is missing.
+    fn report_missing_translation(&self, key: &str, lookup_crate: &str, locale: &Locale) {
+        let fallback = match self
+            .entries(locale, key, lookup_crate)
+            .find(|(_, _, entry)| !is_empty_entry(entry))
+        {
+            None => FallbackLevel::Key,
+            Some((source, crate_name, _)) => {
+                match source.lookup_language(locale, key, crate_name) {
+                    Some(language) if !locale.messages.contains(&language) => {
+                        FallbackLevel::DefaultLanguage
+                    }
+                    _ => return,
+                }
+            }
+        };
+
+        let missing_translation = MissingTranslation {
+            language: locale.messages.first().cloned().unwrap_or_default(),
+            crate_name: lookup_crate.to_string(),
+            key: key.to_string(),
+            fallback,
+        };
+        for callback in self.missing_translation_callbacks.iter() {
+            callback(&missing_translation);
+        }
+    }
+
     /// Look up a translation with caching.
     ///
     /// This method first checks the cache for a previously resolved translation.
@@ -406,7 +831,7 @@ impl I18nManager {
     ///
     /// # Note
     ///
-    /// This is an internal method used by the [`tr!`] and [`trn!`] macros.
+    /// This is an internal method used by the [`tr!`], [`trn!`] and [`trs!`] macros.
     /// You should use those macros instead of calling this directly.
     ///
     /// If you need functionality not provided by the macros, please file an issue.
@@ -418,9 +843,42 @@ impl I18nManager {
         lookup_crate: &str,
         hash: u64,
         locale_override: Option<&Locale>,
+    ) -> I18nString {
+        self.lookup_cached_with_markup(key, variables, lookup_crate, hash, locale_override, false)
+    }
+
+    /// Look up a translation with caching, keeping its rich text markup.
+    ///
+    /// Substituted variables are escaped, so that they are never interpreted as markup.
+    ///
+    /// # Note
+    ///
+    /// This is an internal method used by the [`tr!`], [`trn!`] and [`trs!`] macros when
+    /// `#markup = true` is passed. You should use those macros instead of calling this directly.
+    #[doc(hidden)]
+    pub fn lookup_markup_cached<'a>(
+        &self,
+        key: &str,
+        variables: &'a [Option<LookupVariable<'a>>],
+        lookup_crate: &str,
+        hash: u64,
+        locale_override: Option<&Locale>,
+    ) -> I18nString {
+        self.lookup_cached_with_markup(key, variables, lookup_crate, hash, locale_override, true)
+    }
+
+    fn lookup_cached_with_markup<'a>(
+        &self,
+        key: &str,
+        variables: &'a [Option<LookupVariable<'a>>],
+        lookup_crate: &str,
+        hash: u64,
+        locale_override: Option<&Locale>,
+        markup: bool,
     ) -> I18nString {
         let mut state = FxHasher::default();
         hash.hash(&mut state);
+        markup.hash(&mut state);
         for variable in variables.iter().flatten() {
             variable.1.hash_value(&mut state);
         }
@@ -430,7 +888,8 @@ impl I18nManager {
         let full_call_hash = state.finish();
 
         self.cache.get(&full_call_hash).clone().unwrap_or_else(|| {
-            let result = self.lookup(key, variables, lookup_crate, locale_override);
+            let result =
+                self.lookup_with_markup(key, variables, lookup_crate, locale_override, markup);
             self.cache.insert(full_call_hash, result.clone());
             self.key_hashes
                 .write()
@@ -445,12 +904,12 @@ impl I18nManager {
     /// Look up a translation from the loaded sources.
     ///
     /// This method searches through all loaded translation sources to find a match
-    /// for the given key. Variable substitution and plural resolution are handled
+    /// for the given key. Variable substitution, plural and select resolution are handled
     /// automatically.
     ///
     /// # Note
     ///
-    /// This is an internal method used by the [`tr!`] and [`trn!`] macros.
+    /// This is an internal method used by the [`tr!`], [`trn!`] and [`trs!`] macros.
     /// You should use those macros instead of calling this directly.
     ///
     /// If you need functionality not provided by the macros, please file an issue.
@@ -461,14 +920,25 @@ impl I18nManager {
         variables: &'a [Option<LookupVariable<'a>>],
         lookup_crate: &str,
         locale_override: Option<&Locale>,
+    ) -> I18nString {
+        self.lookup_with_markup(key, variables, lookup_crate, locale_override, false)
+    }
+
+    fn lookup_with_markup<'a>(
+        &self,
+        key: &str,
+        variables: &'a [Option<LookupVariable<'a>>],
+        lookup_crate: &str,
+        locale_override: Option<&Locale>,
+        markup: bool,
     ) -> I18nString {
         let locale = locale_override.unwrap_or(&self.locale);
 
-        for source in self.sources.iter().rev() {
-            let Some(entry) = source.lookup(locale, key, lookup_crate) else {
-                continue;
-            };
+        if !self.missing_translation_callbacks.is_empty() {
+            self.report_missing_translation(key, lookup_crate, locale);
+        }
 
+        for (_, _, entry) in self.entries(locale, key, lookup_crate) {
             let parts: &[I18nStringPart] = match &entry {
                 I18nEntry::Entry(entry) => entry,
                 I18nEntry::PluralEntry(entry) => {
@@ -490,9 +960,14 @@ impl I18nManager {
                         Variable::Count(count) => {
                             // Plural entries with count substitution always need allocation
                             let plural_parts = entry.lookup(*count, locale);
-                            return self.resolve_parts_to_string(&plural_parts, variables, locale);
+                            return self.resolve_parts_to_string(
+                                &plural_parts,
+                                variables,
+                                locale,
+                                markup,
+                            );
                         }
-                        Variable::String(string) => {
+                        Variable::String(string) | Variable::Select(string) => {
                             panic!("Count variable ({string}) not of type isize")
                         }
                         Variable::Modified(_inital, _subsequent) => {
@@ -500,6 +975,26 @@ impl I18nManager {
                         }
                     }
                 }
+                I18nEntry::SelectEntry(entry) => {
+                    let (_, value) = variables
+                        .iter()
+                        .find(|variable| match variable {
+                            Some((name, _)) => *name == "select",
+                            None => false,
+                        })
+                        .and_then(Option::as_deref)
+                        .unwrap_or_else(|| {
+                            panic!(
+                                "Resolved select string for {key}, but no select variable \
+                                provided for substitution",
+                            )
+                        });
+
+                    match value {
+                        Variable::Select(value) => entry.lookup(value),
+                        _ => panic!("Select variable for {key} not of type String"),
+                    }
+                }
             };
 
             // Fast path: if there's exactly one static part with no variables, return borrowed
@@ -509,16 +1004,17 @@ impl I18nManager {
             {
                 #[cfg(feature = "pseudotranslation")]
                 {
-                    if std::env::var("CNTP_I18N_ENABLE_PSEUDOTRANSLATION").is_ok() {
-                        return pseudotranslation::contain(
-                            &pseudotranslation::mangle(&s.to_string()),
-                            s.len(),
-                        )
-                        .into();
+                    if let Some(pseudotranslation) = &self.pseudotranslation {
+                        return pseudotranslation
+                            .contain(&mangle_static(pseudotranslation, s, markup), s.len())
+                            .into();
                     }
                 }
 
-                return s.clone();
+                return match resolve_static(s, markup) {
+                    Cow::Borrowed(_) => s.clone(),
+                    Cow::Owned(stripped) => stripped.into(),
+                };
             }
 
             // Check if all parts are static (no variable substitution needed)
@@ -532,7 +1028,7 @@ impl I18nManager {
                     .iter()
                     .filter_map(|p| {
                         if let I18nStringPart::Static(s) = p {
-                            Some(s.as_ref())
+                            Some(resolve_static(s, markup))
                         } else {
                             None
                         }
@@ -547,7 +1043,7 @@ impl I18nManager {
             }
 
             // Slow path: need variable substitution
-            let resolved = self.resolve_parts_to_string(parts, variables, locale);
+            let resolved = self.resolve_parts_to_string(parts, variables, locale, markup);
 
             // If the translation is empty, fall back to the next source
             if resolved.is_empty() {
@@ -566,9 +1062,19 @@ impl I18nManager {
         parts: &[I18nStringPart],
         variables: &'a [Option<LookupVariable<'a>>],
         locale: &Locale,
+        markup: bool,
     ) -> I18nString {
         let mut result = String::new();
 
+        // Substituted values are always displayed as-is, even when they look like markup
+        let escape = |value: &str| -> String {
+            if markup {
+                escape_markup(value).into_owned()
+            } else {
+                value.to_string()
+            }
+        };
+
         #[cfg(feature = "pseudotranslation")]
         let mut len = 0;
 
@@ -577,18 +1083,16 @@ impl I18nManager {
                 I18nStringPart::Static(borrowed) => {
                     #[cfg(feature = "pseudotranslation")]
                     {
-                        if std::env::var("CNTP_I18N_ENABLE_PSEUDOTRANSLATION").is_ok() {
+                        if let Some(pseudotranslation) = &self.pseudotranslation {
                             len += borrowed.len();
-                            result.push_str(
-                                pseudotranslation::mangle(&borrowed.to_string()).as_ref(),
-                            );
+                            result.push_str(&mangle_static(pseudotranslation, borrowed, markup));
                         } else {
-                            result.push_str(borrowed)
+                            result.push_str(&resolve_static(borrowed, markup))
                         }
                     }
 
                     #[cfg(not(feature = "pseudotranslation"))]
-                    result.push_str(borrowed)
+                    result.push_str(&resolve_static(borrowed, markup))
                 }
                 I18nStringPart::Variable(variable, idx) => {
                     let substituted_variable = variables
@@ -614,30 +1118,31 @@ impl I18nManager {
 
                             #[cfg(feature = "pseudotranslation")]
                             {
-                                if std::env::var("CNTP_I18N_ENABLE_PSEUDOTRANSLATION").is_ok() {
-                                    result.push_str(&pseudotranslation::contain_variable(
-                                        &modify_result,
+                                if let Some(pseudotranslation) = &self.pseudotranslation {
+                                    result.push_str(&escape(
+                                        &pseudotranslation.contain_variable(&modify_result),
                                     ));
                                 } else {
-                                    result.push_str(&modify_result);
+                                    result.push_str(&escape(&modify_result));
                                 }
                             }
 
                             #[cfg(not(feature = "pseudotranslation"))]
-                            result.push_str(&modify_result);
+                            result.push_str(&escape(&modify_result));
                         }
-                        Some(Variable::String(str)) => {
+                        Some(Variable::String(str) | Variable::Select(str)) => {
                             #[cfg(feature = "pseudotranslation")]
                             {
-                                if std::env::var("CNTP_I18N_ENABLE_PSEUDOTRANSLATION").is_ok() {
-                                    result.push_str(&pseudotranslation::contain_variable(str))
+                                if let Some(pseudotranslation) = &self.pseudotranslation {
+                                    result
+                                        .push_str(&escape(&pseudotranslation.contain_variable(str)))
                                 } else {
-                                    result.push_str(str)
+                                    result.push_str(&escape(str))
                                 }
                             }
 
                             #[cfg(not(feature = "pseudotranslation"))]
-                            result.push_str(str)
+                            result.push_str(&escape(str))
                         }
                         Some(Variable::Count(_)) => {
                             panic!("Unexpected count variable")
@@ -654,22 +1159,150 @@ impl I18nManager {
         }
 
         #[cfg(feature = "pseudotranslation")]
-        if std::env::var("CNTP_I18N_ENABLE_PSEUDOTRANSLATION").is_ok() {
-            return I18nString::Owned(pseudotranslation::contain(&result, len).into());
+        if let Some(pseudotranslation) = &self.pseudotranslation {
+            return I18nString::Owned(pseudotranslation.contain(&result, len).into());
         }
 
         I18nString::Owned(result.into())
     }
 }
 
+/// Whether an entry has no text, in which case it is skipped during lookup.
+fn is_empty_entry(entry: &I18nEntry) -> bool {
+    matches!(entry, I18nEntry::Entry(parts) if parts.iter().all(
+        |part| matches!(part, I18nStringPart::Static(s) if s.is_empty())
+    ))
+}
+
+/// Prepare static text from a translation for output, removing its markup unless it is kept.
+fn resolve_static(part: &str, markup: bool) -> Cow<'_, str> {
+    if markup {
+        Cow::Borrowed(part)
+    } else {
+        strip_markup(part)
+    }
+}
+
+#[cfg(feature = "pseudotranslation")]
+fn mangle_static(pseudotranslation: &Pseudotranslation, part: &str, markup: bool) -> String {
+    if markup {
+        map_markup_text(part, |text| pseudotranslation.mangle(text))
+    } else {
+        pseudotranslation.mangle(&strip_markup(part))
+    }
+}
+
 impl Default for I18nManager {
     fn default() -> Self {
-        I18nManager {
+        #[allow(unused_mut)]
+        let mut manager = I18nManager {
             sources: vec![Box::new(HardcodedI18nSource)],
+            overrides: vec![],
             locale: Locale::current(),
             cache: Cache::new(500),
             key_hashes: RwLock::new(HashMap::new()),
             cache_eviction_callbacks: vec![],
+            missing_translation_callbacks: vec![],
+            #[cfg(feature = "pseudotranslation")]
+            pseudotranslation: None,
+        };
+
+        #[cfg(feature = "pseudotranslation")]
+        manager.set_pseudotranslation(Pseudotranslation::from_env());
+
+        manager
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::I18nManager;
+    use cntp_i18n_core::string::I18nString;
+    use cntp_i18n_core::{I18nEntry, I18nSource, I18nStringPart};
+    use cntp_localesupport::Locale;
+
+    /// A source for a single crate that falls back to English, like the generated ones do.
+    struct CrateSource {
+        crate_name: &'static str,
+        entries: Vec<(&'static str, I18nEntry<'static>)>,
+    }
+
+    impl CrateSource {
+        fn new(crate_name: &'static str, entries: &[(&'static str, &'static str)]) -> Self {
+            CrateSource {
+                crate_name,
+                entries: entries
+                    .iter()
+                    .map(|(language, text)| {
+                        let parts = vec![I18nStringPart::Static(I18nString::Borrowed(text))];
+                        (*language, I18nEntry::Entry(parts.leak()))
+                    })
+                    .collect(),
+            }
+        }
+
+        fn find(&self, locale: &Locale, lookup_crate: &str) -> Option<&(&str, I18nEntry<'_>)> {
+            if self.crate_name != lookup_crate {
+                return None;
+            }
+
+            locale
+                .messages
+                .iter()
+                .map(String::as_str)
+                .chain(["en"])
+                .find_map(|language| {
+                    self.entries
+                        .iter()
+                        .find(|(entry_language, _)| *entry_language == language)
+                })
+        }
+    }
+
+    impl I18nSource for CrateSource {
+        fn lookup(
+            &'_ self,
+            locale: &Locale,
+            _: &str,
+            lookup_crate: &str,
+        ) -> Option<&'_ I18nEntry<'_>> {
+            self.find(locale, lookup_crate).map(|(_, entry)| entry)
         }
+
+        fn lookup_language(&self, locale: &Locale, _: &str, lookup_crate: &str) -> Option<String> {
+            self.find(locale, lookup_crate)
+                .map(|(language, _)| language.to_string())
+        }
+    }
+
+    fn manager(locale: &str) -> I18nManager {
+        let mut manager = I18nManager::default();
+        manager.load_source(CrateSource::new(
+            "library",
+            &[("en", "Hello"), ("de", "Hallo")],
+        ));
+        manager.load_source(CrateSource::new("app", &[("en", "Hi")]));
+        manager.override_translations("library", "app", ["GREETING"]);
+        manager.locale = Locale::new_from_locale_identifier(locale);
+        manager
+    }
+
+    #[test]
+    fn override_applies_in_its_language() {
+        let manager = manager("en");
+        assert_eq!(&*manager.lookup("GREETING", &[], "library", None), "Hi");
+    }
+
+    #[test]
+    fn override_does_not_hide_preferred_language() {
+        let manager = manager("de");
+        assert_eq!(&*manager.lookup("GREETING", &[], "library", None), "Hallo");
+
+        let origins = manager.translation_origins("GREETING", "library");
+        let crates = origins
+            .iter()
+            .map(|origin| origin.crate_name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(crates, ["library", "app"]);
     }
 }
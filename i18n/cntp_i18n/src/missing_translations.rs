@@ -0,0 +1,130 @@
+//! Reporting of the translations that are missing while an application is used.
+//!
+//! When the `CNTP_I18N_REPORT_MISSING_TRANSLATIONS` environment variable is set to a file
+//! path, [`install_if_enabled`] records every missing translation that is looked up, and
+//! writes them to that file. This lets someone walk through an application in a language
+//! and get a list of every untranslated string they came across:
+//!
+//! ```bash
+//! CNTP_I18N_REPORT_MISSING_TRANSLATIONS=missing-translations.json cargo run
+//! ```
+//!
+//! The report is a JSON object mapping each language to the strings that were missing in
+//! it:
+//!
+//! ```json
+//! {
+//!   "fr": [
+//!     {
+//!       "crate": "contemporary",
+//!       "fallback": "defaultLanguage",
+//!       "key": "ABOUT_TITLE"
+//!     }
+//!   ]
+//! }
+//! ```
+//!
+//! The report is written shortly after new missing translations are found, so it is
+//! complete when the application exits, even if it doesn't exit cleanly.
+
+use crate::{FallbackLevel, I18N_MANAGER, MissingTranslation};
+use serde_json::{Value, json};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+use tracing::{error, info};
+
+const REPORT_MISSING_TRANSLATIONS_ENV_VAR: &str = "CNTP_I18N_REPORT_MISSING_TRANSLATIONS";
+
+/// How long to wait for more missing translations before writing the report.
+const WRITE_DELAY: Duration = Duration::from_millis(500);
+
+/// Record every missing translation that is looked up, and write them to a report at `path`.
+///
+/// The missing translations are collected on a background thread, which rewrites the
+/// report whenever new ones are found.
+pub fn install(path: impl Into<PathBuf>) {
+    let path = path.into();
+    let (sender, receiver) = mpsc::channel::<MissingTranslation>();
+
+    let spawn_result = thread::Builder::new()
+        .name("cntp-i18n-missing-translations".into())
+        .spawn(move || {
+            let mut missing_translations = BTreeSet::new();
+            let mut changed = false;
+            loop {
+                match receiver.recv_timeout(WRITE_DELAY) {
+                    Ok(missing_translation) => {
+                        changed |= missing_translations.insert(missing_translation);
+                    }
+                    Err(RecvTimeoutError::Timeout) if changed => {
+                        write_report(&path, &missing_translations);
+                        changed = false;
+                    }
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Disconnected) => {
+                        if changed {
+                            write_report(&path, &missing_translations);
+                        }
+                        return;
+                    }
+                }
+            }
+        });
+    if let Err(e) = spawn_result {
+        error!("Unable to report missing translations: {:?}", e);
+        return;
+    }
+
+    I18N_MANAGER
+        .write()
+        .unwrap()
+        .subscribe_to_missing_translations(move |missing_translation| {
+            let _ = sender.send(missing_translation.clone());
+        });
+}
+
+/// Call [`install`] with the path in the `CNTP_I18N_REPORT_MISSING_TRANSLATIONS`
+/// environment variable, if it is set.
+pub fn install_if_enabled() {
+    let Some(path) = std::env::var_os(REPORT_MISSING_TRANSLATIONS_ENV_VAR) else {
+        return;
+    };
+
+    let path = PathBuf::from(path);
+    info!("Reporting missing translations to {}", path.display());
+    install(path);
+}
+
+fn write_report(path: &Path, missing_translations: &BTreeSet<MissingTranslation>) {
+    let mut report = BTreeMap::<&str, Vec<Value>>::new();
+    for missing_translation in missing_translations {
+        let fallback = match missing_translation.fallback {
+            FallbackLevel::DefaultLanguage => "defaultLanguage",
+            FallbackLevel::Key => "key",
+        };
+
+        report
+            .entry(&missing_translation.language)
+            .or_default()
+            .push(json!({
+                "crate": missing_translation.crate_name,
+                "key": missing_translation.key,
+                "fallback": fallback,
+            }));
+    }
+
+    let result = serde_json::to_string_pretty(&report)
+        .map_err(|e| e.to_string())
+        .and_then(|contents| fs::write(path, contents).map_err(|e| e.to_string()));
+    if let Err(e) = result {
+        error!(
+            "Unable to write missing translation report to {}: {}",
+            path.display(),
+            e
+        );
+    }
+}
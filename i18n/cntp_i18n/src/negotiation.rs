@@ -0,0 +1,120 @@
+/// Split a language identifier into lowercase subtags, accepting `_` as a separator.
+fn subtags(language: &str) -> Vec<String> {
+    language
+        .split(['-', '_'])
+        .filter(|subtag| !subtag.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect()
+}
+
+/// The script subtag of a language identifier (e.g., "hant" in "zh-Hant-TW"), if any.
+fn script(subtags: &[String]) -> Option<&str> {
+    subtags
+        .get(1)
+        .filter(|subtag| subtag.len() == 4 && subtag.chars().all(|c| c.is_ascii_alphabetic()))
+        .map(String::as_str)
+}
+
+/// Pick the available language that best matches the requested languages.
+///
+/// Requested languages are tried in order of preference. For each one, the available
+/// languages are searched for:
+///
+/// 1. the same language (e.g., "de-AT" for "de-AT")
+/// 2. the language with subtags removed from the end (e.g., "de" for "de-AT")
+/// 3. the same language in another region (e.g., "pt-PT" for "pt-BR"), as long as the
+///    script matches
+///
+/// before moving on to the next requested language.
+pub(crate) fn negotiate_language(requested: &[String], available: &[String]) -> Option<String> {
+    let available = available
+        .iter()
+        .map(|language| (language, subtags(language)))
+        .collect::<Vec<_>>();
+
+    requested.iter().find_map(|language| {
+        let requested = subtags(language);
+        if requested.is_empty() {
+            return None;
+        }
+
+        (1..=requested.len())
+            .rev()
+            .find_map(|len| {
+                available
+                    .iter()
+                    .find(|(_, subtags)| subtags[..] == requested[..len])
+            })
+            .or_else(|| {
+                available.iter().find(|(_, subtags)| {
+                    subtags.first() == requested.first()
+                        && (script(&requested).is_none() || script(subtags) == script(&requested))
+                })
+            })
+            .map(|(language, _)| language.to_string())
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::negotiation::negotiate_language;
+
+    fn negotiate(requested: &[&str], available: &[&str]) -> Option<String> {
+        let requested = requested.iter().map(|l| l.to_string()).collect::<Vec<_>>();
+        let available = available.iter().map(|l| l.to_string()).collect::<Vec<_>>();
+        negotiate_language(&requested, &available)
+    }
+
+    #[test]
+    fn exact_match() {
+        assert_eq!(
+            negotiate(&["de-AT"], &["de", "de-AT"]).as_deref(),
+            Some("de-AT")
+        );
+    }
+
+    #[test]
+    fn parent_language() {
+        assert_eq!(negotiate(&["de-AT"], &["en", "de"]).as_deref(), Some("de"));
+    }
+
+    #[test]
+    fn other_region() {
+        assert_eq!(
+            negotiate(&["pt-BR"], &["en", "pt-PT"]).as_deref(),
+            Some("pt-PT")
+        );
+        assert_eq!(
+            negotiate(&["pt-BR"], &["pt-PT", "pt-BR"]).as_deref(),
+            Some("pt-BR")
+        );
+    }
+
+    #[test]
+    fn script_mismatch() {
+        assert_eq!(negotiate(&["zh-Hant"], &["zh-CN"]), None);
+        assert_eq!(
+            negotiate(&["zh-Hant"], &["zh-CN", "zh-Hant-TW"]).as_deref(),
+            Some("zh-Hant-TW")
+        );
+    }
+
+    #[test]
+    fn underscore_separators() {
+        assert_eq!(negotiate(&["de_AT"], &["de"]).as_deref(), Some("de"));
+        assert_eq!(negotiate(&["pt-BR"], &["pt_BR"]).as_deref(), Some("pt_BR"));
+    }
+
+    #[test]
+    fn order_of_preference() {
+        assert_eq!(
+            negotiate(&["fr", "de-AT"], &["de", "fr-CA"]).as_deref(),
+            Some("fr-CA")
+        );
+        assert_eq!(
+            negotiate(&["eo", "de"], &["de", "fr"]).as_deref(),
+            Some("de")
+        );
+        assert_eq!(negotiate(&["eo"], &["de", "fr"]), None);
+    }
+}
@@ -1,3 +1,168 @@
+//! Pseudotranslation, for testing an application's readiness for translation.
+//!
+//! Pseudotranslation replaces every translation with a modified version of the source text,
+//! so that problems can be found before real translations are available:
+//!
+//! - **Accents** replace letters with accented versions, revealing text that is not
+//!   translatable because it stays unaccented.
+//! - **Expansion** pads each translation, as translations are often longer than the source
+//!   text. This reveals text that is truncated or overflows its container.
+//! - **Brackets** surround each translation with `[` and `]`, and each substituted variable
+//!   with `»` and `«`. Missing brackets reveal truncation, and several bracketed strings next to
+//!   each other reveal sentences built by concatenating strings.
+//! - **RTL** displays text right-to-left using bidirectional control characters, and flips
+//!   [`Locale::layout_direction`] to right-to-left. This reveals layout and mirroring problems
+//!   before right-to-left translations, such as Arabic or Hebrew, are available.
+//!
+//! Pseudotranslation is enabled by setting the `CNTP_I18N_ENABLE_PSEUDOTRANSLATION`
+//! environment variable, or with [`I18nManager::set_pseudotranslation`]. The environment
+//! variable can contain a comma separated list of modes to enable:
+//!
+//! ```sh
+//! # Accents, 70% expansion and brackets
+//! CNTP_I18N_ENABLE_PSEUDOTRANSLATION=1
+//!
+//! # Only 30% expansion and brackets
+//! CNTP_I18N_ENABLE_PSEUDOTRANSLATION=expand=30,brackets
+//!
+//! # Right-to-left with accents
+//! CNTP_I18N_ENABLE_PSEUDOTRANSLATION=rtl,accents
+//! ```
+//!
+//! [`Locale::layout_direction`]: cntp_localesupport::Locale::layout_direction
+//! [`I18nManager::set_pseudotranslation`]: crate::I18nManager::set_pseudotranslation
+
+use tracing::warn;
+
+/// The environment variable that enables pseudotranslation.
+const PSEUDOTRANSLATION_ENV_VAR: &str = "CNTP_I18N_ENABLE_PSEUDOTRANSLATION";
+
+/// Starts right-to-left embedding for a whole translation.
+const RIGHT_TO_LEFT_EMBEDDING: char = '\u{202B}';
+/// Displays static text right-to-left, as if it were written in a right-to-left script.
+const RIGHT_TO_LEFT_OVERRIDE: char = '\u{202E}';
+/// Ends an embedding or override.
+const POP_DIRECTIONAL_FORMATTING: char = '\u{202C}';
+
+/// The modes of pseudotranslation to apply.
+///
+/// The default enables accents, 70% expansion and brackets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pseudotranslation {
+    /// Replace letters with accented versions.
+    pub accents: bool,
+    /// Pad each translation by this percentage of its length.
+    pub expansion: usize,
+    /// Surround translations with `[` and `]`, and substituted variables with `»` and `«`.
+    pub brackets: bool,
+    /// Display translations right-to-left, and flip the layout direction to right-to-left.
+    pub rtl: bool,
+}
+
+impl Default for Pseudotranslation {
+    fn default() -> Self {
+        Pseudotranslation {
+            accents: true,
+            expansion: 70,
+            brackets: true,
+            rtl: false,
+        }
+    }
+}
+
+impl Pseudotranslation {
+    /// Read the pseudotranslation modes from the `CNTP_I18N_ENABLE_PSEUDOTRANSLATION`
+    /// environment variable, or `None` if it is not set.
+    pub fn from_env() -> Option<Self> {
+        std::env::var(PSEUDOTRANSLATION_ENV_VAR)
+            .ok()
+            .map(|modes| Self::from_modes(&modes))
+    }
+
+    /// Parse a comma separated list of modes.
+    ///
+    /// The modes are `accents`, `brackets`, `rtl` and `expand=<percentage>`. Modes that are
+    /// not listed are disabled. If the list is empty, `1` or `true`, the default modes are used.
+    pub fn from_modes(modes: &str) -> Self {
+        let modes = modes.trim();
+        if modes.is_empty() || modes == "1" || modes.eq_ignore_ascii_case("true") {
+            return Self::default();
+        }
+
+        let mut pseudotranslation = Pseudotranslation {
+            accents: false,
+            expansion: 0,
+            brackets: false,
+            rtl: false,
+        };
+        for mode in modes.split(',').map(str::trim) {
+            match mode.split_once('=') {
+                None if mode == "accents" => pseudotranslation.accents = true,
+                None if mode == "brackets" => pseudotranslation.brackets = true,
+                None if mode == "rtl" => pseudotranslation.rtl = true,
+                Some(("expand", percentage)) => match percentage.parse() {
+                    Ok(percentage) => pseudotranslation.expansion = percentage,
+                    Err(_) => warn!("Invalid pseudotranslation expansion {percentage}"),
+                },
+                _ => warn!("Unknown pseudotranslation mode {mode}"),
+            }
+        }
+        pseudotranslation
+    }
+
+    /// Modify static text from a translation.
+    pub(crate) fn mangle(&self, string: &str) -> String {
+        let string = if self.accents {
+            accent(string)
+        } else {
+            string.to_string()
+        };
+
+        if self.rtl && !string.is_empty() {
+            format!("{RIGHT_TO_LEFT_OVERRIDE}{string}{POP_DIRECTIONAL_FORMATTING}")
+        } else {
+            string
+        }
+    }
+
+    /// Finish a translation, given the length of its static text.
+    pub(crate) fn contain(&self, string: &str, len: usize) -> String {
+        let mut extension_length = (len * self.expansion) / 100;
+        let mut all_extensions = NUMBER_EXTENSIONS.to_vec();
+        let mut extensions = Vec::new();
+
+        while extension_length > 0 {
+            let next_extension = all_extensions.pop().unwrap();
+            extension_length = extension_length.saturating_sub(next_extension.len());
+            extensions.push(self.mangle(next_extension));
+            if all_extensions.is_empty() {
+                all_extensions = NUMBER_EXTENSIONS.to_vec();
+            }
+        }
+
+        let mut contained = string.to_string();
+        if !extensions.is_empty() {
+            contained = format!("{contained} {}", extensions.join(" "));
+        }
+        if self.brackets {
+            contained = format!("[{contained}]");
+        }
+        if self.rtl {
+            contained = format!("{RIGHT_TO_LEFT_EMBEDDING}{contained}{POP_DIRECTIONAL_FORMATTING}");
+        }
+        contained
+    }
+
+    /// Modify a substituted variable.
+    pub(crate) fn contain_variable(&self, string: &str) -> String {
+        if self.brackets {
+            format!("»{string}«")
+        } else {
+            string.to_string()
+        }
+    }
+}
+
 const NUMBER_EXTENSIONS: &[&str] = &[
     "twenty",
     "nineteen",
@@ -21,7 +186,7 @@ const NUMBER_EXTENSIONS: &[&str] = &[
     "one",
 ];
 
-pub fn mangle(string: &String) -> String {
+fn accent(string: &str) -> String {
     string
         .chars()
         .map(|c| match c {
@@ -74,23 +239,89 @@ pub fn mangle(string: &String) -> String {
         .collect::<String>()
 }
 
-pub fn contain(string: &String, len: usize) -> String {
-    let mut extension_length = (len * 7) / 10;
-    let mut all_extensions = NUMBER_EXTENSIONS.to_vec();
-    let mut extensions = Vec::new();
-
-    while extension_length > 0 {
-        let next_extension = all_extensions.pop().unwrap();
-        extension_length = extension_length.saturating_sub(next_extension.len());
-        extensions.push(next_extension);
-        if all_extensions.is_empty() {
-            all_extensions = NUMBER_EXTENSIONS.to_vec();
+#[cfg(test)]
+mod test {
+    use crate::pseudotranslation::{
+        POP_DIRECTIONAL_FORMATTING, Pseudotranslation, RIGHT_TO_LEFT_EMBEDDING,
+        RIGHT_TO_LEFT_OVERRIDE,
+    };
+
+    fn modes(accents: bool, expansion: usize, brackets: bool, rtl: bool) -> Pseudotranslation {
+        Pseudotranslation {
+            accents,
+            expansion,
+            brackets,
+            rtl,
         }
     }
 
-    format!("[{} {}]", string, extensions.join(" "))
-}
+    #[test]
+    fn default_modes() {
+        assert_eq!(
+            Pseudotranslation::from_modes(""),
+            Pseudotranslation::default()
+        );
+        assert_eq!(
+            Pseudotranslation::from_modes("1"),
+            Pseudotranslation::default()
+        );
+        assert_eq!(
+            Pseudotranslation::from_modes(" TRUE "),
+            Pseudotranslation::default()
+        );
+        assert_eq!(Pseudotranslation::default(), modes(true, 70, true, false));
+    }
+
+    #[test]
+    fn listed_modes() {
+        assert_eq!(
+            Pseudotranslation::from_modes("expand=30,brackets"),
+            modes(false, 30, true, false)
+        );
+        assert_eq!(
+            Pseudotranslation::from_modes("rtl, accents"),
+            modes(true, 0, false, true)
+        );
+    }
+
+    #[test]
+    fn unknown_modes() {
+        assert_eq!(
+            Pseudotranslation::from_modes("brackets,sparkles,expand=lots"),
+            modes(false, 0, true, false)
+        );
+    }
 
-pub fn contain_variable(string: &String) -> String {
-    format!("»{}«", string)
+    #[test]
+    fn brackets() {
+        let pseudotranslation = modes(false, 0, true, false);
+        assert_eq!(pseudotranslation.contain("Hello", 5), "[Hello]");
+        assert_eq!(pseudotranslation.contain_variable("name"), "»name«");
+
+        let pseudotranslation = modes(false, 60, true, false);
+        assert_eq!(pseudotranslation.contain("Hello", 5), "[Hello one]");
+    }
+
+    #[test]
+    fn right_to_left() {
+        let pseudotranslation = modes(false, 0, true, true);
+        assert_eq!(
+            pseudotranslation.mangle("Hello"),
+            format!("{RIGHT_TO_LEFT_OVERRIDE}Hello{POP_DIRECTIONAL_FORMATTING}")
+        );
+        assert_eq!(pseudotranslation.mangle(""), "");
+        assert_eq!(
+            pseudotranslation.contain("Hello", 5),
+            format!("{RIGHT_TO_LEFT_EMBEDDING}[Hello]{POP_DIRECTIONAL_FORMATTING}")
+        );
+
+        // The expansion is static text, so it is displayed right-to-left as well
+        let pseudotranslation = modes(false, 60, false, true);
+        assert_eq!(
+            pseudotranslation.contain("Hello", 5),
+            format!(
+                "{RIGHT_TO_LEFT_EMBEDDING}Hello {RIGHT_TO_LEFT_OVERRIDE}one{POP_DIRECTIONAL_FORMATTING}{POP_DIRECTIONAL_FORMATTING}"
+            )
+        );
+    }
 }
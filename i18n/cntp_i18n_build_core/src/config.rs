@@ -12,17 +12,34 @@
 //! default_language = "en"           # Source language (default: "en")
 //! translation_directory = "translations"  # Where translation files are stored
 //! match_line_endings = true         # Normalize line endings to platform default
+//! placeholder_validation = "warn"   # How to report translations with mismatched placeholders
+//! stale_translations = "show"       # Whether to show translations of outdated source strings
+//!
+//! # Macros that wrap `tr!` or `trn!`, and the argument positions of their strings
+//! [i18n.macros.menu_item]
+//! key = 0
+//! default = 1
+//!
+//! # Which languages `tr_load!` embeds (default: every language)
+//! [i18n.embed]
+//! languages = ["en", "de", "fr"]    # Only embed these languages
+//! exclude = ["eo"]                  # Never embed these languages
+//! min_coverage = 80                 # Only embed languages with this percentage translated
+//! features = true                   # Only embed a language if the `lang-<language>` feature is on
 //! ```
 //!
 //! If no configuration file exists, the defaults above are used.
 
 use std::{
+    collections::{HashMap, HashSet},
     fs::{OpenOptions, create_dir_all},
     io::Read,
     path::{Path, PathBuf},
 };
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+
+use crate::load::TranslationEntry;
 
 /// Load the i18n configuration from the project's `i18n.toml` file.
 ///
@@ -104,6 +121,172 @@ pub struct I18n {
     ///
     /// Default: `true`
     pub match_line_endings: bool,
+
+    /// How to report translations whose placeholders don't match the variables passed to
+    /// `tr!`, `trn!` or `trs!` at compile time.
+    ///
+    /// Default: [`PlaceholderValidation::Warn`]
+    pub placeholder_validation: PlaceholderValidation,
+
+    /// Whether `tr_load!` includes translations that were made for an older version of their
+    /// source string.
+    ///
+    /// Default: [`StaleTranslations::Show`]
+    pub stale_translations: StaleTranslations,
+
+    /// Macros that wrap `tr!` or `trn!`, keyed by macro name.
+    ///
+    /// Strings passed to these macros are extracted into the translation catalog in the same
+    /// way as strings passed to `tr!` and `trn!`.
+    ///
+    /// Default: no macros
+    pub macros: HashMap<String, MacroMapping>,
+
+    /// Which languages `tr_load!` embeds into the binary.
+    ///
+    /// Default: every language
+    pub embed: Embed,
+}
+
+/// Which languages `tr_load!` embeds into the binary.
+///
+/// This corresponds to the `[i18n.embed]` section in `i18n.toml`. The default language is
+/// always embedded, as it is used when a translation is missing.
+#[derive(Deserialize, Default, Debug, Clone)]
+#[serde(default, deny_unknown_fields)]
+pub struct Embed {
+    /// The languages to embed. If empty, every language is embedded.
+    pub languages: Vec<String>,
+
+    /// Languages that are never embedded.
+    pub exclude: Vec<String>,
+
+    /// The minimum percentage of strings that a language must have translated to be
+    /// embedded, for leaving out unfinished translations.
+    pub min_coverage: Option<f64>,
+
+    /// Whether each language is only embedded when the cargo feature named
+    /// `lang-<language>` (see [`Embed::feature`]) is enabled in the crate calling `tr_load!`.
+    /// The crate needs to declare these features in its `Cargo.toml`.
+    pub features: bool,
+}
+
+impl Embed {
+    /// Get the reason a language is not embedded, or `None` if it is.
+    ///
+    /// This doesn't take [`features`](Self::features) into account, which are checked when
+    /// the crate is compiled.
+    ///
+    /// # Arguments
+    ///
+    /// * `language` - The language of the catalog
+    /// * `default_language` - The default language, which is always embedded
+    /// * `catalog` - The strings in the catalog
+    /// * `keys` - The keys of every string defined in the source code, used to calculate
+    ///   the coverage of the catalog
+    pub fn excluded_reason(
+        &self,
+        language: &str,
+        default_language: &str,
+        catalog: &[(String, TranslationEntry)],
+        keys: &[&str],
+    ) -> Option<String> {
+        if language == default_language {
+            return None;
+        }
+
+        if !self.languages.is_empty() && !self.languages.iter().any(|l| l == language) {
+            return Some("it is not listed in the languages to embed".to_string());
+        }
+
+        if self.exclude.iter().any(|l| l == language) {
+            return Some("it is excluded".to_string());
+        }
+
+        if let Some(min_coverage) = self.min_coverage
+            && !keys.is_empty()
+        {
+            let translated_keys = catalog
+                .iter()
+                .filter(|(_, entry)| !entry.is_empty())
+                .map(|(key, _)| key.as_str())
+                .collect::<HashSet<_>>();
+            let translated = keys
+                .iter()
+                .filter(|key| translated_keys.contains(**key))
+                .count();
+            let coverage = translated as f64 / keys.len() as f64 * 100.0;
+            if coverage < min_coverage {
+                return Some(format!(
+                    "it is {coverage:.1}% translated, below the minimum coverage of \
+                    {min_coverage:.1}%"
+                ));
+            }
+        }
+
+        None
+    }
+
+    /// Get the name of the cargo feature that enables a language when
+    /// [`features`](Self::features) is set.
+    pub fn feature(language: &str) -> String {
+        format!("lang-{language}")
+    }
+}
+
+/// The positions of the arguments of a macro that wraps `tr!` or `trn!`.
+///
+/// Positions are counted from zero, and include named arguments such as `name = value`.
+///
+/// ```toml
+/// [i18n.macros.menu_item]
+/// key = 0
+/// default = 1
+///
+/// [i18n.macros.count_label]
+/// key = 0
+/// plural = [1, 2]
+/// ```
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct MacroMapping {
+    /// The position of the translation key.
+    pub key: usize,
+    /// The position of the default string, for macros that wrap `tr!`.
+    #[serde(default)]
+    pub default: Option<usize>,
+    /// The positions of the plural forms of the default string, in the order of the default
+    /// language's plural categories, for macros that wrap `trn!`.
+    #[serde(default)]
+    pub plural: Vec<usize>,
+}
+
+/// How the translation macros report translations with mismatched placeholders.
+///
+/// A translation is mismatched if it references a variable that isn't passed to the macro,
+/// uses a modifier inside a placeholder, or leaves out a variable that the source string uses.
+#[derive(Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PlaceholderValidation {
+    /// Don't check placeholders.
+    Allow,
+    /// Emit a compiler warning for each mismatched translation.
+    #[default]
+    Warn,
+    /// Fail compilation for each mismatched translation.
+    Deny,
+}
+
+/// How `tr_load!` handles translations that need to be reviewed because their source string
+/// has changed since they were translated.
+#[derive(Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum StaleTranslations {
+    /// Show the translation until it is reviewed.
+    #[default]
+    Show,
+    /// Show the string in the default language until the translation is reviewed.
+    Fallback,
 }
 
 impl Default for I18n {
@@ -112,6 +295,10 @@ impl Default for I18n {
             default_language: "en".into(),
             translation_directory: "translations".into(),
             match_line_endings: true,
+            placeholder_validation: PlaceholderValidation::default(),
+            stale_translations: StaleTranslations::default(),
+            macros: HashMap::new(),
+            embed: Embed::default(),
         }
     }
 }
@@ -14,8 +14,10 @@
 //!
 //! - [`config`] - Configuration file (`i18n.toml`) loading and parsing
 //! - [`load`] - Translation file (`.json`) loading utilities
+//! - [`placeholder`] - Placeholder (`{{name}}`) extraction utilities
 
 #![warn(missing_docs)]
 
 pub mod config;
 pub mod load;
+pub mod placeholder;
@@ -8,6 +8,8 @@
 //! Translation files are JSON objects mapping keys to either:
 //! - A string (for simple translations)
 //! - An object with plural categories (for plural translations)
+//! - An object with an `ordinal` object of plural categories (for ordinal plural translations)
+//! - An object with a `select` object of cases (for select translations)
 //!
 //! ### Simple translations
 //!
@@ -28,21 +30,91 @@
 //!     }
 //! }
 //! ```
+//!
+//! ### Ordinal plural translations
+//!
+//! Ordinal plural translations use the language's ordinal plural categories,
+//! rather than its cardinal plural categories.
+//!
+//! ```json
+//! {
+//!     "FINISHED_PLACE": {
+//!         "ordinal": {
+//!             "one": "You finished in {{count}}st place",
+//!             "two": "You finished in {{count}}nd place",
+//!             "few": "You finished in {{count}}rd place",
+//!             "other": "You finished in {{count}}th place"
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! ### Select translations
+//!
+//! The cases of a select translation are keyed by the value of the `select`
+//! variable. The "other" case is used when no other case matches.
+//!
+//! ```json
+//! {
+//!     "PROFILE_UPDATED": {
+//!         "select": {
+//!             "masculine": "{{name}} updated his profile",
+//!             "feminine": "{{name}} updated her profile",
+//!             "other": "{{name}} updated their profile"
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! ## Metadata
+//!
+//! The `meta.json` file generated alongside the default language's catalog
+//! describes every string that is currently defined in the source code:
+//!
+//! ```json
+//! {
+//!     "ITEMS": {
+//!         "context": "main.rs",
+//!         "definedIn": "src/main.rs:12",
+//!         "plural": true,
+//!         "ordinal": false,
+//!         "select": false,
+//!         "description": null,
+//!         "sourceHash": "5f1a0c2e9b3d4a78",
+//!         "translatedFrom": {
+//!             "de": {
+//!                 "sourceHash": "5f1a0c2e9b3d4a78",
+//!                 "translationHash": "c04e1b7d22f9a613"
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! `sourceHash` is a hash of the string in the default language's catalog, and
+//! `translatedFrom` records, for each language, the hash of the source string at
+//! the time the translation last changed. A translation whose recorded source hash
+//! differs from the current one was made for an older version of the source string,
+//! and needs to be reviewed.
 
 use std::{
     collections::HashMap,
     fs::OpenOptions,
     io::{self, ErrorKind},
+    iter::once,
     path::Path,
 };
 
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
 /// A translation entry loaded from a translation file.
 ///
-/// This enum represents the two types of translations:
+/// This enum represents the four types of translations:
 /// - Simple string translations
 /// - Plural translations with multiple forms
+/// - Ordinal plural translations with multiple forms
+/// - Select translations with multiple cases
 pub enum TranslationEntry {
     /// A simple, non-plural translation string.
     Entry(String),
@@ -51,6 +123,68 @@ pub enum TranslationEntry {
     /// The keys are ICU plural categories: "zero", "one", "two", "few", "many", "other".
     /// The "other" key is always required.
     PluralEntry(HashMap<String, String>),
+    /// An ordinal plural translation with forms keyed by ordinal plural category.
+    ///
+    /// The keys are the same as those of [`PluralEntry`](TranslationEntry::PluralEntry), but
+    /// are selected with the language's ordinal plural rules. The "other" key is always required.
+    OrdinalEntry(HashMap<String, String>),
+    /// A select translation with strings keyed by the value of the `select` variable.
+    ///
+    /// The "other" key is always required.
+    SelectEntry(HashMap<String, String>),
+}
+
+impl TranslationEntry {
+    /// Whether the entry contains no text, such as an entry that has not been
+    /// translated yet.
+    pub fn is_empty(&self) -> bool {
+        match self {
+            TranslationEntry::Entry(string) => string.trim().is_empty(),
+            TranslationEntry::PluralEntry(forms)
+            | TranslationEntry::OrdinalEntry(forms)
+            | TranslationEntry::SelectEntry(forms) => {
+                forms.values().all(|form| form.trim().is_empty())
+            }
+        }
+    }
+
+    /// Get a hash of the contents of the entry, as a hexadecimal string.
+    ///
+    /// The hash is stable across builds and platforms, so it can be stored in
+    /// `meta.json` and compared later.
+    pub fn content_hash(&self) -> String {
+        let parts = match self {
+            TranslationEntry::Entry(string) => vec!["entry", string.as_str()],
+            TranslationEntry::PluralEntry(forms)
+            | TranslationEntry::OrdinalEntry(forms)
+            | TranslationEntry::SelectEntry(forms) => {
+                let kind = match self {
+                    TranslationEntry::PluralEntry(_) => "plural",
+                    TranslationEntry::OrdinalEntry(_) => "ordinal",
+                    _ => "select",
+                };
+
+                let mut forms = forms.iter().collect::<Vec<_>>();
+                forms.sort();
+                once(kind)
+                    .chain(
+                        forms
+                            .into_iter()
+                            .flat_map(|(category, string)| [category.as_str(), string.as_str()]),
+                    )
+                    .collect()
+            }
+        };
+
+        // 64-bit FNV-1a, with each part terminated by a null byte
+        let hash = parts
+            .iter()
+            .flat_map(|part| part.bytes().chain(once(0)))
+            .fold(0xcbf29ce484222325u64, |hash, byte| {
+                (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+            });
+        format!("{hash:016x}")
+    }
 }
 
 /// Load translations from a JSON file.
@@ -65,7 +199,7 @@ pub enum TranslationEntry {
 /// # Returns
 ///
 /// A vector of `(key, entry)` tuples, where each entry is either a simple
-/// string or a plural entry with multiple forms.
+/// string, a plural or ordinal entry with multiple forms or a select entry with multiple cases.
 ///
 /// # Errors
 ///
@@ -73,7 +207,7 @@ pub enum TranslationEntry {
 /// - The file cannot be opened or read
 /// - The file is not valid JSON
 /// - The JSON structure is invalid (not an object, or contains invalid entries)
-/// - A plural entry is missing the required "other" form
+/// - A plural, ordinal or select entry is missing the required "other" form
 ///
 /// # Example
 ///
@@ -85,9 +219,12 @@ pub enum TranslationEntry {
 /// for (key, entry) in entries {
 ///     match entry {
 ///         TranslationEntry::Entry(text) => println!("{}: {}", key, text),
-///         TranslationEntry::PluralEntry(forms) => {
+///         TranslationEntry::PluralEntry(forms) | TranslationEntry::OrdinalEntry(forms) => {
 ///             println!("{}: {} forms", key, forms.len());
 ///         }
+///         TranslationEntry::SelectEntry(cases) => {
+///             println!("{}: {} cases", key, cases.len());
+///         }
 ///     }
 /// }
 /// ```
@@ -106,7 +243,24 @@ pub fn translation(path: &Path) -> io::Result<Vec<(String, TranslationEntry)>> {
 
         if let Some(string) = kv.1.as_str() {
             entries.push((name, TranslationEntry::Entry(string.to_string())));
+        } else if let Some(cases) = kv.1.get("select").and_then(Value::as_object) {
+            if !cases.contains_key("other") {
+                return Err(io::Error::new(
+                    ErrorKind::InvalidData,
+                    r#"Select entry has no "other" entry"#,
+                ));
+            }
+
+            let cases = cases
+                .into_iter()
+                .filter_map(|(case, v)| v.as_str().map(|string| (case.clone(), string.to_string())))
+                .collect();
+
+            entries.push((name, TranslationEntry::SelectEntry(cases)))
         } else if let Some(inner_object) = kv.1.as_object() {
+            let ordinal_object = inner_object.get("ordinal").and_then(Value::as_object);
+            let inner_object = ordinal_object.unwrap_or(inner_object);
+
             let other = inner_object.get("other");
             if other.is_none() {
                 return Err(io::Error::new(
@@ -128,7 +282,14 @@ pub fn translation(path: &Path) -> io::Result<Vec<(String, TranslationEntry)>> {
                 .filter_map(|(cat, v)| v.as_str().map(|string| (cat.clone(), string.to_string())))
                 .collect();
 
-            entries.push((name, TranslationEntry::PluralEntry(squeeze)))
+            entries.push((
+                name,
+                if ordinal_object.is_some() {
+                    TranslationEntry::OrdinalEntry(squeeze)
+                } else {
+                    TranslationEntry::PluralEntry(squeeze)
+                },
+            ))
         } else {
             return Err(io::Error::new(
                 ErrorKind::InvalidData,
@@ -139,3 +300,65 @@ pub fn translation(path: &Path) -> io::Result<Vec<(String, TranslationEntry)>> {
 
     Ok(entries)
 }
+
+/// Metadata about a translation string, as stored in `meta.json`.
+#[derive(Deserialize, Clone, Debug, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct TranslationMeta {
+    /// The name of the file the string is defined in.
+    pub context: Option<String>,
+    /// The location the string is defined at, in the form `path:line`.
+    pub defined_in: Option<String>,
+    /// Whether the string is a plural string.
+    pub plural: bool,
+    /// Whether the string is a plural string using ordinal plural categories.
+    pub ordinal: bool,
+    /// Whether the string is a select string.
+    pub select: bool,
+    /// The description provided to translators, if any.
+    pub description: Option<String>,
+    /// The [content hash](TranslationEntry::content_hash) of the string in the default
+    /// language's catalog.
+    pub source_hash: Option<String>,
+    /// The source string each language's translation was made for, keyed by language.
+    pub translated_from: HashMap<String, TranslatedFrom>,
+}
+
+impl TranslationMeta {
+    /// Whether the translation for `language` was made for an older version of the
+    /// source string, and needs to be reviewed.
+    pub fn is_stale(&self, language: &str) -> bool {
+        match (&self.source_hash, self.translated_from.get(language)) {
+            (Some(source_hash), Some(translated_from)) => {
+                translated_from.source_hash != *source_hash
+            }
+            _ => false,
+        }
+    }
+}
+
+/// The version of the source string a translation was made for, as stored in `meta.json`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TranslatedFrom {
+    /// The [content hash](TranslationEntry::content_hash) of the source string at the time
+    /// the translation last changed.
+    pub source_hash: String,
+    /// The [content hash](TranslationEntry::content_hash) of the translation.
+    pub translation_hash: String,
+}
+
+/// Load translation metadata from a `meta.json` file.
+///
+/// # Arguments
+///
+/// * `path` - Path to the `meta.json` file
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be opened or read, or if it is not a
+/// valid metadata file.
+pub fn meta(path: &Path) -> io::Result<HashMap<String, TranslationMeta>> {
+    let file = OpenOptions::new().read(true).open(path)?;
+    Ok(serde_json::from_reader(file)?)
+}
@@ -0,0 +1,35 @@
+//! Placeholder extraction utilities.
+//!
+//! Translation strings refer to variables using `{{name}}` syntax. This module
+//! extracts the names of those variables so that translations can be compared
+//! against their source strings.
+
+/// Extract the names of all placeholders in a translation string.
+///
+/// Placeholders are returned in the order in which they appear. A placeholder
+/// that is used more than once is returned more than once.
+///
+/// # Example
+///
+/// ```rust
+/// use cntp_i18n_build_core::placeholder::placeholders;
+///
+/// assert_eq!(
+///     placeholders("{{count}} items in {{folder}}"),
+///     vec!["count", "folder"]
+/// );
+/// ```
+pub fn placeholders(string: &str) -> Vec<&str> {
+    let mut placeholders = Vec::new();
+    let mut remaining = string;
+    while let Some(start) = remaining.find("{{") {
+        let after_start = &remaining[start + 2..];
+        let Some(end) = after_start.find("}}") else {
+            break;
+        };
+
+        placeholders.push(&after_start[..end]);
+        remaining = &after_start[end + 2..];
+    }
+    placeholders
+}
@@ -16,11 +16,13 @@
 //! ## Core Types
 //!
 //! - [`I18nSource`] - Trait for translation providers (implement this for custom sources)
-//! - [`I18nEntry`] - A translation entry (either singular or plural)
+//! - [`I18nEntry`] - A translation entry (singular, plural or select)
 //! - [`I18nStringEntry`] - A simple string translation
 //! - [`I18nPluralStringEntry`] - A pluralized translation with multiple forms
+//! - [`I18nSelectStringEntry`] - A translation with a case for each value of a variable
 //! - [`I18nStringPart`] - A component of a translation (static text, variable, or count)
 //! - [`I18nString`](string::I18nString) - An efficient string type (borrowed or owned)
+//! - [`Markup`](markup::Markup) - Text with rich text markup resolved into styled runs
 //!
 //! ## Feature flags
 //!
@@ -61,6 +63,10 @@
 ///
 /// This module is only available when the `gpui` feature is enabled.
 pub mod gpui;
+/// Rich text markup in translations.
+///
+/// See [`parse_markup`](markup::parse_markup) for resolving markup into styled runs.
+pub mod markup;
 /// String types for internationalized text.
 ///
 /// See [`I18nString`](string::I18nString) for the main type.
@@ -122,6 +128,33 @@ pub trait I18nSource: Send + Sync {
     /// When `None` is returned, the manager will try the next source in the chain.
     fn lookup(&'_ self, locale: &Locale, id: &str, lookup_crate: &str)
     -> Option<&'_ I18nEntry<'_>>;
+
+    /// List the languages this source has translations for.
+    ///
+    /// Languages are identified the same way as in [`Locale::messages`] (e.g., "de" or
+    /// "pt-BR"). Returns `None` if the source can't tell which languages it contains, in
+    /// which case it is not taken into account when listing the available languages.
+    fn available_languages(&self) -> Option<Vec<String>> {
+        None
+    }
+
+    /// The language of the entry that [`lookup`](Self::lookup) returns for the same
+    /// arguments.
+    ///
+    /// Sources that fall back to a language the locale doesn't request, such as a default
+    /// language, should implement this so that the fallback can be reported as a missing
+    /// translation. Returns `None` if the source doesn't know, or has no entry.
+    fn lookup_language(&self, _locale: &Locale, _id: &str, _lookup_crate: &str) -> Option<String> {
+        None
+    }
+
+    /// A human-readable description of this source, used when diagnosing where a
+    /// translation came from.
+    ///
+    /// Defaults to the name of the type implementing this trait.
+    fn name(&self) -> String {
+        std::any::type_name::<Self>().to_string()
+    }
 }
 
 /// A simple (non-plural) translation entry.
@@ -143,6 +176,13 @@ pub struct I18nStringEntry {
 /// This struct holds all the plural forms for a translation. The appropriate
 /// form is selected at runtime based on the count and the locale's plural rules.
 ///
+/// # Ordinal plurals
+///
+/// When `ordinal` is `true`, the form is selected with the locale's ordinal plural
+/// rules, used for strings like "1st", "2nd" and "3rd", instead of its cardinal
+/// plural rules. For example, English ordinals use the "one", "two", "few" and
+/// "other" forms.
+///
 /// # Required forms
 ///
 /// The `other` form is always required and serves as the fallback. Other forms
@@ -161,6 +201,8 @@ pub struct I18nStringEntry {
 pub struct I18nPluralStringEntry<'a> {
     /// The locale identifier for plural rule selection.
     pub locale: I18nString,
+    /// Whether the form is selected with ordinal plural rules instead of cardinal plural rules.
+    pub ordinal: bool,
     /// Translation for the "zero" plural category (e.g., Arabic).
     pub zero: Option<&'a [I18nStringPart]>,
     /// Translation for the "one" plural category (e.g., English singular).
@@ -179,7 +221,8 @@ impl I18nPluralStringEntry<'_> {
     /// Select the appropriate plural form based on the count and locale.
     ///
     /// This method uses ICU plural rules to determine which form to use
-    /// for the given count in the specified locale.
+    /// for the given count in the specified locale. Ordinal plural rules are
+    /// used if this is an ordinal plural entry.
     ///
     /// # Arguments
     ///
@@ -193,7 +236,11 @@ impl I18nPluralStringEntry<'_> {
     pub fn lookup(&self, count: isize, cntp_locale: &Locale) -> Vec<I18nStringPart> {
         let lookup_core = || -> anyhow::Result<Vec<I18nStringPart>> {
             let locale = icu::locale::Locale::try_from_str(&self.locale)?;
-            let pr = PluralRules::try_new(locale.into(), Default::default())?;
+            let pr = if self.ordinal {
+                PluralRules::try_new_ordinal(locale.into())?
+            } else {
+                PluralRules::try_new(locale.into(), Default::default())?
+            };
 
             Ok(match pr.category_for(count) {
                 PluralCategory::Zero => self
@@ -232,7 +279,57 @@ impl I18nPluralStringEntry<'_> {
     }
 }
 
-/// A translation entry, which can be either singular or plural.
+/// A select translation entry with multiple cases.
+///
+/// Select entries choose a string based on the value of the `select` variable, and
+/// are used for strings that vary with something other than a count, such as the
+/// grammatical gender of a person or noun.
+///
+/// # Required cases
+///
+/// The `other` case is always required and is used when no other case matches the
+/// value of the `select` variable.
+///
+/// # Example JSON
+///
+/// ```json
+/// {
+///     "PROFILE_UPDATED": {
+///         "select": {
+///             "masculine": "{{name}} updated his profile",
+///             "feminine": "{{name}} updated her profile",
+///             "other": "{{name}} updated their profile"
+///         }
+///     }
+/// }
+/// ```
+pub struct I18nSelectStringEntry<'a> {
+    /// The cases, other than the "other" case, keyed by the value of the `select` variable.
+    pub cases: &'a [(I18nString, &'a [I18nStringPart])],
+    /// Translation for the "other" case (always required, used as fallback).
+    pub other: &'a [I18nStringPart],
+}
+
+impl I18nSelectStringEntry<'_> {
+    /// Select the case matching the value of the `select` variable.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value of the `select` variable
+    ///
+    /// # Returns
+    ///
+    /// The parts of the matching case, or of the "other" case if no case matches.
+    pub fn lookup(&self, value: &str) -> &[I18nStringPart] {
+        self.cases
+            .iter()
+            .find(|(case, _)| case.as_ref() == value)
+            .map(|(_, parts)| *parts)
+            .unwrap_or(self.other)
+    }
+}
+
+/// A translation entry, which can be singular, plural or select.
 ///
 /// This enum represents a single translation unit loaded from a translation file.
 /// The macro system uses this to determine how to render the translation.
@@ -244,6 +341,8 @@ pub enum I18nEntry<'a> {
     Entry(&'a [I18nStringPart]),
     /// A plural translation with multiple forms based on count.
     PluralEntry(I18nPluralStringEntry<'a>),
+    /// A select translation with multiple cases based on the value of a variable.
+    SelectEntry(I18nSelectStringEntry<'a>),
 }
 
 /// A component of a translated string.
@@ -283,14 +382,16 @@ pub enum I18nStringPart {
 impl I18nEntry<'_> {
     /// Returns `true` if this is a simple (non-plural) entry.
     pub fn is_singular(&self) -> bool {
-        match self {
-            I18nEntry::Entry(_) => true,
-            I18nEntry::PluralEntry(_) => false,
-        }
+        matches!(self, I18nEntry::Entry(_))
     }
 
     /// Returns `true` if this is a plural entry.
     pub fn is_plural(&self) -> bool {
-        !self.is_singular()
+        matches!(self, I18nEntry::PluralEntry(_))
+    }
+
+    /// Returns `true` if this is a select entry.
+    pub fn is_select(&self) -> bool {
+        matches!(self, I18nEntry::SelectEntry(_))
     }
 }
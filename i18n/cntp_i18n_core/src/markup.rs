@@ -0,0 +1,234 @@
+//! Rich text markup in translations.
+//!
+//! Translations can contain a small subset of HTML-like markup, so that emphasis and links
+//! can be placed wherever the translator needs them:
+//!
+//! | Markup                         | Meaning                                      |
+//! |--------------------------------|----------------------------------------------|
+//! | `<b>text</b>`                  | Bold text                                    |
+//! | `<i>text</i>`                  | Italic text                                  |
+//! | `<a href="name">text</a>`      | A link, handled by the handler `name`        |
+//! | `&lt;` `&gt;` `&amp;` `&quot;` | The literal characters `<`, `>`, `&` and `"` |
+//!
+//! Anything that is not recognised as markup, such as a lone `<` or an unknown tag, is kept
+//! as text. Closing tags that do not match an open tag are ignored, and tags left open at the
+//! end of the string are closed automatically.
+//!
+//! [`parse_markup`] resolves markup into [`Markup`], a plain string and a list of styled
+//! [`MarkupRun`]s, while [`strip_markup`] removes markup, leaving only the text.
+
+use std::{borrow::Cow, ops::Range};
+
+/// Text with markup resolved into styled runs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Markup {
+    /// The text, with all markup removed.
+    pub text: String,
+    /// The styled runs of the text, in order. Every byte of [`text`](Markup::text) is covered
+    /// by exactly one run.
+    pub runs: Vec<MarkupRun>,
+}
+
+/// A run of text that has the same style.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MarkupRun {
+    /// The byte range of the run within [`Markup::text`].
+    pub range: Range<usize>,
+    /// Whether the run is bold.
+    pub bold: bool,
+    /// Whether the run is italic.
+    pub italic: bool,
+    /// The name of the handler for the link this run is part of, if any.
+    pub link: Option<String>,
+}
+
+enum Token<'a> {
+    Text(&'a str),
+    Bold(bool),
+    Italic(bool),
+    Link(Option<String>),
+}
+
+/// Split a string into text and markup tokens.
+fn tokens(markup: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut rest = markup;
+
+    while let Some(index) = rest.find(['<', '&']) {
+        let (text, candidate) = rest.split_at(index);
+        if !text.is_empty() {
+            tokens.push(Token::Text(text));
+        }
+
+        match parse_markup_token(candidate) {
+            Some((token, len)) => {
+                tokens.push(token);
+                rest = &candidate[len..];
+            }
+            None => {
+                tokens.push(Token::Text(&candidate[..1]));
+                rest = &candidate[1..];
+            }
+        }
+    }
+
+    if !rest.is_empty() {
+        tokens.push(Token::Text(rest));
+    }
+
+    tokens
+}
+
+/// Parse the tag or entity at the start of a string, returning the token and its length.
+fn parse_markup_token(candidate: &str) -> Option<(Token<'_>, usize)> {
+    let fixed = [
+        ("<b>", Token::Bold(true)),
+        ("</b>", Token::Bold(false)),
+        ("<i>", Token::Italic(true)),
+        ("</i>", Token::Italic(false)),
+        ("</a>", Token::Link(None)),
+        ("&lt;", Token::Text("<")),
+        ("&gt;", Token::Text(">")),
+        ("&amp;", Token::Text("&")),
+        ("&quot;", Token::Text("\"")),
+    ];
+
+    if let Some((tag, token)) = fixed
+        .into_iter()
+        .find(|(tag, _)| candidate.starts_with(tag))
+    {
+        return Some((token, tag.len()));
+    }
+
+    let href = candidate.strip_prefix("<a href=\"")?;
+    let end = href.find('"')?;
+    let name = &href[..end];
+    href[end + 1..].starts_with('>').then(|| {
+        (
+            Token::Link(Some(name.to_string())),
+            "<a href=\"".len() + end + 2,
+        )
+    })
+}
+
+/// Resolve markup into text and styled runs.
+///
+/// # Example
+///
+/// ```rust
+/// use cntp_i18n_core::markup::parse_markup;
+///
+/// let markup = parse_markup("Read the <a href=\"terms\"><b>terms</b></a> first");
+/// assert_eq!(markup.text, "Read the terms first");
+/// assert_eq!(markup.runs[1].range, 9..14);
+/// assert!(markup.runs[1].bold);
+/// assert_eq!(markup.runs[1].link.as_deref(), Some("terms"));
+/// ```
+pub fn parse_markup(markup: &str) -> Markup {
+    let mut result = Markup::default();
+    let mut bold = 0usize;
+    let mut italic = 0usize;
+    let mut link = None;
+
+    for token in tokens(markup) {
+        match token {
+            Token::Text(text) => {
+                let start = result.text.len();
+                result.text.push_str(text);
+                let end = result.text.len();
+
+                match result.runs.last_mut() {
+                    Some(run)
+                        if run.bold == (bold > 0)
+                            && run.italic == (italic > 0)
+                            && run.link == link =>
+                    {
+                        run.range.end = end;
+                    }
+                    _ => result.runs.push(MarkupRun {
+                        range: start..end,
+                        bold: bold > 0,
+                        italic: italic > 0,
+                        link: link.clone(),
+                    }),
+                }
+            }
+            Token::Bold(true) => bold += 1,
+            Token::Bold(false) => bold = bold.saturating_sub(1),
+            Token::Italic(true) => italic += 1,
+            Token::Italic(false) => italic = italic.saturating_sub(1),
+            Token::Link(name) => link = name,
+        }
+    }
+
+    result
+}
+
+/// Remove markup from a string, leaving only the text.
+///
+/// Strings without any markup are returned unchanged without allocating.
+pub fn strip_markup(markup: &str) -> Cow<'_, str> {
+    if !markup.contains(['<', '&']) {
+        return Cow::Borrowed(markup);
+    }
+
+    tokens(markup)
+        .into_iter()
+        .filter_map(|token| match token {
+            Token::Text(text) => Some(text),
+            _ => None,
+        })
+        .collect::<String>()
+        .into()
+}
+
+/// Escape a string so that it is displayed as-is when inserted into markup.
+pub fn escape_markup(text: &str) -> Cow<'_, str> {
+    if !text.contains(['<', '>', '&', '"']) {
+        return Cow::Borrowed(text);
+    }
+
+    text.chars()
+        .fold(String::with_capacity(text.len()), |mut escaped, c| {
+            match c {
+                '<' => escaped.push_str("&lt;"),
+                '>' => escaped.push_str("&gt;"),
+                '&' => escaped.push_str("&amp;"),
+                '"' => escaped.push_str("&quot;"),
+                c => escaped.push(c),
+            }
+            escaped
+        })
+        .into()
+}
+
+/// Transform the text of a markup string, leaving its tags intact.
+///
+/// Literal characters represented by entities are escaped again after the transformation.
+pub fn map_markup_text(markup: &str, mut transform: impl FnMut(&str) -> String) -> String {
+    let mut result = String::with_capacity(markup.len());
+    let mut rest = markup;
+
+    while let Some(index) = rest.find(['<', '&']) {
+        let (text, candidate) = rest.split_at(index);
+        result.push_str(&transform(text));
+
+        match parse_markup_token(candidate) {
+            Some((Token::Text(text), len)) => {
+                result.push_str(&escape_markup(&transform(text)));
+                rest = &candidate[len..];
+            }
+            Some((_, len)) => {
+                result.push_str(&candidate[..len]);
+                rest = &candidate[len..];
+            }
+            None => {
+                result.push_str(&escape_markup(&candidate[..1]));
+                rest = &candidate[1..];
+            }
+        }
+    }
+
+    result.push_str(&transform(rest));
+    result
+}
@@ -0,0 +1,154 @@
+//! A cache of the strings found in each source file, so that files that haven't
+//! changed since the last generation don't need to be parsed again.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    hash::{DefaultHasher, Hash, Hasher},
+    io,
+    path::{Path, PathBuf},
+};
+
+use cntp_i18n_build_core::config::MacroMapping;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::{ExpectedString, TrInfo};
+
+/// The strings found in a single source file.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CachedFile {
+    hash: u64,
+    pub strings: Vec<(String, TrInfo)>,
+    pub expected_strings: Vec<ExpectedString>,
+}
+
+impl CachedFile {
+    pub fn new(
+        contents: &str,
+        strings: Vec<(String, TrInfo)>,
+        expected_strings: Vec<ExpectedString>,
+    ) -> Self {
+        Self {
+            hash: content_hash(contents),
+            strings,
+            expected_strings,
+        }
+    }
+}
+
+/// The strings found in every source file of a crate during the last generation.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SourceCache {
+    /// The version of `cntp_i18n_gen` that wrote the cache.
+    version: String,
+    /// The default language the strings were extracted for, which determines the plural
+    /// categories of plural strings.
+    default_language: String,
+    /// The custom macros the strings were extracted from.
+    macros: BTreeMap<String, MacroMapping>,
+    files: HashMap<PathBuf, CachedFile>,
+}
+
+impl SourceCache {
+    pub fn new(default_language: &str, macros: &HashMap<String, MacroMapping>) -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            default_language: default_language.to_string(),
+            macros: macros
+                .iter()
+                .map(|(name, mapping)| (name.clone(), mapping.clone()))
+                .collect(),
+            files: HashMap::new(),
+        }
+    }
+
+    /// Load the cache at `path`, or create an empty cache if it doesn't exist or was written
+    /// for a different version or configuration.
+    pub fn load(
+        path: &Path,
+        default_language: &str,
+        macros: &HashMap<String, MacroMapping>,
+    ) -> Self {
+        let empty_cache = Self::new(default_language, macros);
+        let cache = fs::read(path)
+            .ok()
+            .and_then(|contents| serde_json::from_slice::<SourceCache>(&contents).ok())
+            .filter(|cache| {
+                cache.version == empty_cache.version
+                    && cache.default_language == empty_cache.default_language
+                    && cache.macros == empty_cache.macros
+            });
+
+        match cache {
+            Some(cache) => cache,
+            None => {
+                debug!("no usable source cache at {:?}", path);
+                empty_cache
+            }
+        }
+    }
+
+    /// Take the cached strings of `file`, if its contents haven't changed.
+    pub fn take(&mut self, file: &Path, contents: &str) -> Option<CachedFile> {
+        let hash = content_hash(contents);
+        self.files
+            .remove(file)
+            .filter(|cached_file| cached_file.hash == hash)
+    }
+
+    pub fn insert(&mut self, file: PathBuf, cached_file: CachedFile) {
+        self.files.insert(file, cached_file);
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_vec(self)?)
+    }
+}
+
+/// Serialization of plural forms, using the CLDR names of their plural categories.
+pub(crate) mod plural_forms {
+    use icu::plurals::PluralCategory;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer, de::Error};
+
+    use crate::catalog::category_name;
+
+    pub fn serialize<S: Serializer>(
+        forms: &[(PluralCategory, String)],
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        forms
+            .iter()
+            .map(|(category, string)| (category_name(*category), string))
+            .collect::<Vec<_>>()
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<(PluralCategory, String)>, D::Error> {
+        Vec::<(String, String)>::deserialize(deserializer)?
+            .into_iter()
+            .map(|(category, string)| {
+                PluralCategory::get_for_cldr_string(&category)
+                    .map(|category| (category, string))
+                    .ok_or_else(|| D::Error::custom(format!("invalid plural category {category}")))
+            })
+            .collect()
+    }
+}
+
+/// Hash the contents of a source file.
+///
+/// A hash that differs between Rust versions only causes the file to be parsed again,
+/// so the hash doesn't need to be stable.
+fn content_hash(contents: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    contents.hash(&mut hasher);
+    hasher.finish()
+}
@@ -0,0 +1,125 @@
+//! Helpers shared by the modules that read and write translation catalogs.
+
+use std::{collections::HashMap, fs, io, path::Path};
+
+use cntp_i18n_build_core::load::TranslationEntry;
+use icu::{
+    locale::Locale,
+    plurals::{PluralCategory, PluralRules},
+};
+use serde_json::{Map, Value};
+
+/// Every plural category, in the order used by catalogs and exchange formats.
+pub(crate) const PLURAL_CATEGORIES: [&str; 6] = ["zero", "one", "two", "few", "many", "other"];
+
+pub(crate) fn category_name(category: PluralCategory) -> &'static str {
+    match category {
+        PluralCategory::Zero => "zero",
+        PluralCategory::One => "one",
+        PluralCategory::Two => "two",
+        PluralCategory::Few => "few",
+        PluralCategory::Many => "many",
+        PluralCategory::Other => "other",
+    }
+}
+
+/// Get the plural categories a language uses, or `None` if the language is not a valid locale.
+pub(crate) fn plural_categories(language: &str) -> Option<Vec<&'static str>> {
+    let locale = Locale::try_from_str(language).ok()?;
+    let plural_rules = PluralRules::try_new(locale.into(), Default::default()).ok()?;
+    Some(plural_rules.categories().map(category_name).collect())
+}
+
+/// Get the ordinal plural categories a language uses, or `None` if the language is not a valid locale.
+pub(crate) fn ordinal_categories(language: &str) -> Option<Vec<&'static str>> {
+    let locale = Locale::try_from_str(language).ok()?;
+    let ordinal_rules = PluralRules::try_new_ordinal(locale.into()).ok()?;
+    Some(ordinal_rules.categories().map(category_name).collect())
+}
+
+/// Sort plural forms into the order used by [`PLURAL_CATEGORIES`].
+pub(crate) fn sorted_forms(forms: &HashMap<String, String>) -> Vec<(&str, &str)> {
+    PLURAL_CATEGORIES
+        .iter()
+        .filter_map(|category| forms.get(*category).map(|form| (*category, form.as_str())))
+        .collect()
+}
+
+/// Sort select cases by name, with the "other" case last.
+pub(crate) fn sorted_cases(cases: &HashMap<String, String>) -> Vec<(&str, &str)> {
+    let mut sorted = cases
+        .iter()
+        .map(|(case, string)| (case.as_str(), string.as_str()))
+        .collect::<Vec<_>>();
+    sorted.sort_by_key(|(case, _)| (*case == "other", *case));
+    sorted
+}
+
+fn forms_object(forms: &HashMap<String, String>) -> Value {
+    Value::Object(
+        sorted_forms(forms)
+            .into_iter()
+            .map(|(category, form)| (category.to_string(), Value::String(form.into())))
+            .collect(),
+    )
+}
+
+/// Check that a language translations are imported into is a valid locale.
+///
+/// The language names the catalog that is written, so anything that could escape the
+/// translation directory is rejected as well.
+pub(crate) fn validate_import_language(language: &str) -> io::Result<()> {
+    if language.contains(['/', '\\']) || plural_categories(language).is_none() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{language} is not a valid locale"),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Merge imported entries into an existing catalog.
+///
+/// Imported entries replace the existing entries with the same key, and every other
+/// existing entry is kept, so that entries skipped during an import keep their translation.
+pub(crate) fn merge_catalog(
+    existing: Vec<(String, TranslationEntry)>,
+    imported: Vec<(String, TranslationEntry)>,
+) -> Vec<(String, TranslationEntry)> {
+    let mut merged = existing.into_iter().collect::<HashMap<_, _>>();
+    merged.extend(imported);
+    merged.into_iter().collect()
+}
+
+/// Write a translation catalog, sorted by key, in the format read by `tr_load!`.
+pub(crate) fn write_catalog(path: &Path, entries: &[(String, TranslationEntry)]) -> io::Result<()> {
+    let mut sorted_entries = entries.iter().collect::<Vec<_>>();
+    sorted_entries.sort_by_key(|(key, _)| key);
+
+    let catalog = sorted_entries
+        .into_iter()
+        .map(|(key, entry)| {
+            let value = match entry {
+                TranslationEntry::Entry(string) => Value::String(string.clone()),
+                TranslationEntry::PluralEntry(forms) => forms_object(forms),
+                TranslationEntry::OrdinalEntry(forms) => Value::Object(Map::from_iter([(
+                    "ordinal".to_string(),
+                    forms_object(forms),
+                )])),
+                TranslationEntry::SelectEntry(cases) => Value::Object(Map::from_iter([(
+                    "select".to_string(),
+                    Value::Object(
+                        sorted_cases(cases)
+                            .into_iter()
+                            .map(|(case, string)| (case.to_string(), Value::String(string.into())))
+                            .collect(),
+                    ),
+                )])),
+            };
+            (key.clone(), value)
+        })
+        .collect::<Map<_, _>>();
+
+    fs::write(path, serde_json::to_string_pretty(&catalog)?)
+}
@@ -0,0 +1,478 @@
+//! Consistency checks for translation catalogs.
+//!
+//! The checks compare every catalog in the translation directory against
+//! `meta.json` and the default language's catalog, reporting:
+//!
+//! - strings that have not been translated
+//! - translations for strings that no longer exist in the source code
+//! - translations whose `{{placeholders}}` differ from the source string
+//! - plural translations that are missing categories the language requires
+//! - translations that are ordinal plurals when the source string is a cardinal plural, or vice versa
+//! - translations that are select strings when the source string is not, or vice versa
+//! - translations made for an older version of the source string, which need to be reviewed
+//!
+//! These checks are exposed on the command line as `cargo cntp-i18n check`.
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::Path,
+};
+
+use crate::catalog::{ordinal_categories, plural_categories};
+use cntp_i18n_build_core::{
+    config::get_i18n_config,
+    load::{self, TranslationEntry, TranslationMeta},
+    placeholder::placeholders,
+};
+use itertools::Itertools;
+use serde::Serialize;
+
+/// How serious a [`CheckIssue`] is.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckSeverity {
+    /// The catalog is incomplete or out of date, but will still work.
+    Warning,
+    /// The catalog contains translations that will display incorrectly.
+    Error,
+}
+
+/// The kind of problem found by a check.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CheckIssueKind {
+    /// The catalog could not be loaded.
+    InvalidCatalog {
+        /// The reason the catalog could not be loaded.
+        reason: String,
+    },
+    /// The catalog's file name is not a valid locale.
+    InvalidLanguage,
+    /// A string defined in the source code has no translation.
+    MissingTranslation,
+    /// A translation exists for a string that is no longer defined in the source code.
+    UnknownKey,
+    /// The source string has changed since the string was translated.
+    NeedsReview,
+    /// A string is plural in the source code but not in the translation, or vice versa.
+    PluralMismatch {
+        /// Whether the source string is plural.
+        expected_plural: bool,
+    },
+    /// A plural string is ordinal in the source code but not in the translation, or vice versa.
+    OrdinalMismatch {
+        /// Whether the source string is an ordinal plural.
+        expected_ordinal: bool,
+    },
+    /// A string is a select string in the source code but not in the translation, or vice versa.
+    SelectMismatch {
+        /// Whether the source string is a select string.
+        expected_select: bool,
+    },
+    /// A plural translation is missing categories required by the language.
+    MissingPluralCategories {
+        /// The categories that are missing.
+        categories: Vec<String>,
+    },
+    /// A translation uses different placeholders to the source string.
+    PlaceholderMismatch {
+        /// The plural category or select case of the translation, for plural and select strings.
+        category: Option<String>,
+        /// Placeholders in the source string that the translation does not use.
+        missing: Vec<String>,
+        /// Placeholders in the translation that the source string does not define.
+        unexpected: Vec<String>,
+    },
+}
+
+impl CheckIssueKind {
+    /// Get the severity of this kind of issue.
+    pub fn severity(&self) -> CheckSeverity {
+        match self {
+            CheckIssueKind::MissingTranslation
+            | CheckIssueKind::UnknownKey
+            | CheckIssueKind::NeedsReview => CheckSeverity::Warning,
+            _ => CheckSeverity::Error,
+        }
+    }
+
+    /// Get a human readable description of this issue.
+    pub fn description(&self) -> String {
+        match self {
+            CheckIssueKind::InvalidCatalog { reason } => {
+                format!("catalog could not be loaded: {reason}")
+            }
+            CheckIssueKind::InvalidLanguage => "catalog name is not a valid locale".into(),
+            CheckIssueKind::MissingTranslation => "translation is missing".into(),
+            CheckIssueKind::UnknownKey => "key is no longer defined in meta.json".into(),
+            CheckIssueKind::NeedsReview => {
+                "needs review, the source string has changed since it was translated".into()
+            }
+            CheckIssueKind::PluralMismatch { expected_plural } => {
+                if *expected_plural {
+                    "source string is plural but the translation is not".into()
+                } else {
+                    "translation is plural but the source string is not".into()
+                }
+            }
+            CheckIssueKind::OrdinalMismatch { expected_ordinal } => {
+                if *expected_ordinal {
+                    "source string is an ordinal plural but the translation is not".into()
+                } else {
+                    "translation is an ordinal plural but the source string is not".into()
+                }
+            }
+            CheckIssueKind::SelectMismatch { expected_select } => {
+                if *expected_select {
+                    "source string is a select string but the translation is not".into()
+                } else {
+                    "translation is a select string but the source string is not".into()
+                }
+            }
+            CheckIssueKind::MissingPluralCategories { categories } => {
+                format!(
+                    "plural translation is missing categories: {}",
+                    categories.join(", ")
+                )
+            }
+            CheckIssueKind::PlaceholderMismatch {
+                category,
+                missing,
+                unexpected,
+            } => {
+                let mut problems = Vec::new();
+                if !missing.is_empty() {
+                    problems.push(format!(
+                        "missing {}",
+                        missing
+                            .iter()
+                            .map(|name| format!("{{{{{name}}}}}"))
+                            .join(", ")
+                    ));
+                }
+                if !unexpected.is_empty() {
+                    problems.push(format!(
+                        "unexpected {}",
+                        unexpected
+                            .iter()
+                            .map(|name| format!("{{{{{name}}}}}"))
+                            .join(", ")
+                    ));
+                }
+
+                match category {
+                    Some(category) => format!(
+                        "placeholders differ from the source string in the \"{category}\" form: {}",
+                        problems.join("; ")
+                    ),
+                    None => format!(
+                        "placeholders differ from the source string: {}",
+                        problems.join("; ")
+                    ),
+                }
+            }
+        }
+    }
+}
+
+/// A problem found in a translation catalog.
+#[derive(Serialize, Debug, Clone)]
+pub struct CheckIssue {
+    /// The language of the catalog the issue was found in.
+    pub language: String,
+    /// The translation key the issue relates to, if it relates to a single key.
+    pub key: Option<String>,
+    /// How serious the issue is.
+    pub severity: CheckSeverity,
+    /// What the issue is.
+    #[serde(flatten)]
+    pub kind: CheckIssueKind,
+}
+
+/// The result of checking every catalog in a project.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct CheckReport {
+    /// Every issue that was found.
+    pub issues: Vec<CheckIssue>,
+}
+
+impl CheckReport {
+    fn push(&mut self, language: &str, key: Option<&str>, kind: CheckIssueKind) {
+        self.issues.push(CheckIssue {
+            language: language.to_string(),
+            key: key.map(str::to_string),
+            severity: kind.severity(),
+            kind,
+        });
+    }
+
+    /// Get the number of issues with the given severity.
+    pub fn count(&self, severity: CheckSeverity) -> usize {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity == severity)
+            .count()
+    }
+}
+
+/// Check every translation catalog in a project.
+///
+/// # Arguments
+///
+/// * `manifest_directory` - Path to the directory containing `Cargo.toml`
+pub fn check(manifest_directory: &Path) -> CheckReport {
+    let config = get_i18n_config(manifest_directory);
+    let default_language = &config.i18n.default_language;
+    let mut report = CheckReport::default();
+
+    let meta = match load::meta(&config.i18n.translation_meta_file(manifest_directory)) {
+        Ok(meta) => meta,
+        Err(e) => {
+            report.push(
+                "meta",
+                None,
+                CheckIssueKind::InvalidCatalog {
+                    reason: e.to_string(),
+                },
+            );
+            return report;
+        }
+    };
+
+    let source = match load::translation(&config.i18n.translation_catalog_file(manifest_directory))
+    {
+        Ok(source) => source.into_iter().collect::<HashMap<_, _>>(),
+        Err(e) => {
+            report.push(
+                default_language,
+                None,
+                CheckIssueKind::InvalidCatalog {
+                    reason: e.to_string(),
+                },
+            );
+            return report;
+        }
+    };
+
+    for catalog_file in config
+        .i18n
+        .catalog_files(manifest_directory)
+        .into_iter()
+        .sorted()
+    {
+        let language = catalog_file
+            .file_stem()
+            .unwrap()
+            .to_string_lossy()
+            .to_string();
+
+        match load::translation(&catalog_file) {
+            Ok(catalog) => {
+                check_catalog(&mut report, &language, &catalog, &meta, &source);
+            }
+            Err(e) => report.push(
+                &language,
+                None,
+                CheckIssueKind::InvalidCatalog {
+                    reason: e.to_string(),
+                },
+            ),
+        }
+    }
+
+    report
+}
+
+/// Check a single catalog against the metadata and source catalog.
+pub(crate) fn check_catalog(
+    report: &mut CheckReport,
+    language: &str,
+    catalog: &[(String, TranslationEntry)],
+    meta: &HashMap<String, TranslationMeta>,
+    source: &HashMap<String, TranslationEntry>,
+) {
+    let plural_categories = plural_categories(language);
+    let ordinal_categories = ordinal_categories(language);
+    if plural_categories.is_none() {
+        report.push(language, None, CheckIssueKind::InvalidLanguage);
+    }
+
+    let translations = catalog
+        .iter()
+        .map(|(key, entry)| (key.as_str(), entry))
+        .collect::<HashMap<_, _>>();
+
+    for key in meta.keys().sorted() {
+        if !translations.contains_key(key.as_str()) {
+            report.push(language, Some(key), CheckIssueKind::MissingTranslation);
+        }
+    }
+
+    for (key, entry) in catalog.iter().sorted_by_key(|(key, _)| key) {
+        let Some(key_meta) = meta.get(key) else {
+            report.push(language, Some(key), CheckIssueKind::UnknownKey);
+            continue;
+        };
+
+        if key_meta.is_stale(language) {
+            report.push(language, Some(key), CheckIssueKind::NeedsReview);
+        }
+
+        match entry {
+            TranslationEntry::Entry(string) => {
+                if key_meta.plural {
+                    report.push(
+                        language,
+                        Some(key),
+                        CheckIssueKind::PluralMismatch {
+                            expected_plural: true,
+                        },
+                    );
+                    continue;
+                }
+
+                if key_meta.select {
+                    report.push(
+                        language,
+                        Some(key),
+                        CheckIssueKind::SelectMismatch {
+                            expected_select: true,
+                        },
+                    );
+                    continue;
+                }
+
+                if let Some(kind) = compare_placeholders(source.get(key), string, None) {
+                    report.push(language, Some(key), kind);
+                }
+            }
+            TranslationEntry::PluralEntry(forms) | TranslationEntry::OrdinalEntry(forms) => {
+                if !key_meta.plural {
+                    report.push(
+                        language,
+                        Some(key),
+                        CheckIssueKind::PluralMismatch {
+                            expected_plural: false,
+                        },
+                    );
+                    continue;
+                }
+
+                let ordinal = matches!(entry, TranslationEntry::OrdinalEntry(_));
+                if key_meta.ordinal != ordinal {
+                    report.push(
+                        language,
+                        Some(key),
+                        CheckIssueKind::OrdinalMismatch {
+                            expected_ordinal: key_meta.ordinal,
+                        },
+                    );
+                    continue;
+                }
+
+                let plural_categories = if ordinal {
+                    &ordinal_categories
+                } else {
+                    &plural_categories
+                };
+                if let Some(plural_categories) = plural_categories {
+                    let missing_categories = plural_categories
+                        .iter()
+                        .filter(|category| !forms.contains_key(**category))
+                        .map(|category| category.to_string())
+                        .collect::<Vec<_>>();
+                    if !missing_categories.is_empty() {
+                        report.push(
+                            language,
+                            Some(key),
+                            CheckIssueKind::MissingPluralCategories {
+                                categories: missing_categories,
+                            },
+                        );
+                    }
+                }
+
+                for (category, string) in forms.iter().sorted_by_key(|(category, _)| *category) {
+                    if let Some(kind) =
+                        compare_placeholders(source.get(key), string, Some(category))
+                    {
+                        report.push(language, Some(key), kind);
+                    }
+                }
+            }
+            TranslationEntry::SelectEntry(cases) => {
+                if !key_meta.select {
+                    report.push(
+                        language,
+                        Some(key),
+                        CheckIssueKind::SelectMismatch {
+                            expected_select: false,
+                        },
+                    );
+                    continue;
+                }
+
+                for (case, string) in cases.iter().sorted_by_key(|(case, _)| *case) {
+                    if let Some(kind) = compare_placeholders(source.get(key), string, Some(case)) {
+                        report.push(language, Some(key), kind);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Compare the placeholders of a translation with those of its source string.
+///
+/// Plural forms commonly leave out `{{count}}` (for example, "One item"), so
+/// `{{count}}` is never required to be present in a plural form.
+///
+/// The cases of a select string are compared with the source case of the same
+/// name, or the "other" case if the source string doesn't have that case.
+pub(crate) fn compare_placeholders(
+    source: Option<&TranslationEntry>,
+    translation: &str,
+    category: Option<&str>,
+) -> Option<CheckIssueKind> {
+    let source_placeholders: BTreeSet<&str> = match source? {
+        TranslationEntry::Entry(string) => placeholders(string).into_iter().collect(),
+        TranslationEntry::PluralEntry(forms) | TranslationEntry::OrdinalEntry(forms) => forms
+            .values()
+            .flat_map(|string| placeholders(string))
+            .collect(),
+        TranslationEntry::SelectEntry(cases) => match category {
+            Some(case) => cases
+                .get(case)
+                .or_else(|| cases.get("other"))
+                .map(|string| placeholders(string))
+                .unwrap_or_default()
+                .into_iter()
+                .collect(),
+            None => cases
+                .values()
+                .flat_map(|string| placeholders(string))
+                .collect(),
+        },
+    };
+    let translation_placeholders: BTreeSet<&str> = placeholders(translation).into_iter().collect();
+
+    let missing = source_placeholders
+        .difference(&translation_placeholders)
+        .filter(|placeholder| category.is_none() || **placeholder != "count")
+        .map(|placeholder| placeholder.to_string())
+        .collect::<Vec<_>>();
+    let unexpected = translation_placeholders
+        .difference(&source_placeholders)
+        .map(|placeholder| placeholder.to_string())
+        .collect::<Vec<_>>();
+
+    if missing.is_empty() && unexpected.is_empty() {
+        None
+    } else {
+        Some(CheckIssueKind::PlaceholderMismatch {
+            category: category.map(str::to_string),
+            missing,
+            unexpected,
+        })
+    }
+}
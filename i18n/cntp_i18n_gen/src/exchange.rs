@@ -0,0 +1,40 @@
+//! Types shared by the formats used to exchange translations with translators.
+
+use serde::Serialize;
+
+/// A translation that was rejected during an import.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct RejectedTranslation {
+    /// The translation key, or the source string if the key could not be determined.
+    pub key: String,
+    /// Why the translation was rejected.
+    pub reason: String,
+}
+
+/// The result of importing translations into a catalog.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ImportReport {
+    /// The language the translations were imported into.
+    pub language: String,
+    /// The number of translations written to the catalog.
+    pub imported: usize,
+    /// The translations that were not written to the catalog.
+    pub rejected: Vec<RejectedTranslation>,
+}
+
+impl ImportReport {
+    pub(crate) fn new(language: &str) -> Self {
+        Self {
+            language: language.to_string(),
+            imported: 0,
+            rejected: Vec::new(),
+        }
+    }
+
+    pub(crate) fn reject(&mut self, key: &str, reason: impl Into<String>) {
+        self.rejected.push(RejectedTranslation {
+            key: key.to_string(),
+            reason: reason.into(),
+        });
+    }
+}
@@ -1,13 +1,13 @@
 //! # `cntp-i18n-gen`
 //!
 //! This crate provides build-time code generation for the `cntp_i18n` system.
-//! It scans your Rust source files for `tr!` and `trn!` macro invocations and generates
+//! It scans your Rust source files for `tr!`, `trn!` and `trs!` macro invocations and generates
 //! the corresponding translation catalog files.
 //!
 //! ## Overview
 //!
 //! The generator walks through all `.rs` files in your `src` directory, extracts
-//! translation strings from `tr!` and `trn!` macro calls, and outputs:
+//! translation strings from `tr!`, `trn!` and `trs!` macro calls, and outputs:
 //!
 //! - `<default_language>.json` - The translation catalog for your default language
 //! - `meta.json` - Metadata about each translation string (file location, description, etc.)
@@ -43,6 +43,58 @@
 //!
 //! ```bash
 //! cargo cntp-i18n generate
+//! cargo cntp-i18n generate --workspace
+//! ```
+//!
+//! With `--workspace`, the catalogs of every workspace member that depends on `cntp_i18n`
+//! are generated, each using the member's own `i18n.toml`.
+//!
+//! ## Checking translations
+//!
+//! The [`check`] module verifies that translated catalogs are consistent with the
+//! source strings, and is exposed on the command line as:
+//!
+//! ```bash
+//! cargo cntp-i18n check
+//! ```
+//!
+//! Translations made for an older version of their source string are reported as
+//! needing review. The [`review`] module marks them as reviewed once a translator
+//! has checked them:
+//!
+//! ```bash
+//! cargo cntp-i18n review --language de
+//! ```
+//!
+//! The [`stats`] module calculates how much of each language has been translated:
+//!
+//! ```bash
+//! cargo cntp-i18n stats
+//! ```
+//!
+//! The [`lint`] module finds user-visible text that hasn't been wrapped in `tr!`:
+//!
+//! ```bash
+//! cargo cntp-i18n lint
+//! ```
+//!
+//! ## Working with translators
+//!
+//! Catalogs can be exported for translators and their work imported again. The
+//! [`po`] module supports Gettext PO and POT files:
+//!
+//! ```bash
+//! cargo cntp-i18n export --format po > template.pot
+//! cargo cntp-i18n export --format po --language de > de.po
+//! cargo cntp-i18n import de.po
+//! ```
+//!
+//! The [`xliff`] module supports XLIFF 2.0 documents, as used by many
+//! localisation vendors:
+//!
+//! ```bash
+//! cargo cntp-i18n export --format xliff --language de > de.xlf
+//! cargo cntp-i18n import de.xlf
 //! ```
 //!
 //! ## Configuration
@@ -67,12 +119,22 @@
 //! }
 //! ```
 
+mod cache;
+mod catalog;
+pub mod check;
+pub mod exchange;
+pub mod lint;
+pub mod po;
+pub mod review;
+pub mod stats;
 #[cfg(test)]
 mod tests;
+pub mod xliff;
 
 use std::{
     cell::RefCell,
     collections::HashMap,
+    env,
     ffi::OsStr,
     fs::{self, OpenOptions},
     io::Write,
@@ -81,18 +143,23 @@ use std::{
     rc::Rc,
 };
 
-use cntp_i18n_build_core::config::get_i18n_config;
-use cntp_i18n_parse::{tr::TrMacroInput, trn::TrnMacroInput};
+use cache::{CachedFile, SourceCache};
+use catalog::category_name;
+use cntp_i18n_build_core::{
+    config::{MacroMapping, get_i18n_config},
+    load,
+};
+use cntp_i18n_parse::{tr::TrMacroInput, trn::TrnMacroInput, trs::TrsMacroInput};
 use icu::{
     locale::Locale,
     plurals::{PluralCategory, PluralRules},
 };
 use itertools::Itertools;
-use proc_macro2::Span;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
-use syn::{Expr, Lit, Macro, Token, parse_file, spanned::Spanned, visit::Visit};
+use syn::{Expr, ExprLit, Lit, Macro, Token, parse_file, spanned::Spanned, visit::Visit};
 use syn::{parse::Parse, punctuated::Punctuated};
-use tracing::{debug, error, info, trace};
+use tracing::{debug, error, info, trace, warn};
 use walkdir::WalkDir;
 
 struct CommaSeperatedExpr {
@@ -109,50 +176,178 @@ impl Parse for CommaSeperatedExpr {
     }
 }
 
+#[derive(Serialize, Deserialize, Clone)]
 enum TrString {
     Single(String),
-    Plural(Vec<(PluralCategory, String)>),
+    Plural(#[serde(with = "cache::plural_forms")] Vec<(PluralCategory, String)>),
+    Select(Vec<(String, String)>),
 }
 
+#[derive(Serialize, Deserialize, Clone)]
 struct TrInfo {
     string: TrString,
     file: PathBuf,
     plural: bool,
+    ordinal: bool,
+    select: bool,
     description: Option<String>,
     line_no: usize,
 }
 
+#[derive(Serialize, Deserialize, Clone)]
 struct ExpectedString {
     id: String,
     file: PathBuf,
-    span: Span,
+    line_no: usize,
 }
 
 struct TrMacroVisitor {
     pub strings: HashMap<String, TrInfo>,
     pub expected_strings: Vec<ExpectedString>,
     pub plural_rules: PluralRules,
+    pub ordinal_rules: PluralRules,
     pub current_path: Rc<RefCell<PathBuf>>,
     pub errors: Vec<VisitorError>,
+    /// Macros that wrap `tr!` or `trn!`, configured in `i18n.toml`.
+    pub custom_macros: HashMap<String, MacroMapping>,
 }
 
 impl TrMacroVisitor {
-    pub fn new(plural_rules: PluralRules, path: Rc<RefCell<PathBuf>>) -> Self {
+    pub fn new(
+        plural_rules: PluralRules,
+        ordinal_rules: PluralRules,
+        path: Rc<RefCell<PathBuf>>,
+    ) -> Self {
         Self {
             strings: HashMap::new(),
             expected_strings: Default::default(),
             plural_rules,
+            ordinal_rules,
             current_path: path,
             errors: Default::default(),
+            custom_macros: Default::default(),
         }
     }
 
+    /// Record the definition of a string, reporting an error if it has already been defined.
+    fn define(&mut self, id: String, info: TrInfo) {
+        let line_no = info.line_no;
+        let file = info.file.clone();
+        if let Some(replaced) = self.strings.insert(id.clone(), info) {
+            self.errors.push(VisitorError {
+                line_no,
+                file,
+                error_type: VisitorErrorType::DuplicateDefinition {
+                    id,
+                    last_seen_file: replaced.file,
+                    last_seen_line: replaced.line_no,
+                },
+            });
+        }
+    }
+
+    /// Extract the string from a macro configured in `i18n.toml` to wrap `tr!` or `trn!`.
+    fn visit_custom_macro(&mut self, mapping: &MacroMapping, arguments: &[Expr], line_no: usize) {
+        let string_argument = |position: usize| match arguments.get(position) {
+            Some(Expr::Lit(ExprLit {
+                lit: Lit::Str(string),
+                ..
+            })) => Some(string.value()),
+            _ => None,
+        };
+
+        let Some(id) = string_argument(mapping.key) else {
+            trace!("custom macro without a literal key, skipping");
+            return;
+        };
+        let file = self.current_path.borrow().clone();
+
+        let (string, plural) = if mapping.plural.is_empty() {
+            match mapping.default.and_then(string_argument) {
+                Some(default_string) => (TrString::Single(default_string), false),
+                None => {
+                    self.expected_strings
+                        .push(ExpectedString { id, file, line_no });
+                    return;
+                }
+            }
+        } else {
+            let forms = mapping
+                .plural
+                .iter()
+                .map_while(|position| string_argument(*position))
+                .collect::<Vec<_>>();
+
+            let category_count = self.plural_rules.categories().count();
+            if forms.is_empty() {
+                self.expected_strings
+                    .push(ExpectedString { id, file, line_no });
+                return;
+            } else if forms.len() != category_count {
+                self.errors.push(VisitorError {
+                    line_no,
+                    file,
+                    error_type: VisitorErrorType::BadPluralArgumentCount {
+                        id,
+                        expected_count: category_count,
+                        actual_count: forms.len(),
+                    },
+                });
+                return;
+            }
+
+            (
+                TrString::Plural(self.plural_rules.categories().zip(forms).collect()),
+                true,
+            )
+        };
+
+        self.define(
+            id,
+            TrInfo {
+                string,
+                file,
+                plural,
+                ordinal: false,
+                select: false,
+                description: None,
+                line_no,
+            },
+        );
+    }
+
+    /// Get the strings defined and referenced in `file`, to be cached.
+    fn file_strings(&self, file: &Path) -> (Vec<(String, TrInfo)>, Vec<ExpectedString>) {
+        let strings = self
+            .strings
+            .iter()
+            .filter(|(_, info)| info.file == file)
+            .map(|(id, info)| (id.clone(), info.clone()))
+            .collect();
+        let expected_strings = self
+            .expected_strings
+            .iter()
+            .filter(|expected| expected.file == file)
+            .cloned()
+            .collect();
+        (strings, expected_strings)
+    }
+
+    /// Add the strings of a file that was visited in a previous generation.
+    fn replay(&mut self, cached_file: &CachedFile) {
+        for (id, info) in &cached_file.strings {
+            self.define(id.clone(), info.clone());
+        }
+        self.expected_strings
+            .extend(cached_file.expected_strings.iter().cloned());
+    }
+
     pub fn finish(&mut self) {
         // Check that all expected strings have been found
         for expected in self.expected_strings.iter() {
             if !self.strings.contains_key(expected.id.as_str()) {
                 self.errors.push(VisitorError {
-                    span: expected.span,
+                    line_no: expected.line_no,
                     file: expected.file.clone(),
                     error_type: VisitorErrorType::MissingDefinition {
                         id: expected.id.clone(),
@@ -197,6 +392,9 @@ impl VisitorError {
                         .unwrap(),
                 )
             }
+            VisitorErrorType::MissingSelectOther { id } => {
+                format!("select string {id} does not have an \"other\" case")
+            }
             VisitorErrorType::MissingDefinition { id } => format!(
                 "Missing definition for {id}. Referenced at {}:{}",
                 self.file
@@ -204,7 +402,7 @@ impl VisitorError {
                     .unwrap()
                     .to_str()
                     .unwrap(),
-                self.span.start().line,
+                self.line_no,
             ),
         }
     }
@@ -225,11 +423,14 @@ pub enum VisitorErrorType {
     MissingDefinition {
         id: String,
     },
+    MissingSelectOther {
+        id: String,
+    },
 }
 
 #[derive(Debug, Clone)]
 pub struct VisitorError {
-    pub span: Span,
+    pub line_no: usize,
     pub file: PathBuf,
     pub error_type: VisitorErrorType,
 }
@@ -240,43 +441,31 @@ impl<'ast> Visit<'ast> for TrMacroVisitor {
             "tr" | "tr_noop" => {
                 if let Ok(contents) = syn::parse2::<TrMacroInput>(mac.tokens.clone()) {
                     if let Some(default_string) = contents.default_string {
-                        let replaced = self.strings.insert(
-                            contents.translation_id.value(),
-                            TrInfo {
-                                string: TrString::Single(default_string.value()),
-                                file: self.current_path.borrow().clone(),
-                                plural: false,
-                                description: contents
-                                    .context
-                                    .iter()
-                                    .find(|v| v.name == "description")
-                                    .and_then(|v| match &v.value {
-                                        Expr::Lit(lit) => match &lit.lit {
-                                            Lit::Str(str) => Some(str.value()),
-                                            _ => None,
-                                        },
+                        let info = TrInfo {
+                            string: TrString::Single(default_string.value()),
+                            file: self.current_path.borrow().clone(),
+                            plural: false,
+                            ordinal: false,
+                            select: false,
+                            description: contents
+                                .context
+                                .iter()
+                                .find(|v| v.name == "description")
+                                .and_then(|v| match &v.value {
+                                    Expr::Lit(lit) => match &lit.lit {
+                                        Lit::Str(str) => Some(str.value()),
                                         _ => None,
-                                    }),
-                                line_no: mac.tokens.span().start().line,
-                            },
-                        );
-
-                        if let Some(replaced) = replaced {
-                            self.errors.push(VisitorError {
-                                span: mac.tokens.span(),
-                                file: self.current_path.borrow().clone(),
-                                error_type: VisitorErrorType::DuplicateDefinition {
-                                    id: contents.translation_id.value(),
-                                    last_seen_file: replaced.file,
-                                    last_seen_line: replaced.line_no,
-                                },
-                            });
-                        }
+                                    },
+                                    _ => None,
+                                }),
+                            line_no: mac.tokens.span().start().line,
+                        };
+                        self.define(contents.translation_id.value(), info);
                     } else {
                         self.expected_strings.push(ExpectedString {
                             id: contents.translation_id.value(),
                             file: self.current_path.borrow().clone(),
-                            span: mac.tokens.span(),
+                            line_no: mac.tokens.span().start().line,
                         })
                     }
 
@@ -287,7 +476,21 @@ impl<'ast> Visit<'ast> for TrMacroVisitor {
             }
             "trn" | "trn_noop" => {
                 if let Ok(contents) = syn::parse2::<TrnMacroInput>(mac.tokens.clone()) {
-                    let category_count = self.plural_rules.categories().count();
+                    let ordinal = contents
+                        .context
+                        .iter()
+                        .find(|v| v.name == "ordinal")
+                        .is_some_and(|v| match &v.value {
+                            Expr::Lit(lit) => matches!(&lit.lit, Lit::Bool(bool) if bool.value),
+                            _ => false,
+                        });
+                    let plural_rules = if ordinal {
+                        &self.ordinal_rules
+                    } else {
+                        &self.plural_rules
+                    };
+
+                    let category_count = plural_rules.categories().count();
                     let string_count = contents.default_strings.len();
                     let id = contents.translation_id.value();
 
@@ -296,11 +499,11 @@ impl<'ast> Visit<'ast> for TrMacroVisitor {
                         self.expected_strings.push(ExpectedString {
                             id,
                             file: self.current_path.borrow().clone(),
-                            span: mac.tokens.span(),
+                            line_no: mac.tokens.span().start().line,
                         })
                     } else if category_count != string_count {
                         self.errors.push(VisitorError {
-                            span: mac.tokens.span(),
+                            line_no: mac.tokens.span().start().line,
                             file: self.current_path.borrow().clone(),
                             error_type: VisitorErrorType::BadPluralArgumentCount {
                                 id: id.clone(),
@@ -309,45 +512,83 @@ impl<'ast> Visit<'ast> for TrMacroVisitor {
                             },
                         });
                     } else {
-                        let forms = self
-                            .plural_rules
+                        let forms = plural_rules
                             .categories()
                             .zip(contents.default_strings.iter())
                             .map(|(category, lit_str)| (category, lit_str.value()))
                             .collect();
 
-                        let replaced = self.strings.insert(
+                        let info = TrInfo {
+                            string: TrString::Plural(forms),
+                            file: self.current_path.borrow().clone(),
+                            plural: true,
+                            ordinal,
+                            select: false,
+                            description: contents
+                                .context
+                                .iter()
+                                .find(|v| v.name == "description")
+                                .and_then(|v| match &v.value {
+                                    Expr::Lit(lit) => match &lit.lit {
+                                        Lit::Str(str) => Some(str.value()),
+                                        _ => None,
+                                    },
+                                    _ => None,
+                                }),
+                            line_no: mac.tokens.span().start().line,
+                        };
+                        self.define(id, info);
+                    }
+
+                    for variable in contents.variables {
+                        self.visit_expr(&variable.value);
+                    }
+                }
+            }
+            "trs" | "trs_noop" => {
+                if let Ok(contents) = syn::parse2::<TrsMacroInput>(mac.tokens.clone()) {
+                    let id = contents.translation_id.value();
+
+                    // If we're reusing an existing translation, don't process this instance
+                    if contents.cases.is_empty() {
+                        self.expected_strings.push(ExpectedString {
                             id,
-                            TrInfo {
-                                string: TrString::Plural(forms),
-                                file: self.current_path.borrow().clone(),
-                                plural: true,
-                                description: contents
-                                    .context
-                                    .iter()
-                                    .find(|v| v.name == "description")
-                                    .and_then(|v| match &v.value {
-                                        Expr::Lit(lit) => match &lit.lit {
-                                            Lit::Str(str) => Some(str.value()),
-                                            _ => None,
-                                        },
+                            file: self.current_path.borrow().clone(),
+                            line_no: mac.tokens.span().start().line,
+                        })
+                    } else if !contents.cases.iter().any(|case| case.name == "other") {
+                        self.errors.push(VisitorError {
+                            line_no: mac.tokens.span().start().line,
+                            file: self.current_path.borrow().clone(),
+                            error_type: VisitorErrorType::MissingSelectOther { id },
+                        });
+                    } else {
+                        let cases = contents
+                            .cases
+                            .iter()
+                            .map(|case| (case.name.clone(), case.default_string.value()))
+                            .collect();
+
+                        let info = TrInfo {
+                            string: TrString::Select(cases),
+                            file: self.current_path.borrow().clone(),
+                            plural: false,
+                            ordinal: false,
+                            select: true,
+                            description: contents
+                                .context
+                                .iter()
+                                .find(|v| v.name == "description")
+                                .and_then(|v| match &v.value {
+                                    Expr::Lit(lit) => match &lit.lit {
+                                        Lit::Str(str) => Some(str.value()),
                                         _ => None,
-                                    }),
-                                line_no: mac.tokens.span().start().line,
-                            },
-                        );
-
-                        if let Some(replaced) = replaced {
-                            self.errors.push(VisitorError {
-                                span: mac.tokens.span(),
-                                file: self.current_path.borrow().clone(),
-                                error_type: VisitorErrorType::DuplicateDefinition {
-                                    id: contents.translation_id.value(),
-                                    last_seen_file: replaced.file,
-                                    last_seen_line: replaced.line_no,
-                                },
-                            });
-                        }
+                                    },
+                                    _ => None,
+                                }),
+                            line_no: mac.tokens.span().start().line,
+                        };
+                        self.define(id, info);
                     }
 
                     for variable in contents.variables {
@@ -355,12 +596,16 @@ impl<'ast> Visit<'ast> for TrMacroVisitor {
                     }
                 }
             }
-            _ => {
+            name => {
                 trace!("non-tr(n) macro, attempting to enter");
 
                 if let Ok(CommaSeperatedExpr { exprs }) =
                     syn::parse2::<CommaSeperatedExpr>(mac.tokens.clone())
                 {
+                    if let Some(mapping) = self.custom_macros.get(name).cloned() {
+                        self.visit_custom_macro(&mapping, &exprs, mac.tokens.span().start().line);
+                    }
+
                     trace!(
                         "found expr list, trying to enter {}",
                         mac.path.segments.last().unwrap().ident
@@ -429,7 +674,7 @@ pub enum GenerationError {
 /// Generate translation catalog files from source code.
 ///
 /// This function scans all Rust source files in the `src` directory of the given
-/// project, extracts `tr!` and `trn!` macro invocations, and generates the
+/// project, extracts `tr!`, `trn!` and `trs!` macro invocations, and generates the
 /// translation catalog and metadata files.
 ///
 /// # Arguments
@@ -462,7 +707,34 @@ pub enum GenerationError {
 ///     }
 /// }
 /// ```
+///
+/// # Caching
+///
+/// When called from a build script, the strings found in each source file are cached in
+/// the build script's `OUT_DIR`, and files that haven't changed since the last build are
+/// not parsed again. Use [`generate_with_cache`] to choose where the cache is stored.
 pub fn generate(manifest_directory: &Path) -> GenerationResult {
+    let cache_file =
+        env::var_os("OUT_DIR").map(|out_dir| PathBuf::from(out_dir).join("cntp_i18n_cache.json"));
+    generate_with_cache(manifest_directory, cache_file.as_deref())
+}
+
+/// Generate translation catalog files from source code, caching the strings found in each
+/// source file.
+///
+/// This works like [`generate`], but source files that haven't changed since `cache_file`
+/// was last written are not parsed again. If `cache_file` is `None`, every source file is
+/// parsed.
+///
+/// # Arguments
+///
+/// * `manifest_directory` - Path to the directory containing `Cargo.toml`
+/// * `cache_file` - Path to the file to store the cache in, which is created if it doesn't
+///   exist
+pub fn generate_with_cache(
+    manifest_directory: &Path,
+    cache_file: Option<&Path>,
+) -> GenerationResult {
     let config = get_i18n_config(manifest_directory);
 
     let Ok(locale) = Locale::try_from_str(&config.i18n.default_language) else {
@@ -473,31 +745,49 @@ pub fn generate(manifest_directory: &Path) -> GenerationResult {
         exit(1);
     };
 
-    let plural_rules = PluralRules::try_new(locale.into(), Default::default())
+    let plural_rules = PluralRules::try_new(locale.clone().into(), Default::default())
         .expect("could not create plural_rules");
+    let ordinal_rules =
+        PluralRules::try_new_ordinal(locale.into()).expect("could not create ordinal_rules");
 
     let current_file = Rc::new(RefCell::new(PathBuf::new()));
 
-    let mut visitor = TrMacroVisitor::new(plural_rules, current_file.clone());
+    let mut visitor = TrMacroVisitor::new(plural_rules, ordinal_rules, current_file.clone());
+    visitor.custom_macros = config.i18n.macros.clone();
 
     let mut errors_encountered = GenerationErrorHandler {
         errors: Default::default(),
     };
 
-    for entry in WalkDir::new(manifest_directory.join("src"))
-        .follow_links(true)
-        .into_iter()
-        .filter_map(|result| result.ok())
-        .filter(|inner| inner.path().extension() == Some(OsStr::new("rs")))
-    {
-        debug!("reading {:?}", entry.path());
+    let mut previous_cache = cache_file
+        .map(|cache_file| {
+            SourceCache::load(
+                cache_file,
+                &config.i18n.default_language,
+                &config.i18n.macros,
+            )
+        })
+        .unwrap_or_else(|| SourceCache::new(&config.i18n.default_language, &config.i18n.macros));
+    let mut cache = SourceCache::new(&config.i18n.default_language, &config.i18n.macros);
+    let mut unchanged_files = 0;
 
+    for entry in source_files(manifest_directory) {
         let Ok(contents) = fs::read_to_string(entry.path()) else {
             errors_encountered
                 .push_string(format!("failed to read source file {:?}", entry.path()));
             continue;
         };
 
+        if let Some(cached_file) = previous_cache.take(entry.path(), &contents) {
+            trace!("{:?} is unchanged, using cached strings", entry.path());
+            visitor.replay(&cached_file);
+            cache.insert(entry.path().to_path_buf(), cached_file);
+            unchanged_files += 1;
+            continue;
+        }
+
+        debug!("reading {:?}", entry.path());
+
         *current_file.borrow_mut() = entry.path().to_path_buf();
 
         let Ok(syntax) = parse_file(&contents) else {
@@ -506,7 +796,28 @@ pub fn generate(manifest_directory: &Path) -> GenerationResult {
             continue;
         };
 
+        let error_count = visitor.errors.len();
         visitor.visit_file(&syntax);
+
+        // Files with errors are parsed again next time, so that their errors are reported again
+        if visitor.errors.len() == error_count {
+            let (strings, expected_strings) = visitor.file_strings(entry.path());
+            cache.insert(
+                entry.path().to_path_buf(),
+                CachedFile::new(&contents, strings, expected_strings),
+            );
+        }
+    }
+
+    if let Some(cache_file) = cache_file {
+        debug!(
+            "{} source file(s) unchanged since last generation",
+            unchanged_files
+        );
+
+        if let Err(e) = cache.save(cache_file) {
+            warn!("failed to write source cache {:?}: {}", cache_file, e);
+        }
     }
 
     visitor.finish();
@@ -536,21 +847,28 @@ pub fn generate(manifest_directory: &Path) -> GenerationResult {
                     catalog[key] = json!(string.as_str());
                 }
                 TrString::Plural(strings) => {
-                    catalog[key] = strings
+                    let forms = strings
                         .iter()
                         .fold(json!({}), |mut key, (category, string)| {
-                            let category_id = match category {
-                                PluralCategory::Zero => "zero",
-                                PluralCategory::One => "one",
-                                PluralCategory::Two => "two",
-                                PluralCategory::Few => "few",
-                                PluralCategory::Many => "many",
-                                PluralCategory::Other => "other",
-                            };
-
-                            key[category_id] = json!(string.as_str());
+                            key[category_name(*category)] = json!(string.as_str());
                             key
-                        })
+                        });
+
+                    catalog[key] = if value.ordinal {
+                        json!({ "ordinal": forms })
+                    } else {
+                        forms
+                    };
+                }
+                TrString::Select(cases) => {
+                    catalog[key] = json!({
+                        "select": cases
+                            .iter()
+                            .fold(json!({}), |mut key, (case, string)| {
+                                key[case] = json!(string.as_str());
+                                key
+                            })
+                    })
                 }
             }
             catalog
@@ -587,12 +905,41 @@ pub fn generate(manifest_directory: &Path) -> GenerationResult {
         catalog_path
     );
 
+    let meta_path = catalog_path.with_file_name("meta.json");
+
+    // Record which version of each source string the existing translations were made for,
+    // so that translations of strings that have since changed can be flagged for review
+    let source = load::translation(&catalog_path)
+        .map(|source| source.into_iter().collect::<HashMap<_, _>>())
+        .unwrap_or_default();
+    let previous_meta = load::meta(&meta_path).unwrap_or_default();
+    let catalogs = config
+        .i18n
+        .catalog_files(manifest_directory)
+        .into_iter()
+        .filter(|file| file != &catalog_path)
+        .filter_map(|file| {
+            let language = file.file_stem()?.to_string_lossy().to_string();
+            match load::translation(&file) {
+                Ok(catalog) => Some((language, catalog.into_iter().collect())),
+                Err(e) => {
+                    error!("failed to read catalog {:?}: {}", file, e);
+                    None
+                }
+            }
+        })
+        .collect::<Vec<_>>();
+
     let meta =
         visitor
             .strings
             .iter()
             .sorted_by_key(|x| x.0)
             .fold(json!({}), |mut meta, (key, value)| {
+                let source_hash = source.get(key).map(|entry| entry.content_hash());
+                let translated_from = source_hash.as_ref().map(|source_hash| {
+                    review::translated_from(key, source_hash, previous_meta.get(key), &catalogs)
+                });
                 meta[key] = json!({
                     "context": value.file.file_name().and_then(|v| v.to_str()),
                     "definedIn": value.file
@@ -607,13 +954,15 @@ pub fn generate(manifest_directory: &Path) -> GenerationResult {
                             , value.line_no
                         )),
                     "plural": value.plural,
+                    "ordinal": value.ordinal,
+                    "select": value.select,
                     "description": value.description,
+                    "sourceHash": source_hash,
+                    "translatedFrom": translated_from.unwrap_or_default(),
                 });
                 meta
             });
 
-    let meta_path = catalog_path.with_file_name("meta.json");
-
     let Ok(meta_file) = OpenOptions::new()
         .write(true)
         .create(true)
@@ -645,6 +994,15 @@ pub fn generate(manifest_directory: &Path) -> GenerationResult {
     }
 }
 
+/// Every Rust source file in the `src` directory of a crate.
+fn source_files(manifest_directory: &Path) -> impl Iterator<Item = walkdir::DirEntry> {
+    WalkDir::new(manifest_directory.join("src"))
+        .follow_links(true)
+        .into_iter()
+        .filter_map(|result| result.ok())
+        .filter(|inner| inner.path().extension() == Some(OsStr::new("rs")))
+}
+
 /// Generate translation files with default settings for use in `build.rs`.
 ///
 /// This is a convenience wrapper around [`generate`] that:
@@ -0,0 +1,259 @@
+//! Detection of user-visible text that hasn't been translated.
+//!
+//! The lint looks for string literals passed directly to methods and functions that
+//! display text, such as `.child("Save")` or `.title("Settings")`, which usually need
+//! to be wrapped in `tr!`. It is exposed on the command line as `cargo cntp-i18n lint`.
+//!
+//! Text that is intentionally untranslated can be allowed by marking the enclosing
+//! item with `#[cntp_i18n::allow_hardcoded]`, or with a `// cntp-i18n: allow-hardcoded`
+//! comment at the end of the line or on its own line above it. Test code marked with
+//! `#[cfg(test)]` is not checked.
+
+use std::{collections::HashMap, fs, path::Path};
+
+use cntp_i18n_build_core::config::{MacroMapping, get_i18n_config};
+use itertools::Itertools;
+use serde::Serialize;
+use syn::{
+    Attribute, Expr, ExprCall, ExprLit, ExprMethodCall, ImplItemFn, ItemFn, ItemImpl, ItemMod, Lit,
+    Macro, TraitItemFn, parse_file,
+    visit::{self, Visit},
+};
+use tracing::{debug, error};
+
+use crate::{CommaSeperatedExpr, source_files};
+
+/// Methods that display the text passed as their first argument.
+const TEXT_METHODS: &[&str] = &[
+    "body",
+    "child",
+    "label",
+    "message",
+    "placeholder",
+    "set_placeholder",
+    "subtitle",
+    "summary",
+    "text",
+    "title",
+    "tooltip",
+];
+
+/// Functions that display text, and the position of the text argument.
+const TEXT_FUNCTIONS: &[(&str, usize)] = &[
+    ("icon_text", 1),
+    ("rich_text", 1),
+    ("simple_tooltip", 0),
+    ("subtitle", 0),
+];
+
+/// Macros whose arguments are translated.
+const TRANSLATION_MACROS: &[&str] = &["tr", "trn", "trs", "trf", "tr_noop", "trn_noop", "trs_noop"];
+
+/// The comment that allows hardcoded text on its line, or the line below when on its own line.
+pub const ALLOW_COMMENT: &str = "cntp-i18n: allow-hardcoded";
+
+/// The attribute that allows hardcoded text in an item.
+const ALLOW_ATTRIBUTE: &str = "allow_hardcoded";
+
+/// User-visible text that hasn't been translated.
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct LintIssue {
+    /// The file the text is in, relative to the directory containing `Cargo.toml`.
+    pub file: String,
+    /// The line the text is on.
+    pub line: usize,
+    /// The column the text starts at.
+    pub column: usize,
+    /// The method or function the text is passed to.
+    pub sink: String,
+    /// The text.
+    pub text: String,
+}
+
+/// The result of linting every source file in a crate.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct LintReport {
+    /// Every untranslated string that was found.
+    pub issues: Vec<LintIssue>,
+}
+
+/// Find user-visible text that hasn't been translated in every source file of a crate.
+///
+/// # Arguments
+///
+/// * `manifest_directory` - Path to the directory containing `Cargo.toml`
+pub fn lint(manifest_directory: &Path) -> LintReport {
+    let config = get_i18n_config(manifest_directory);
+    let mut report = LintReport::default();
+
+    for entry in source_files(manifest_directory).sorted_by(|a, b| a.path().cmp(b.path())) {
+        debug!("linting {:?}", entry.path());
+
+        let Ok(contents) = fs::read_to_string(entry.path()) else {
+            error!("failed to read source file {:?}", entry.path());
+            continue;
+        };
+
+        let file = entry
+            .path()
+            .strip_prefix(manifest_directory)
+            .unwrap_or(entry.path())
+            .iter()
+            .map(|component| component.to_string_lossy())
+            .join("/");
+
+        match lint_source(&contents, &file, &config.i18n.macros) {
+            Ok(issues) => report.issues.extend(issues),
+            Err(_) => error!("failed to parse source file {:?}", entry.path()),
+        }
+    }
+
+    report
+}
+
+/// Find user-visible text that hasn't been translated in a source file.
+pub(crate) fn lint_source(
+    contents: &str,
+    file: &str,
+    custom_macros: &HashMap<String, MacroMapping>,
+) -> syn::Result<Vec<LintIssue>> {
+    let syntax = parse_file(contents)?;
+
+    let mut visitor = HardcodedTextVisitor {
+        file,
+        lines: contents.lines().collect(),
+        custom_macros,
+        issues: Vec::new(),
+    };
+    visitor.visit_file(&syntax);
+
+    Ok(visitor.issues)
+}
+
+struct HardcodedTextVisitor<'a> {
+    file: &'a str,
+    lines: Vec<&'a str>,
+    custom_macros: &'a HashMap<String, MacroMapping>,
+    issues: Vec<LintIssue>,
+}
+
+impl HardcodedTextVisitor<'_> {
+    fn check_argument(&mut self, argument: Option<&Expr>, sink: &str) {
+        let Some(Expr::Lit(ExprLit {
+            lit: Lit::Str(string),
+            ..
+        })) = argument
+        else {
+            return;
+        };
+
+        // Text without any letters, such as separators and symbols, doesn't need translating
+        let text = string.value();
+        if !text.chars().any(char::is_alphabetic) {
+            return;
+        }
+
+        let start = string.span().start();
+        let line = |number: usize| self.lines.get(number.checked_sub(1)?).copied();
+        let allowed_by_comment = line(start.line).is_some_and(|line| line.contains(ALLOW_COMMENT))
+            || line(start.line.saturating_sub(1)).is_some_and(|line| {
+                line.trim_start().starts_with("//") && line.contains(ALLOW_COMMENT)
+            });
+        if allowed_by_comment {
+            return;
+        }
+
+        self.issues.push(LintIssue {
+            file: self.file.to_string(),
+            line: start.line,
+            column: start.column + 1,
+            sink: sink.to_string(),
+            text,
+        });
+    }
+}
+
+/// Whether an item is allowed to contain hardcoded text.
+fn allowed(attributes: &[Attribute]) -> bool {
+    attributes.iter().any(|attribute| {
+        attribute
+            .path()
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == ALLOW_ATTRIBUTE)
+            || (attribute.path().is_ident("cfg")
+                && attribute
+                    .meta
+                    .require_list()
+                    .is_ok_and(|list| list.tokens.to_string() == "test"))
+    })
+}
+
+impl<'ast> Visit<'ast> for HardcodedTextVisitor<'_> {
+    fn visit_expr_method_call(&mut self, call: &'ast ExprMethodCall) {
+        let method = call.method.to_string();
+        if TEXT_METHODS.contains(&method.as_str()) {
+            self.check_argument(call.args.first(), &method);
+        }
+
+        visit::visit_expr_method_call(self, call);
+    }
+
+    fn visit_expr_call(&mut self, call: &'ast ExprCall) {
+        if let Expr::Path(path) = &*call.func
+            && let Some(segment) = path.path.segments.last()
+            && let Some((function, position)) = TEXT_FUNCTIONS
+                .iter()
+                .find(|(function, _)| segment.ident == function)
+        {
+            self.check_argument(call.args.iter().nth(*position), function);
+        }
+
+        visit::visit_expr_call(self, call);
+    }
+
+    fn visit_macro(&mut self, mac: &'ast Macro) {
+        let name = mac.path.segments.last().unwrap().ident.to_string();
+        if TRANSLATION_MACROS.contains(&name.as_str()) || self.custom_macros.contains_key(&name) {
+            return;
+        }
+
+        if let Ok(CommaSeperatedExpr { exprs }) =
+            syn::parse2::<CommaSeperatedExpr>(mac.tokens.clone())
+        {
+            for expr in exprs.iter() {
+                self.visit_expr(expr);
+            }
+        }
+    }
+
+    fn visit_item_fn(&mut self, item: &'ast ItemFn) {
+        if !allowed(&item.attrs) {
+            visit::visit_item_fn(self, item);
+        }
+    }
+
+    fn visit_item_impl(&mut self, item: &'ast ItemImpl) {
+        if !allowed(&item.attrs) {
+            visit::visit_item_impl(self, item);
+        }
+    }
+
+    fn visit_item_mod(&mut self, item: &'ast ItemMod) {
+        if !allowed(&item.attrs) {
+            visit::visit_item_mod(self, item);
+        }
+    }
+
+    fn visit_impl_item_fn(&mut self, item: &'ast ImplItemFn) {
+        if !allowed(&item.attrs) {
+            visit::visit_impl_item_fn(self, item);
+        }
+    }
+
+    fn visit_trait_item_fn(&mut self, item: &'ast TraitItemFn) {
+        if !allowed(&item.attrs) {
+            visit::visit_trait_item_fn(self, item);
+        }
+    }
+}
@@ -0,0 +1,647 @@
+//! Gettext PO and POT import and export.
+//!
+//! Catalogs are exported with the translation key as the `msgctxt` of each
+//! entry, and the source string as the `msgid`. Descriptions from `meta.json`
+//! are exported as extracted comments (`#.`) and the location the string is
+//! defined at as a reference comment (`#:`).
+//!
+//! ## Plurals
+//!
+//! Plural strings are exported with the source language's "one" form as the
+//! `msgid` and its "other" form as the `msgid_plural`. Each `msgstr[n]` maps
+//! to the n-th plural category the target language uses, in the order
+//! zero, one, two, few, many, other. For example, Ukrainian uses the "one",
+//! "few", "many" and "other" categories, so `msgstr[2]` is the "many" form.
+//!
+//! Exported PO files record this mapping in an `X-Cntp-Plural-Categories`
+//! header, which is used when the file is imported again.
+//!
+//! ## Select strings
+//!
+//! Gettext has no equivalent of select strings, so each case of a select
+//! string is exported as a separate entry whose `msgctxt` is the translation
+//! key and the case name separated by a full stop, such as `PROFILE_UPDATED.feminine`.
+//!
+//! ## Ordinal plurals
+//!
+//! Gettext plural forms are always cardinal, so ordinal plural strings are
+//! exported in the same way as select strings, with one entry for each ordinal
+//! category the target language uses, such as `FINISHED_PLACE.few`.
+//!
+//! These functions are exposed on the command line as
+//! `cargo cntp-i18n export --format po` and `cargo cntp-i18n import`.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::{self, ErrorKind},
+    path::Path,
+};
+
+use cntp_i18n_build_core::{
+    config::get_i18n_config,
+    load::{self, TranslationEntry, TranslationMeta},
+};
+use itertools::Itertools;
+
+use crate::catalog::{
+    PLURAL_CATEGORIES, merge_catalog, ordinal_categories, plural_categories, sorted_cases,
+    sorted_forms, validate_import_language, write_catalog,
+};
+use crate::exchange::ImportReport;
+
+/// The header recording which plural category each `msgstr[n]` maps to.
+const PLURAL_CATEGORIES_HEADER: &str = "X-Cntp-Plural-Categories";
+
+/// Export a project's strings as a PO file.
+///
+/// If `language` is `None`, a POT template with empty translations is exported.
+/// Otherwise, the translations are filled in from that language's catalog, if it exists.
+///
+/// # Arguments
+///
+/// * `manifest_directory` - Path to the directory containing `Cargo.toml`
+/// * `language` - The language to export, or `None` to export a template
+///
+/// # Errors
+///
+/// Returns an error if `meta.json` or any catalog cannot be loaded, or if the
+/// language is not a valid locale.
+pub fn export(manifest_directory: &Path, language: Option<&str>) -> io::Result<String> {
+    let config = get_i18n_config(manifest_directory);
+    let meta = load::meta(&config.i18n.translation_meta_file(manifest_directory))?;
+    let source = load::translation(&config.i18n.translation_catalog_file(manifest_directory))?
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+
+    let Some(language) = language else {
+        return Ok(write_po(&meta, &source, None));
+    };
+
+    let catalog_path = config
+        .i18n
+        .translation_directory(manifest_directory)
+        .join(format!("{language}.json"));
+    let translations = if catalog_path.exists() {
+        load::translation(&catalog_path)?.into_iter().collect()
+    } else {
+        HashMap::new()
+    };
+
+    Ok(write_po(&meta, &source, Some((language, &translations))))
+}
+
+/// Import a PO file into a language's catalog.
+///
+/// The language is taken from the `language` argument if provided, and
+/// otherwise from the `Language` header of the PO file. The translated entries
+/// of the PO file are merged into the catalog for that language. Entries that
+/// are fuzzy, untranslated, or no longer defined in `meta.json` are skipped,
+/// and keep their existing translation in the catalog.
+///
+/// # Arguments
+///
+/// * `manifest_directory` - Path to the directory containing `Cargo.toml`
+/// * `contents` - The contents of the PO file
+/// * `language` - The language to import into, overriding the PO file's header
+///
+/// # Errors
+///
+/// Returns an error if the PO file cannot be parsed, the language cannot be
+/// determined, or the catalog cannot be written.
+pub fn import(
+    manifest_directory: &Path,
+    contents: &str,
+    language: Option<&str>,
+) -> io::Result<ImportReport> {
+    let config = get_i18n_config(manifest_directory);
+    let meta = load::meta(&config.i18n.translation_meta_file(manifest_directory))?;
+
+    let entries = parse_po(contents)?;
+    let header = entries
+        .iter()
+        .find(|entry| entry.context.is_none() && entry.id.is_empty())
+        .map(|entry| parse_header(entry.strings.get(&0).map_or("", String::as_str)))
+        .unwrap_or_default();
+
+    let Some(language) = language
+        .map(str::to_string)
+        .or_else(|| header.get("Language").cloned())
+        .filter(|language| !language.is_empty())
+    else {
+        return Err(io::Error::new(
+            ErrorKind::InvalidInput,
+            "the PO file has no Language header, so the language must be specified",
+        ));
+    };
+
+    validate_import_language(&language)?;
+    if language == config.i18n.default_language {
+        return Err(io::Error::new(
+            ErrorKind::InvalidInput,
+            "translations cannot be imported into the default language",
+        ));
+    }
+
+    let categories = match header.get(PLURAL_CATEGORIES_HEADER) {
+        Some(categories) => categories.split(',').map(str::trim).collect(),
+        None => plural_categories(&language).unwrap_or_default(),
+    };
+
+    let (imported, report) = import_entries(&language, &entries, &categories, &meta);
+
+    let catalog_path = config
+        .i18n
+        .translation_directory(manifest_directory)
+        .join(format!("{language}.json"));
+    let existing = if catalog_path.exists() {
+        load::translation(&catalog_path)?
+    } else {
+        Vec::new()
+    };
+    write_catalog(&catalog_path, &merge_catalog(existing, imported))?;
+
+    Ok(report)
+}
+
+/// Convert parsed PO entries into catalog entries.
+pub(crate) fn import_entries(
+    language: &str,
+    entries: &[PoEntry],
+    categories: &[&str],
+    meta: &HashMap<String, TranslationMeta>,
+) -> (Vec<(String, TranslationEntry)>, ImportReport) {
+    let mut report = ImportReport::new(language);
+    let mut catalog = Vec::new();
+    let mut select = BTreeMap::<String, HashMap<String, String>>::new();
+    let mut ordinal = BTreeMap::<String, HashMap<String, String>>::new();
+
+    for entry in entries {
+        let Some(key) = &entry.context else {
+            if !entry.id.is_empty() {
+                report.reject(&entry.id, "entry has no msgctxt identifying its key");
+            }
+            continue;
+        };
+
+        if entry.strings.values().all(|string| string.is_empty()) {
+            continue;
+        }
+
+        if entry.flags.iter().any(|flag| flag == "fuzzy") {
+            report.reject(key, "translation is marked as fuzzy");
+            continue;
+        }
+
+        let Some(key_meta) = meta.get(key) else {
+            let Some((parent_key, case)) =
+                key.rsplit_once('.').filter(|_| entry.id_plural.is_none())
+            else {
+                report.reject(key, "key is no longer defined in meta.json");
+                continue;
+            };
+            let string = entry.strings.get(&0).cloned().unwrap_or_default();
+
+            match meta.get(parent_key) {
+                Some(key_meta) if key_meta.select => {
+                    select
+                        .entry(parent_key.to_string())
+                        .or_default()
+                        .insert(case.to_string(), string);
+                }
+                Some(key_meta) if key_meta.ordinal => {
+                    if !PLURAL_CATEGORIES.contains(&case) {
+                        report.reject(key, "ordinal translation has an unknown category");
+                        continue;
+                    }
+                    ordinal
+                        .entry(parent_key.to_string())
+                        .or_default()
+                        .insert(case.to_string(), string);
+                }
+                _ => report.reject(key, "key is no longer defined in meta.json"),
+            }
+            continue;
+        };
+
+        if key_meta.select {
+            report.reject(
+                key,
+                "source string is a select string but the translation is not",
+            );
+            continue;
+        }
+
+        if key_meta.ordinal {
+            report.reject(
+                key,
+                "source string is an ordinal plural but the translation is not",
+            );
+            continue;
+        }
+
+        if key_meta.plural != entry.id_plural.is_some() {
+            report.reject(
+                key,
+                if key_meta.plural {
+                    "source string is plural but the translation is not"
+                } else {
+                    "translation is plural but the source string is not"
+                },
+            );
+            continue;
+        }
+
+        if !key_meta.plural {
+            catalog.push((
+                key.clone(),
+                TranslationEntry::Entry(entry.strings.get(&0).cloned().unwrap_or_default()),
+            ));
+            report.imported += 1;
+            continue;
+        }
+
+        let forms = entry
+            .strings
+            .iter()
+            .filter(|(_, string)| !string.is_empty())
+            .filter_map(|(index, string)| {
+                categories
+                    .get(*index)
+                    .map(|category| (category.to_string(), string.clone()))
+            })
+            .collect::<HashMap<_, _>>();
+
+        if !forms.contains_key("other") {
+            report.reject(key, "plural translation has no \"other\" form");
+            continue;
+        }
+
+        catalog.push((key.clone(), TranslationEntry::PluralEntry(forms)));
+        report.imported += 1;
+    }
+
+    for (key, mut cases) in select {
+        cases.retain(|_, string| !string.is_empty());
+        if !cases.contains_key("other") {
+            report.reject(&key, "select translation has no \"other\" case");
+            continue;
+        }
+
+        catalog.push((key, TranslationEntry::SelectEntry(cases)));
+        report.imported += 1;
+    }
+
+    for (key, mut forms) in ordinal {
+        forms.retain(|_, string| !string.is_empty());
+        if !forms.contains_key("other") {
+            report.reject(&key, "ordinal translation has no \"other\" form");
+            continue;
+        }
+
+        catalog.push((key, TranslationEntry::OrdinalEntry(forms)));
+        report.imported += 1;
+    }
+
+    (catalog, report)
+}
+
+/// Write a PO file. If `translations` is `None`, a POT template is written.
+pub(crate) fn write_po(
+    meta: &HashMap<String, TranslationMeta>,
+    source: &HashMap<String, TranslationEntry>,
+    translations: Option<(&str, &HashMap<String, TranslationEntry>)>,
+) -> String {
+    let mut po = String::new();
+
+    let categories = translations
+        .and_then(|(language, _)| plural_categories(language))
+        .unwrap_or_else(|| vec!["one", "other"]);
+    let ordinal_categories = translations.and_then(|(language, _)| ordinal_categories(language));
+
+    let mut header = vec![
+        "Content-Type: text/plain; charset=UTF-8".to_string(),
+        "Content-Transfer-Encoding: 8bit".to_string(),
+    ];
+    match translations {
+        Some((language, _)) => {
+            header.push(format!("Language: {language}"));
+            header.push(format!(
+                "Plural-Forms: nplurals={}; plural={};",
+                categories.len(),
+                plural_expression(language, &categories)
+            ));
+            header.push(format!(
+                "{PLURAL_CATEGORIES_HEADER}: {}",
+                categories.join(", ")
+            ));
+        }
+        None => {
+            header.push("Language: ".to_string());
+            header.push("Plural-Forms: nplurals=INTEGER; plural=EXPRESSION;".to_string());
+        }
+    }
+
+    write_string(&mut po, "msgid", "");
+    write_string(
+        &mut po,
+        "msgstr",
+        &header.iter().map(|line| format!("{line}\n")).join(""),
+    );
+
+    for key in meta.keys().sorted() {
+        let Some(source_entry) = source.get(key) else {
+            continue;
+        };
+        let key_meta = &meta[key];
+        let translation = translations.and_then(|(_, translations)| translations.get(key));
+
+        match source_entry {
+            TranslationEntry::Entry(string) => {
+                write_comments(&mut po, key_meta);
+                write_string(&mut po, "msgctxt", key);
+                write_string(&mut po, "msgid", string);
+                let translated = match translation {
+                    Some(TranslationEntry::Entry(translated)) => translated.as_str(),
+                    _ => "",
+                };
+                write_string(&mut po, "msgstr", translated);
+            }
+            TranslationEntry::PluralEntry(forms) => {
+                write_comments(&mut po, key_meta);
+                write_string(&mut po, "msgctxt", key);
+
+                let sorted = sorted_forms(forms);
+                let singular = forms
+                    .get("one")
+                    .map(String::as_str)
+                    .or_else(|| sorted.first().map(|(_, form)| *form))
+                    .unwrap_or_default();
+                let plural = forms.get("other").map_or("", String::as_str);
+                write_string(&mut po, "msgid", singular);
+                write_string(&mut po, "msgid_plural", plural);
+
+                for (index, category) in categories.iter().enumerate() {
+                    let translated = match translation {
+                        Some(TranslationEntry::PluralEntry(translated)) => {
+                            translated.get(*category).map_or("", String::as_str)
+                        }
+                        _ => "",
+                    };
+                    write_string(&mut po, &format!("msgstr[{index}]"), translated);
+                }
+            }
+            TranslationEntry::OrdinalEntry(forms) => {
+                let sorted = sorted_forms(forms);
+                let categories = ordinal_categories
+                    .clone()
+                    .unwrap_or_else(|| sorted.iter().map(|(category, _)| *category).collect());
+
+                for category in categories {
+                    let string = forms
+                        .get(category)
+                        .or_else(|| forms.get("other"))
+                        .map_or("", String::as_str);
+                    write_comments(&mut po, key_meta);
+                    write_string(&mut po, "msgctxt", &format!("{key}.{category}"));
+                    write_string(&mut po, "msgid", string);
+                    let translated = match translation {
+                        Some(TranslationEntry::OrdinalEntry(translated)) => {
+                            translated.get(category).map_or("", String::as_str)
+                        }
+                        _ => "",
+                    };
+                    write_string(&mut po, "msgstr", translated);
+                }
+            }
+            TranslationEntry::SelectEntry(cases) => {
+                for (case, string) in sorted_cases(cases) {
+                    write_comments(&mut po, key_meta);
+                    write_string(&mut po, "msgctxt", &format!("{key}.{case}"));
+                    write_string(&mut po, "msgid", string);
+                    let translated = match translation {
+                        Some(TranslationEntry::SelectEntry(translated)) => {
+                            translated.get(case).map_or("", String::as_str)
+                        }
+                        _ => "",
+                    };
+                    write_string(&mut po, "msgstr", translated);
+                }
+            }
+        }
+    }
+
+    po
+}
+
+/// Start a new entry, writing the description and location of the string as comments.
+fn write_comments(po: &mut String, key_meta: &TranslationMeta) {
+    po.push('\n');
+    if let Some(description) = &key_meta.description {
+        for line in description.lines() {
+            po.push_str(&format!("#. {line}\n"));
+        }
+    }
+    if let Some(defined_in) = &key_meta.defined_in {
+        po.push_str(&format!("#: {defined_in}\n"));
+    }
+}
+
+/// Get a gettext plural expression that selects the index of the matching plural category.
+///
+/// Gettext expressions are only known for common plural rules. For other languages,
+/// `0` is returned; the mapping is still recorded in the `X-Cntp-Plural-Categories` header.
+fn plural_expression(language: &str, categories: &[&str]) -> &'static str {
+    let primary_language = language.split(['-', '_']).next().unwrap_or_default();
+    match (categories, primary_language) {
+        (["other"], _) => "0",
+        (["one", "other"], "hy" | "kab" | "ff") => "(n > 1)",
+        (["one", "other"], _) => "(n != 1)",
+        // Millions take the "many" form, as in "1 million de fichiers"
+        (["one", "many", "other"], "fr") => "(n==0 || n==1 ? 0 : n%1000000==0 ? 1 : 2)",
+        (["one", "many", "other"], "pt") if !is_european_portuguese(language) => {
+            "(n==0 || n==1 ? 0 : n%1000000==0 ? 1 : 2)"
+        }
+        (["one", "many", "other"], "es" | "it" | "ca" | "pt") => {
+            "(n==1 ? 0 : n!=0 && n%1000000==0 ? 1 : 2)"
+        }
+        (["one", "few", "many", "other"], "ru" | "uk" | "be") => {
+            "(n%10==1 && n%100!=11 ? 0 : n%10>=2 && n%10<=4 && (n%100<12 || n%100>14) ? 1 : 2)"
+        }
+        (["one", "few", "many", "other"], "pl") => {
+            "(n==1 ? 0 : n%10>=2 && n%10<=4 && (n%100<12 || n%100>14) ? 1 : 2)"
+        }
+        (["one", "few", "many", "other"], "cs" | "sk") => "(n==1 ? 0 : n>=2 && n<=4 ? 1 : 3)",
+        (["one", "two", "other"], "he") => "(n==1 ? 0 : n==2 ? 1 : 2)",
+        (["zero", "one", "two", "few", "many", "other"], "ar") => {
+            "(n==0 ? 0 : n==1 ? 1 : n==2 ? 2 : n%100>=3 && n%100<=10 ? 3 : n%100>=11 ? 4 : 5)"
+        }
+        _ => "0",
+    }
+}
+
+/// European Portuguese uses "one" only for 1, while Brazilian Portuguese also uses it for 0.
+fn is_european_portuguese(language: &str) -> bool {
+    language.replace('_', "-").eq_ignore_ascii_case("pt-PT")
+}
+
+/// Write a keyword and string, splitting the string over several lines if it contains newlines.
+fn write_string(po: &mut String, keyword: &str, value: &str) {
+    if value.trim_end_matches('\n').contains('\n') {
+        po.push_str(&format!("{keyword} \"\"\n"));
+        for line in value.split_inclusive('\n') {
+            po.push_str(&format!("\"{}\"\n", escape(line)));
+        }
+    } else {
+        po.push_str(&format!("{keyword} \"{}\"\n", escape(value)));
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+        .replace('\t', "\\t")
+}
+
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => unescaped.push('\n'),
+            Some('r') => unescaped.push('\r'),
+            Some('t') => unescaped.push('\t'),
+            Some(other) => unescaped.push(other),
+            None => unescaped.push('\\'),
+        }
+    }
+    unescaped
+}
+
+/// A single entry in a PO file.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct PoEntry {
+    pub flags: Vec<String>,
+    pub context: Option<String>,
+    pub id: String,
+    pub id_plural: Option<String>,
+    /// The translated strings, keyed by their `msgstr` index.
+    pub strings: BTreeMap<usize, String>,
+}
+
+#[derive(Clone, Copy)]
+enum PoField {
+    Context,
+    Id,
+    IdPlural,
+    String(usize),
+}
+
+/// Parse the entries of a PO file.
+pub(crate) fn parse_po(contents: &str) -> io::Result<Vec<PoEntry>> {
+    let mut entries = Vec::new();
+    let mut entry = PoEntry::default();
+    let mut has_id = false;
+    let mut field = None;
+
+    for (line_number, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        let invalid = |reason: &str| {
+            io::Error::new(
+                ErrorKind::InvalidData,
+                format!("line {}: {}", line_number + 1, reason),
+            )
+        };
+
+        // Obsolete entries are kept by translation tools, but are no longer used
+        if line.starts_with("#~") {
+            continue;
+        }
+
+        if let Some(flags) = line.strip_prefix("#,") {
+            if has_id {
+                entries.push(std::mem::take(&mut entry));
+                has_id = false;
+            }
+            entry
+                .flags
+                .extend(flags.split(',').map(|flag| flag.trim().to_string()));
+            continue;
+        }
+
+        if line.is_empty() || line.starts_with('#') {
+            field = None;
+            continue;
+        }
+
+        let (keyword, value) = match line.split_once(char::is_whitespace) {
+            Some((keyword, value)) if !line.starts_with('"') => (keyword, value.trim()),
+            _ => ("", line),
+        };
+
+        let string = value
+            .strip_prefix('"')
+            .and_then(|value| value.strip_suffix('"'))
+            .map(unescape)
+            .ok_or_else(|| invalid("expected a quoted string"))?;
+
+        let new_field = match keyword {
+            "" => field.ok_or_else(|| invalid("string continues no keyword"))?,
+            "msgctxt" => PoField::Context,
+            "msgid" => PoField::Id,
+            "msgid_plural" => PoField::IdPlural,
+            "msgstr" => PoField::String(0),
+            keyword => {
+                let index = keyword
+                    .strip_prefix("msgstr[")
+                    .and_then(|index| index.strip_suffix(']'))
+                    .and_then(|index| index.parse().ok())
+                    .ok_or_else(|| invalid(&format!("unknown keyword {keyword}")))?;
+                PoField::String(index)
+            }
+        };
+
+        // A msgctxt or msgid after the translated strings starts a new entry
+        if !keyword.is_empty()
+            && has_id
+            && matches!(new_field, PoField::Context | PoField::Id)
+            && !entry.strings.is_empty()
+        {
+            entries.push(std::mem::take(&mut entry));
+            has_id = false;
+        }
+
+        match new_field {
+            PoField::Context => entry.context.get_or_insert_default().push_str(&string),
+            PoField::Id => {
+                has_id = true;
+                entry.id.push_str(&string)
+            }
+            PoField::IdPlural => entry.id_plural.get_or_insert_default().push_str(&string),
+            PoField::String(index) => entry.strings.entry(index).or_default().push_str(&string),
+        }
+        field = Some(new_field);
+    }
+
+    if has_id {
+        entries.push(entry);
+    }
+
+    Ok(entries)
+}
+
+/// Parse the `Key: Value` lines of the header entry.
+fn parse_header(header: &str) -> HashMap<String, String> {
+    header
+        .lines()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
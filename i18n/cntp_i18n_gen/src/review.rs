@@ -0,0 +1,118 @@
+//! Tracking of translations made for an older version of their source string.
+//!
+//! When generating, `meta.json` records a hash of every source string and, for
+//! each language, the hash of the source string at the time the translation last
+//! changed. When a source string is edited, its translations keep the old hash, and
+//! are reported by `cargo cntp-i18n check` as needing review until a translator
+//! either changes them or marks them as reviewed:
+//!
+//! ```bash
+//! cargo cntp-i18n review --language de
+//! ```
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs, io,
+    path::Path,
+};
+
+use cntp_i18n_build_core::{
+    config::get_i18n_config,
+    load::{self, TranslatedFrom, TranslationEntry, TranslationMeta},
+};
+use itertools::Itertools;
+use serde_json::{Value, json};
+
+/// Work out the source string each language's translation of `key` was made for.
+///
+/// Translations that haven't changed since the last generation keep their previous
+/// record. New and changed translations are recorded as translations of the current
+/// source string.
+pub(crate) fn translated_from(
+    key: &str,
+    source_hash: &str,
+    previous: Option<&TranslationMeta>,
+    catalogs: &[(String, HashMap<String, TranslationEntry>)],
+) -> BTreeMap<String, TranslatedFrom> {
+    catalogs
+        .iter()
+        .filter_map(|(language, catalog)| {
+            let entry = catalog.get(key).filter(|entry| !entry.is_empty())?;
+            let translation_hash = entry.content_hash();
+
+            let translated_from = previous
+                .and_then(|previous| previous.translated_from.get(language))
+                .filter(|translated_from| translated_from.translation_hash == translation_hash)
+                .cloned()
+                .unwrap_or_else(|| TranslatedFrom {
+                    source_hash: source_hash.to_string(),
+                    translation_hash,
+                });
+
+            Some((language.clone(), translated_from))
+        })
+        .collect()
+}
+
+/// Mark translations as reviewed against the current source string.
+///
+/// If `keys` is empty, every translation in `language` that needs review is marked.
+/// Returns the keys that were marked.
+///
+/// # Arguments
+///
+/// * `manifest_directory` - Path to the directory containing `Cargo.toml`
+/// * `language` - The language of the translations to mark
+/// * `keys` - The keys of the translations to mark
+///
+/// # Errors
+///
+/// Returns an error if `meta.json` or the language's catalog cannot be read, or
+/// `meta.json` cannot be written.
+pub fn mark_reviewed(
+    manifest_directory: &Path,
+    language: &str,
+    keys: &[String],
+) -> io::Result<Vec<String>> {
+    let config = get_i18n_config(manifest_directory);
+    let meta_path = config.i18n.translation_meta_file(manifest_directory);
+    let meta = load::meta(&meta_path)?;
+    let catalog = load::translation(
+        &config
+            .i18n
+            .translation_directory(manifest_directory)
+            .join(format!("{language}.json")),
+    )?
+    .into_iter()
+    .collect::<HashMap<_, _>>();
+
+    let keys = if keys.is_empty() {
+        meta.iter()
+            .filter(|(_, key_meta)| key_meta.is_stale(language))
+            .map(|(key, _)| key.clone())
+            .sorted()
+            .collect()
+    } else {
+        keys.to_vec()
+    };
+
+    let mut raw_meta: Value = serde_json::from_slice(&fs::read(&meta_path)?)?;
+    let mut marked = Vec::new();
+    for key in keys {
+        let Some(source_hash) = meta.get(&key).and_then(|meta| meta.source_hash.clone()) else {
+            continue;
+        };
+        let Some(entry) = catalog.get(&key).filter(|entry| !entry.is_empty()) else {
+            continue;
+        };
+
+        raw_meta[&key]["translatedFrom"][language] = json!(TranslatedFrom {
+            source_hash,
+            translation_hash: entry.content_hash(),
+        });
+        marked.push(key);
+    }
+
+    fs::write(&meta_path, serde_json::to_string_pretty(&raw_meta)?)?;
+    Ok(marked)
+}
@@ -0,0 +1,150 @@
+//! Translation coverage statistics.
+//!
+//! Statistics are calculated for every catalog in the translation directory
+//! other than the default language's, using `meta.json` to determine which
+//! strings are currently defined in the source code.
+//!
+//! These statistics are exposed on the command line as `cargo cntp-i18n stats`.
+
+use std::{
+    collections::HashMap,
+    io::{self, ErrorKind},
+    path::Path,
+};
+
+use cntp_i18n_build_core::{
+    config::get_i18n_config,
+    load::{self, TranslationEntry, TranslationMeta},
+};
+use itertools::Itertools;
+use serde::Serialize;
+
+/// Translation statistics for a single language.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+pub struct LanguageStats {
+    /// The language of the catalog.
+    pub language: String,
+    /// The number of strings defined in the source code.
+    pub total: usize,
+    /// The number of strings with a translation.
+    pub translated: usize,
+    /// The number of strings not present in the catalog.
+    pub missing: usize,
+    /// The number of strings present in the catalog with an empty translation.
+    pub empty: usize,
+    /// The number of words in the source text of untranslated strings.
+    pub untranslated_words: usize,
+    /// The percentage of strings that have been translated.
+    pub coverage: f64,
+}
+
+/// Translation statistics for every language in a project.
+#[derive(Serialize, Debug, Clone, Default)]
+pub struct StatsReport {
+    /// The statistics for each language, sorted by language.
+    pub languages: Vec<LanguageStats>,
+}
+
+/// Calculate translation statistics for every catalog in a project.
+///
+/// # Arguments
+///
+/// * `manifest_directory` - Path to the directory containing `Cargo.toml`
+///
+/// # Errors
+///
+/// Returns an error if `meta.json` or any catalog cannot be loaded.
+pub fn stats(manifest_directory: &Path) -> io::Result<StatsReport> {
+    let config = get_i18n_config(manifest_directory);
+    let meta = load::meta(&config.i18n.translation_meta_file(manifest_directory))?;
+    let source = load::translation(&config.i18n.translation_catalog_file(manifest_directory))?
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+
+    let mut report = StatsReport::default();
+    for catalog_file in config
+        .i18n
+        .catalog_files(manifest_directory)
+        .into_iter()
+        .sorted()
+    {
+        let language = catalog_file
+            .file_stem()
+            .unwrap()
+            .to_string_lossy()
+            .to_string();
+        if language == config.i18n.default_language {
+            continue;
+        }
+
+        let catalog = load::translation(&catalog_file).map_err(|e| {
+            io::Error::new(
+                ErrorKind::InvalidData,
+                format!("unable to load {}: {}", catalog_file.display(), e),
+            )
+        })?;
+        report
+            .languages
+            .push(language_stats(&language, &catalog, &meta, &source));
+    }
+
+    Ok(report)
+}
+
+/// Calculate translation statistics for a single catalog.
+pub(crate) fn language_stats(
+    language: &str,
+    catalog: &[(String, TranslationEntry)],
+    meta: &HashMap<String, TranslationMeta>,
+    source: &HashMap<String, TranslationEntry>,
+) -> LanguageStats {
+    let translations = catalog
+        .iter()
+        .map(|(key, entry)| (key.as_str(), entry))
+        .collect::<HashMap<_, _>>();
+
+    let mut stats = LanguageStats {
+        language: language.to_string(),
+        total: meta.len(),
+        translated: 0,
+        missing: 0,
+        empty: 0,
+        untranslated_words: 0,
+        coverage: 100.0,
+    };
+
+    for key in meta.keys() {
+        match translations.get(key.as_str()) {
+            Some(entry) if !entry.is_empty() => {
+                stats.translated += 1;
+                continue;
+            }
+            Some(_) => stats.empty += 1,
+            None => stats.missing += 1,
+        }
+
+        stats.untranslated_words += source.get(key).map(word_count).unwrap_or_default();
+    }
+
+    if stats.total > 0 {
+        stats.coverage = stats.translated as f64 / stats.total as f64 * 100.0;
+    }
+
+    stats
+}
+
+/// Count the words a translator needs to translate for an entry.
+///
+/// Only the "other" form of a plural or select entry is counted, as the other forms
+/// are usually variations of the same text.
+fn word_count(entry: &TranslationEntry) -> usize {
+    match entry {
+        TranslationEntry::Entry(string) => string.split_whitespace().count(),
+        TranslationEntry::PluralEntry(forms)
+        | TranslationEntry::OrdinalEntry(forms)
+        | TranslationEntry::SelectEntry(forms) => forms
+            .get("other")
+            .map(|form| form.split_whitespace().count())
+            .unwrap_or_default(),
+    }
+}
@@ -1,7 +1,17 @@
+mod cache;
+mod check;
+mod custom_macros;
 mod descriptions;
 mod duplicates;
 mod helpers;
+mod lint;
+mod ordinal_plural_gen;
+mod po;
 mod predefine;
+mod review;
+mod select_gen;
 mod simple_gen;
 mod simple_plural_gen;
+mod stats;
 mod too_many_plurals;
+mod xliff;
@@ -0,0 +1,57 @@
+use crate::TrString;
+use crate::VisitorErrorType;
+use crate::cache::CachedFile;
+use crate::tests::helpers::{new_visitor, visit_string};
+use icu::plurals::PluralCategory;
+use std::path::Path;
+
+#[test]
+fn replay_cached_file() {
+    let contents = include_str!("predefine.rs.in");
+    let visitor = visit_string(contents, "en");
+
+    let (strings, expected_strings) = visitor.file_strings(Path::new(""));
+    let cached_file = CachedFile::new(contents, strings, expected_strings);
+    let cached_file: CachedFile =
+        serde_json::from_str(&serde_json::to_string(&cached_file).unwrap()).unwrap();
+
+    let mut replayed = new_visitor("en");
+    replayed.replay(&cached_file);
+    replayed.finish();
+
+    assert_eq!(replayed.strings.len(), 2);
+    match &replayed.strings["HELLO_WORLD_PLURAL"].string {
+        TrString::Plural(forms) => {
+            assert_eq!(forms[0].0, PluralCategory::One);
+            assert_eq!(forms[1].0, PluralCategory::Other);
+        }
+        _ => panic!("HELLO_WORLD_PLURAL is not plural"),
+    }
+
+    assert_eq!(replayed.errors.len(), 2);
+    match &replayed.errors[0].error_type {
+        VisitorErrorType::MissingDefinition { id } => {
+            assert_eq!(id, "THIS_STRING_IS_NOT_DEFINED");
+            assert_eq!(replayed.errors[0].line_no, visitor.errors[0].line_no);
+        }
+        _ => panic!("Visitor returned incorrect error type"),
+    }
+}
+
+#[test]
+fn replay_reports_duplicates() {
+    let contents = include_str!("simple_plural_gen.rs.in");
+    let visitor = visit_string(contents, "en");
+    let (strings, expected_strings) = visitor.file_strings(Path::new(""));
+    let cached_file = CachedFile::new(contents, strings, expected_strings);
+
+    let mut replayed = new_visitor("en");
+    replayed.replay(&cached_file);
+    replayed.replay(&cached_file);
+
+    assert_eq!(replayed.errors.len(), 1);
+    assert!(matches!(
+        &replayed.errors[0].error_type,
+        VisitorErrorType::DuplicateDefinition { id, .. } if id == "HELLO_WORLD"
+    ));
+}
@@ -0,0 +1,194 @@
+use crate::check::{CheckIssueKind, CheckReport, check_catalog};
+use cntp_i18n_build_core::load::{TranslationEntry, TranslationMeta};
+use std::collections::HashMap;
+
+fn meta(keys: &[(&str, bool)]) -> HashMap<String, TranslationMeta> {
+    keys.iter()
+        .map(|(key, plural)| {
+            (
+                key.to_string(),
+                TranslationMeta {
+                    plural: *plural,
+                    ..Default::default()
+                },
+            )
+        })
+        .collect()
+}
+
+fn plural(forms: &[(&str, &str)]) -> TranslationEntry {
+    TranslationEntry::PluralEntry(
+        forms
+            .iter()
+            .map(|(category, string)| (category.to_string(), string.to_string()))
+            .collect(),
+    )
+}
+
+fn source() -> HashMap<String, TranslationEntry> {
+    HashMap::from([
+        (
+            "GREETING".to_string(),
+            TranslationEntry::Entry("Hello, {{name}}!".into()),
+        ),
+        (
+            "ITEMS".to_string(),
+            plural(&[("one", "{{count}} item"), ("other", "{{count}} items")]),
+        ),
+    ])
+}
+
+fn check(
+    language: &str,
+    catalog: Vec<(String, TranslationEntry)>,
+) -> Vec<(String, CheckIssueKind)> {
+    let mut report = CheckReport::default();
+    check_catalog(
+        &mut report,
+        language,
+        &catalog,
+        &meta(&[("GREETING", false), ("ITEMS", true)]),
+        &source(),
+    );
+    report
+        .issues
+        .into_iter()
+        .map(|issue| (issue.key.unwrap_or_default(), issue.kind))
+        .collect()
+}
+
+#[test]
+fn consistent_catalog() {
+    let issues = check(
+        "de",
+        vec![
+            (
+                "GREETING".into(),
+                TranslationEntry::Entry("Hallo, {{name}}!".into()),
+            ),
+            (
+                "ITEMS".into(),
+                plural(&[("one", "Ein Element"), ("other", "{{count}} Elemente")]),
+            ),
+        ],
+    );
+    assert_eq!(issues, vec![]);
+}
+
+#[test]
+fn missing_and_unknown_keys() {
+    let issues = check(
+        "de",
+        vec![("REMOVED".into(), TranslationEntry::Entry("Entfernt".into()))],
+    );
+    assert_eq!(
+        issues,
+        vec![
+            ("GREETING".into(), CheckIssueKind::MissingTranslation),
+            ("ITEMS".into(), CheckIssueKind::MissingTranslation),
+            ("REMOVED".into(), CheckIssueKind::UnknownKey),
+        ]
+    );
+}
+
+#[test]
+fn placeholder_mismatch() {
+    let issues = check(
+        "de",
+        vec![
+            (
+                "GREETING".into(),
+                TranslationEntry::Entry("Hallo, {{nmae}}!".into()),
+            ),
+            (
+                "ITEMS".into(),
+                plural(&[("one", "Ein Element"), ("other", "{{cuont}} Elemente")]),
+            ),
+        ],
+    );
+    assert_eq!(
+        issues,
+        vec![
+            (
+                "GREETING".into(),
+                CheckIssueKind::PlaceholderMismatch {
+                    category: None,
+                    missing: vec!["name".into()],
+                    unexpected: vec!["nmae".into()],
+                }
+            ),
+            (
+                "ITEMS".into(),
+                CheckIssueKind::PlaceholderMismatch {
+                    category: Some("other".into()),
+                    missing: vec![],
+                    unexpected: vec!["cuont".into()],
+                }
+            ),
+        ]
+    );
+}
+
+#[test]
+fn missing_plural_categories() {
+    // Ukrainian requires the "one", "few", "many" and "other" categories
+    let issues = check(
+        "uk",
+        vec![
+            (
+                "GREETING".into(),
+                TranslationEntry::Entry("Привіт, {{name}}!".into()),
+            ),
+            (
+                "ITEMS".into(),
+                plural(&[
+                    ("one", "{{count}} елемент"),
+                    ("other", "{{count}} елемента"),
+                ]),
+            ),
+        ],
+    );
+    assert_eq!(
+        issues,
+        vec![(
+            "ITEMS".into(),
+            CheckIssueKind::MissingPluralCategories {
+                categories: vec!["few".into(), "many".into()],
+            }
+        )]
+    );
+}
+
+#[test]
+fn plural_mismatch() {
+    let issues = check(
+        "de",
+        vec![
+            (
+                "GREETING".into(),
+                plural(&[("one", "Hallo"), ("other", "Hallo")]),
+            ),
+            (
+                "ITEMS".into(),
+                TranslationEntry::Entry("{{count}} Elemente".into()),
+            ),
+        ],
+    );
+    assert_eq!(
+        issues,
+        vec![
+            (
+                "GREETING".into(),
+                CheckIssueKind::PluralMismatch {
+                    expected_plural: false
+                }
+            ),
+            (
+                "ITEMS".into(),
+                CheckIssueKind::PluralMismatch {
+                    expected_plural: true
+                }
+            ),
+        ]
+    );
+}
@@ -0,0 +1,55 @@
+use crate::TrString;
+use crate::tests::helpers::new_visitor;
+use cntp_i18n_build_core::config::MacroMapping;
+use std::collections::HashMap;
+use syn::{parse_file, visit::Visit};
+
+#[test]
+fn custom_macros() {
+    let mut visitor = new_visitor("en");
+    visitor.custom_macros = HashMap::from([
+        (
+            "menu_item".to_string(),
+            MacroMapping {
+                key: 0,
+                default: Some(1),
+                plural: vec![],
+            },
+        ),
+        (
+            "count_label".to_string(),
+            MacroMapping {
+                key: 0,
+                default: None,
+                plural: vec![1, 2],
+            },
+        ),
+    ]);
+    visitor.visit_file(&parse_file(include_str!("custom_macros.rs.in")).unwrap());
+    visitor.finish();
+
+    assert!(visitor.errors.is_empty());
+    assert_eq!(visitor.strings.len(), 2);
+
+    let file_open = visitor
+        .strings
+        .get("FILE_OPEN")
+        .expect("FILE_OPEN not found in parsed strings");
+    match &file_open.string {
+        TrString::Single(string) => assert_eq!(string, "Open File"),
+        _ => panic!("FILE_OPEN is not a single string"),
+    }
+
+    let files_selected = visitor
+        .strings
+        .get("FILES_SELECTED")
+        .expect("FILES_SELECTED not found in parsed strings");
+    assert!(files_selected.plural);
+    match &files_selected.string {
+        TrString::Plural(forms) => {
+            assert_eq!(forms.len(), 2);
+            assert_eq!(forms[1].1, "{{count}} files selected");
+        }
+        _ => panic!("FILES_SELECTED is not plural"),
+    }
+}
@@ -1,10 +1,21 @@
 #[cfg(test)]
-pub fn visit_string(string: &str, locale: &str) -> crate::TrMacroVisitor {
+pub fn new_visitor(locale: &str) -> crate::TrMacroVisitor {
     use icu::locale::Locale;
     use icu::plurals::PluralRules;
     use std::cell::RefCell;
     use std::path::PathBuf;
     use std::rc::Rc;
+
+    let locale = Locale::try_from_str(locale).expect("Invalid Locale");
+    crate::TrMacroVisitor::new(
+        PluralRules::try_new(locale.clone().into(), Default::default()).unwrap(),
+        PluralRules::try_new_ordinal(locale.into()).unwrap(),
+        Rc::new(RefCell::new(PathBuf::default())),
+    )
+}
+
+#[cfg(test)]
+pub fn visit_string(string: &str, locale: &str) -> crate::TrMacroVisitor {
     use syn::parse_file;
     use syn::visit::Visit;
 
@@ -12,15 +23,7 @@ pub fn visit_string(string: &str, locale: &str) -> crate::TrMacroVisitor {
         panic!("failed to parse source file");
     };
 
-    let mut visitor = crate::TrMacroVisitor::new(
-        PluralRules::try_new(
-            Locale::try_from_str(locale).expect("Invalid Locale").into(),
-            Default::default(),
-        )
-        .unwrap(),
-        Rc::new(RefCell::new(PathBuf::default())),
-    );
-
+    let mut visitor = new_visitor(locale);
     visitor.visit_file(&syntax);
     visitor.finish();
 
@@ -0,0 +1,23 @@
+use crate::lint::lint_source;
+use std::collections::HashMap;
+
+#[test]
+fn hardcoded_text() {
+    let issues = lint_source(include_str!("lint.rs.in"), "src/main.rs", &HashMap::new())
+        .expect("failed to parse source file");
+
+    assert_eq!(
+        issues
+            .iter()
+            .map(|issue| (issue.line, issue.sink.as_str(), issue.text.as_str()))
+            .collect::<Vec<_>>(),
+        vec![
+            (3, "child", "Save"),
+            (6, "subtitle", "Appearance"),
+            (7, "title", "Delete File?"),
+            (13, "icon_text", "Copy"),
+        ]
+    );
+    assert_eq!(issues[0].file, "src/main.rs");
+    assert_eq!(issues[0].column, 16);
+}
@@ -0,0 +1,33 @@
+use crate::TrString;
+use crate::tests::helpers::visit_string;
+use icu::plurals::PluralCategory;
+
+#[test]
+fn ordinal_plural_gen() {
+    let visitor = visit_string(include_str!("ordinal_plural_gen.rs.in"), "en");
+
+    let finished_place = visitor
+        .strings
+        .get("FINISHED_PLACE")
+        .expect("FINISHED_PLACE not found in parsed strings");
+    assert!(finished_place.plural);
+    assert!(finished_place.ordinal);
+    match &finished_place.string {
+        TrString::Plural(plural_strings) => {
+            assert_eq!(
+                plural_strings
+                    .iter()
+                    .map(|(category, _)| *category)
+                    .collect::<Vec<_>>(),
+                [
+                    PluralCategory::One,
+                    PluralCategory::Two,
+                    PluralCategory::Few,
+                    PluralCategory::Other
+                ]
+            );
+            assert_eq!(plural_strings[2].1, "You finished in {{count}}rd place");
+        }
+        _ => panic!("FINISHED_PLACE is not plural"),
+    }
+}
@@ -0,0 +1,338 @@
+use crate::catalog::merge_catalog;
+use crate::po::{import, import_entries, parse_po, write_po};
+use cntp_i18n_build_core::config::get_i18n_config;
+use cntp_i18n_build_core::load::{TranslationEntry, TranslationMeta};
+use std::collections::HashMap;
+use std::fs;
+use std::io::ErrorKind;
+
+fn meta() -> HashMap<String, TranslationMeta> {
+    HashMap::from([
+        (
+            "GREETING".to_string(),
+            TranslationMeta {
+                defined_in: Some("src/main.rs:4".into()),
+                description: Some("Shown when the application starts".into()),
+                ..Default::default()
+            },
+        ),
+        (
+            "ITEMS".to_string(),
+            TranslationMeta {
+                defined_in: Some("src/main.rs:8".into()),
+                plural: true,
+                ..Default::default()
+            },
+        ),
+        (
+            "MULTILINE".to_string(),
+            TranslationMeta {
+                defined_in: Some("src/main.rs:12".into()),
+                ..Default::default()
+            },
+        ),
+    ])
+}
+
+fn plural(forms: &[(&str, &str)]) -> TranslationEntry {
+    TranslationEntry::PluralEntry(
+        forms
+            .iter()
+            .map(|(category, string)| (category.to_string(), string.to_string()))
+            .collect(),
+    )
+}
+
+fn source() -> HashMap<String, TranslationEntry> {
+    HashMap::from([
+        (
+            "GREETING".to_string(),
+            TranslationEntry::Entry("Hello, \"{{name}}\"!".into()),
+        ),
+        (
+            "ITEMS".to_string(),
+            plural(&[("one", "{{count}} item"), ("other", "{{count}} items")]),
+        ),
+        (
+            "MULTILINE".to_string(),
+            TranslationEntry::Entry("First line\nSecond line".into()),
+        ),
+    ])
+}
+
+#[test]
+fn export_template() {
+    let pot = write_po(&meta(), &source(), None);
+
+    assert!(pot.contains("Plural-Forms: nplurals=INTEGER; plural=EXPRESSION;"));
+    assert!(pot.contains(
+        "\n#. Shown when the application starts\n\
+         #: src/main.rs:4\n\
+         msgctxt \"GREETING\"\n\
+         msgid \"Hello, \\\"{{name}}\\\"!\"\n\
+         msgstr \"\"\n"
+    ));
+    assert!(pot.contains(
+        "msgctxt \"ITEMS\"\n\
+         msgid \"{{count}} item\"\n\
+         msgid_plural \"{{count}} items\"\n\
+         msgstr[0] \"\"\n\
+         msgstr[1] \"\"\n"
+    ));
+    assert!(pot.contains(
+        "msgid \"\"\n\
+         \"First line\\n\"\n\
+         \"Second line\"\n"
+    ));
+}
+
+#[test]
+fn round_trip() {
+    let translations = HashMap::from([
+        (
+            "GREETING".to_string(),
+            TranslationEntry::Entry("Привіт, \"{{name}}\"!".into()),
+        ),
+        (
+            "ITEMS".to_string(),
+            plural(&[
+                ("one", "{{count}} елемент"),
+                ("few", "{{count}} елементи"),
+                ("many", "{{count}} елементів"),
+                ("other", "{{count}} елемента"),
+            ]),
+        ),
+        (
+            "MULTILINE".to_string(),
+            TranslationEntry::Entry("Перший рядок\nДругий рядок".into()),
+        ),
+    ]);
+
+    let po = write_po(&meta(), &source(), Some(("uk", &translations)));
+    assert!(po.contains("X-Cntp-Plural-Categories: one, few, many, other"));
+    assert!(po.contains("msgstr[2] \"{{count}} елементів\""));
+
+    let entries = parse_po(&po).unwrap();
+    let (catalog, report) =
+        import_entries("uk", &entries, &["one", "few", "many", "other"], &meta());
+    assert_eq!(report.imported, 3);
+    assert!(report.rejected.is_empty());
+
+    for (key, entry) in catalog {
+        match (entry, &translations[&key]) {
+            (TranslationEntry::Entry(imported), TranslationEntry::Entry(expected)) => {
+                assert_eq!(&imported, expected)
+            }
+            (TranslationEntry::PluralEntry(imported), TranslationEntry::PluralEntry(expected)) => {
+                assert_eq!(&imported, expected)
+            }
+            _ => panic!("{key} changed between plural and singular"),
+        }
+    }
+}
+
+#[test]
+fn plural_forms_header() {
+    let translations = HashMap::new();
+
+    let po = write_po(&meta(), &source(), Some(("fr", &translations)));
+    assert!(
+        po.contains("Plural-Forms: nplurals=3; plural=(n==0 || n==1 ? 0 : n%1000000==0 ? 1 : 2);")
+    );
+    assert!(po.contains("X-Cntp-Plural-Categories: one, many, other"));
+    assert!(po.contains(
+        "msgstr[0] \"\"\n\
+         msgstr[1] \"\"\n\
+         msgstr[2] \"\"\n"
+    ));
+
+    let po = write_po(&meta(), &source(), Some(("es", &translations)));
+    assert!(
+        po.contains("Plural-Forms: nplurals=3; plural=(n==1 ? 0 : n!=0 && n%1000000==0 ? 1 : 2);")
+    );
+    assert!(po.contains("X-Cntp-Plural-Categories: one, many, other"));
+
+    let po = write_po(&meta(), &source(), Some(("de", &translations)));
+    assert!(po.contains("Plural-Forms: nplurals=2; plural=(n != 1);"));
+}
+
+#[test]
+fn import_skips_unusable_entries() {
+    let po = r#"
+msgid ""
+msgstr ""
+"Language: de\n"
+
+#, fuzzy
+msgctxt "GREETING"
+msgid "Hello, {{name}}!"
+msgstr "Hallo, {{name}}!"
+
+msgctxt "ITEMS"
+msgid "{{count}} item"
+msgid_plural "{{count}} items"
+msgstr[0] "{{count}} Element"
+msgstr[1] ""
+
+msgctxt "MULTILINE"
+msgid "First line\nSecond line"
+msgstr ""
+
+msgctxt "REMOVED"
+msgid "Removed"
+msgstr "Entfernt"
+
+#~ msgctxt "OBSOLETE"
+#~ msgid "Obsolete"
+#~ msgstr "Veraltet"
+"#;
+
+    let entries = parse_po(po).unwrap();
+    let (catalog, report) = import_entries("de", &entries, &["one", "other"], &meta());
+    assert!(catalog.is_empty());
+    assert_eq!(report.imported, 0);
+    assert_eq!(
+        report
+            .rejected
+            .iter()
+            .map(|rejected| rejected.key.as_str())
+            .collect::<Vec<_>>(),
+        vec!["GREETING", "ITEMS", "REMOVED"]
+    );
+}
+
+#[test]
+fn import_keeps_existing_translations() {
+    let po = r#"
+msgid ""
+msgstr ""
+"Language: de\n"
+
+#, fuzzy
+msgctxt "GREETING"
+msgid "Hello, {{name}}!"
+msgstr "Hallo, {{name}}?"
+
+msgctxt "MULTILINE"
+msgid "First line\nSecond line"
+msgstr "Erste Zeile\nZweite Zeile"
+"#;
+
+    let existing = vec![
+        (
+            "GREETING".to_string(),
+            TranslationEntry::Entry("Hallo, {{name}}!".into()),
+        ),
+        (
+            "MULTILINE".to_string(),
+            TranslationEntry::Entry("Alte Zeile".into()),
+        ),
+    ];
+
+    let entries = parse_po(po).unwrap();
+    let (imported, report) = import_entries("de", &entries, &["one", "other"], &meta());
+    assert_eq!(report.imported, 1);
+
+    let catalog = merge_catalog(existing, imported)
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+    assert_eq!(catalog.len(), 2);
+    match (&catalog["GREETING"], &catalog["MULTILINE"]) {
+        (TranslationEntry::Entry(greeting), TranslationEntry::Entry(multiline)) => {
+            assert_eq!(greeting, "Hallo, {{name}}!");
+            assert_eq!(multiline, "Erste Zeile\nZweite Zeile");
+        }
+        _ => panic!("the merged catalog has the wrong kind of entries"),
+    }
+}
+
+#[test]
+fn import_rejects_languages_that_are_not_locales() {
+    let manifest_directory =
+        std::env::temp_dir().join(format!("cntp_i18n_gen_po_import_{}", std::process::id()));
+    let config = get_i18n_config(&manifest_directory);
+    fs::write(config.i18n.translation_meta_file(&manifest_directory), "{}").unwrap();
+
+    // The plural categories header means the language isn't needed to read the file
+    let po = r#"
+msgid ""
+msgstr ""
+"Language: ../de\n"
+"X-Cntp-Plural-Categories: one, other\n"
+"#;
+    let error = import(&manifest_directory, po, None).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+    assert!(!manifest_directory.join("de.json").exists());
+
+    let error = import(&manifest_directory, po, Some("not a locale")).unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidInput);
+
+    let translation_directory = config.i18n.translation_directory(&manifest_directory);
+    assert_eq!(fs::read_dir(&translation_directory).unwrap().count(), 1);
+    fs::remove_dir_all(manifest_directory).unwrap();
+}
+
+#[test]
+fn parse_errors() {
+    assert!(parse_po("msgid \"unterminated\n").is_err());
+    assert!(parse_po("msgfoo \"value\"\n").is_err());
+}
+
+#[test]
+fn select_cases() {
+    let mut meta = meta();
+    meta.insert(
+        "REPLIED".to_string(),
+        TranslationMeta {
+            select: true,
+            ..Default::default()
+        },
+    );
+    let mut source = source();
+    source.insert(
+        "REPLIED".to_string(),
+        TranslationEntry::SelectEntry(HashMap::from([
+            ("feminine".to_string(), "She replied".to_string()),
+            ("other".to_string(), "They replied".to_string()),
+        ])),
+    );
+
+    let pot = write_po(&meta, &source, None);
+    assert!(pot.contains(
+        "msgctxt \"REPLIED.feminine\"\n\
+         msgid \"She replied\"\n\
+         msgstr \"\"\n"
+    ));
+    assert!(pot.contains(
+        "msgctxt \"REPLIED.other\"\n\
+         msgid \"They replied\"\n\
+         msgstr \"\"\n"
+    ));
+
+    let po = r#"
+msgctxt "REPLIED.feminine"
+msgid "She replied"
+msgstr "Sie hat geantwortet"
+
+msgctxt "REPLIED.masculine"
+msgid ""
+msgstr "Er hat geantwortet"
+
+msgctxt "REPLIED.other"
+msgid "They replied"
+msgstr "Hat geantwortet"
+"#;
+
+    let entries = parse_po(po).unwrap();
+    let (catalog, report) = import_entries("de", &entries, &["one", "other"], &meta);
+    assert_eq!(report.imported, 1);
+    assert!(report.rejected.is_empty());
+    match &catalog[..] {
+        [(key, TranslationEntry::SelectEntry(cases))] => {
+            assert_eq!(key, "REPLIED");
+            assert_eq!(cases.len(), 3);
+            assert_eq!(cases["masculine"], "Er hat geantwortet");
+        }
+        _ => panic!("REPLIED was not imported as a select string"),
+    }
+}
@@ -0,0 +1,91 @@
+use crate::check::{CheckIssueKind, CheckReport, check_catalog};
+use crate::review::translated_from;
+use cntp_i18n_build_core::load::{TranslatedFrom, TranslationEntry, TranslationMeta};
+use std::collections::HashMap;
+
+fn entry(string: &str) -> TranslationEntry {
+    TranslationEntry::Entry(string.into())
+}
+
+fn catalogs(translation: &str) -> Vec<(String, HashMap<String, TranslationEntry>)> {
+    vec![(
+        "de".to_string(),
+        HashMap::from([("GREETING".to_string(), entry(translation))]),
+    )]
+}
+
+#[test]
+fn content_hash_is_stable() {
+    assert_eq!(entry("Hello").content_hash(), entry("Hello").content_hash());
+    assert_ne!(
+        entry("Hello").content_hash(),
+        entry("Hello!").content_hash()
+    );
+    assert_ne!(
+        entry("one").content_hash(),
+        TranslationEntry::PluralEntry(HashMap::from([("other".into(), "one".into())]))
+            .content_hash()
+    );
+}
+
+#[test]
+fn source_change_marks_translation_stale() {
+    let old_source = entry("Hello").content_hash();
+    let new_source = entry("Hello there").content_hash();
+
+    let first = translated_from("GREETING", &old_source, None, &catalogs("Hallo"));
+    let previous = TranslationMeta {
+        source_hash: Some(old_source.clone()),
+        translated_from: first.clone().into_iter().collect(),
+        ..Default::default()
+    };
+
+    // The translation hasn't changed, so it keeps the old source hash
+    let second = translated_from("GREETING", &new_source, Some(&previous), &catalogs("Hallo"));
+    assert_eq!(second, first);
+
+    let meta = TranslationMeta {
+        source_hash: Some(new_source.clone()),
+        translated_from: second.into_iter().collect(),
+        ..Default::default()
+    };
+    assert!(meta.is_stale("de"));
+
+    let mut report = CheckReport::default();
+    check_catalog(
+        &mut report,
+        "de",
+        &[("GREETING".into(), entry("Hallo"))],
+        &HashMap::from([("GREETING".to_string(), meta.clone())]),
+        &HashMap::from([("GREETING".to_string(), entry("Hello there"))]),
+    );
+    assert_eq!(
+        report
+            .issues
+            .into_iter()
+            .map(|issue| issue.kind)
+            .collect::<Vec<_>>(),
+        vec![CheckIssueKind::NeedsReview]
+    );
+
+    // Changing the translation records it against the new source string
+    let third = translated_from(
+        "GREETING",
+        &new_source,
+        Some(&meta),
+        &catalogs("Hallo zusammen"),
+    );
+    assert_eq!(
+        third.get("de"),
+        Some(&TranslatedFrom {
+            source_hash: new_source,
+            translation_hash: entry("Hallo zusammen").content_hash(),
+        })
+    );
+}
+
+#[test]
+fn empty_translations_are_not_recorded() {
+    let source = entry("Hello").content_hash();
+    assert!(translated_from("GREETING", &source, None, &catalogs(" ")).is_empty());
+}
@@ -0,0 +1,44 @@
+use crate::tests::helpers::visit_string;
+use crate::{TrString, VisitorErrorType};
+
+#[test]
+fn select_gen() {
+    let visitor = visit_string(include_str!("select_gen.rs.in"), "en");
+
+    let profile_updated = visitor
+        .strings
+        .get("PROFILE_UPDATED")
+        .expect("PROFILE_UPDATED not found in parsed strings");
+    assert_eq!(profile_updated.line_no, 3);
+    assert!(profile_updated.select);
+    assert!(!profile_updated.plural);
+    match &profile_updated.string {
+        TrString::Select(cases) => {
+            assert_eq!(
+                cases,
+                &vec![
+                    (
+                        "masculine".to_string(),
+                        "{{name}} updated his profile".to_string()
+                    ),
+                    (
+                        "feminine".to_string(),
+                        "{{name}} updated her profile".to_string()
+                    ),
+                    (
+                        "other".to_string(),
+                        "{{name}} updated their profile".to_string()
+                    ),
+                ]
+            );
+        }
+        _ => panic!("PROFILE_UPDATED is not a select string"),
+    }
+
+    assert!(!visitor.strings.contains_key("REPLIED"));
+    assert_eq!(visitor.errors.len(), 1);
+    match &visitor.errors[0].error_type {
+        VisitorErrorType::MissingSelectOther { id } => assert_eq!(id, "REPLIED"),
+        _ => panic!("Visitor returned incorrect error type"),
+    }
+}
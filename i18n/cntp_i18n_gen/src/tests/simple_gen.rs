@@ -15,5 +15,6 @@ fn simple_gen() {
             assert_eq!(source_string, "Hello World!")
         }
         TrString::Plural(_) => panic!("HELLO_WORLD is plural"),
+        TrString::Select(_) => panic!("HELLO_WORLD is a select string"),
     }
 }
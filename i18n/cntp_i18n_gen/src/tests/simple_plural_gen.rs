@@ -15,6 +15,9 @@ fn simple_plural_gen() {
         TrString::Single(_) => {
             panic!("HELLO_WORLD is singular")
         }
+        TrString::Select(_) => {
+            panic!("HELLO_WORLD is a select string")
+        }
         TrString::Plural(plural_strings) => {
             assert_eq!(plural_strings.len(), 2);
             let singular = plural_strings.first().unwrap();
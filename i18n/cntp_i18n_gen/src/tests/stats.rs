@@ -0,0 +1,89 @@
+use crate::stats::{LanguageStats, language_stats};
+use cntp_i18n_build_core::load::{TranslationEntry, TranslationMeta};
+use std::collections::HashMap;
+
+fn meta(keys: &[(&str, bool)]) -> HashMap<String, TranslationMeta> {
+    keys.iter()
+        .map(|(key, plural)| {
+            (
+                key.to_string(),
+                TranslationMeta {
+                    plural: *plural,
+                    ..Default::default()
+                },
+            )
+        })
+        .collect()
+}
+
+fn plural(forms: &[(&str, &str)]) -> TranslationEntry {
+    TranslationEntry::PluralEntry(
+        forms
+            .iter()
+            .map(|(category, string)| (category.to_string(), string.to_string()))
+            .collect(),
+    )
+}
+
+#[test]
+fn stats() {
+    let meta = meta(&[
+        ("GREETING", false),
+        ("FAREWELL", false),
+        ("ITEMS", true),
+        ("QUESTION", false),
+    ]);
+    let source = HashMap::from([
+        (
+            "GREETING".to_string(),
+            TranslationEntry::Entry("Hello, {{name}}!".into()),
+        ),
+        (
+            "FAREWELL".to_string(),
+            TranslationEntry::Entry("See you later".into()),
+        ),
+        (
+            "ITEMS".to_string(),
+            plural(&[("one", "{{count}} item"), ("other", "{{count}} items")]),
+        ),
+        (
+            "QUESTION".to_string(),
+            TranslationEntry::Entry("How are you?".into()),
+        ),
+    ]);
+    let catalog = vec![
+        (
+            "GREETING".to_string(),
+            TranslationEntry::Entry("Hallo, {{name}}!".into()),
+        ),
+        ("FAREWELL".to_string(), TranslationEntry::Entry("  ".into())),
+        (
+            "ITEMS".to_string(),
+            plural(&[("one", ""), ("other", "{{count}} Elemente")]),
+        ),
+        (
+            "REMOVED".to_string(),
+            TranslationEntry::Entry("Entfernt".into()),
+        ),
+    ];
+
+    assert_eq!(
+        language_stats("de", &catalog, &meta, &source),
+        LanguageStats {
+            language: "de".into(),
+            total: 4,
+            translated: 2,
+            missing: 1,
+            empty: 1,
+            untranslated_words: 6,
+            coverage: 50.0,
+        }
+    );
+}
+
+#[test]
+fn no_strings() {
+    let stats = language_stats("de", &[], &HashMap::new(), &HashMap::new());
+    assert_eq!(stats.total, 0);
+    assert_eq!(stats.coverage, 100.0);
+}
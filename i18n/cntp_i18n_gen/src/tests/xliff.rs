@@ -0,0 +1,257 @@
+use crate::catalog::{merge_catalog, validate_import_language};
+use crate::xliff::{import_document, write_xliff};
+use cntp_i18n_build_core::load::{TranslationEntry, TranslationMeta};
+use roxmltree::Document;
+use std::collections::HashMap;
+
+fn meta() -> HashMap<String, TranslationMeta> {
+    HashMap::from([
+        (
+            "GREETING".to_string(),
+            TranslationMeta {
+                context: Some("main.rs".into()),
+                defined_in: Some("src/main.rs:4".into()),
+                description: Some("Shown when <the> application starts".into()),
+                ..Default::default()
+            },
+        ),
+        (
+            "ITEMS".to_string(),
+            TranslationMeta {
+                plural: true,
+                ..Default::default()
+            },
+        ),
+    ])
+}
+
+fn plural(forms: &[(&str, &str)]) -> TranslationEntry {
+    TranslationEntry::PluralEntry(
+        forms
+            .iter()
+            .map(|(category, string)| (category.to_string(), string.to_string()))
+            .collect(),
+    )
+}
+
+fn source() -> HashMap<String, TranslationEntry> {
+    HashMap::from([
+        (
+            "GREETING".to_string(),
+            TranslationEntry::Entry("Hello, {{name}} & {{name}}!".into()),
+        ),
+        (
+            "ITEMS".to_string(),
+            plural(&[("one", "{{count}} item"), ("other", "{{count}} items")]),
+        ),
+    ])
+}
+
+type Catalog = Vec<(String, TranslationEntry)>;
+
+fn import(xliff: &str) -> (Catalog, Vec<(String, String)>) {
+    let document = Document::parse(xliff).unwrap();
+    let (mut catalog, report) = import_document("de", &document, &meta(), &source()).unwrap();
+    catalog.sort_by(|(a, _), (b, _)| a.cmp(b));
+    assert_eq!(report.imported, catalog.len());
+    (
+        catalog,
+        report
+            .rejected
+            .into_iter()
+            .map(|rejected| (rejected.key, rejected.reason))
+            .collect(),
+    )
+}
+
+#[test]
+fn export() {
+    let xliff = write_xliff("en", &meta(), &source(), None);
+
+    assert!(xliff.contains(
+        "    <unit id=\"GREETING\">\n\
+         \x20     <notes>\n\
+         \x20       <note category=\"context\">main.rs</note>\n\
+         \x20       <note category=\"description\">Shown when &lt;the&gt; application starts</note>\n\
+         \x20       <note category=\"location\">src/main.rs:4</note>\n\
+         \x20     </notes>\n\
+         \x20     <originalData>\n\
+         \x20       <data id=\"d1\">{{name}}</data>\n\
+         \x20     </originalData>\n\
+         \x20     <segment state=\"initial\">\n\
+         \x20       <source xml:space=\"preserve\">Hello, \
+         <ph id=\"ph1\" dataRef=\"d1\" canCopy=\"no\" canDelete=\"no\" disp=\"{{name}}\"/> &amp; \
+         <ph id=\"ph1_2\" dataRef=\"d1\" canCopy=\"no\" canDelete=\"no\" disp=\"{{name}}\"/>!</source>\n\
+         \x20     </segment>\n\
+         \x20   </unit>\n"
+    ));
+    assert!(xliff.contains("<group id=\"ITEMS\" type=\"cntp:plural\">"));
+    assert!(xliff.contains("<unit id=\"ITEMS.one\" name=\"one\">"));
+    assert!(xliff.contains("<unit id=\"ITEMS.other\" name=\"other\">"));
+    assert!(!xliff.contains("<target"));
+}
+
+#[test]
+fn round_trip() {
+    let translations = HashMap::from([
+        (
+            "GREETING".to_string(),
+            TranslationEntry::Entry("{{name}} & {{name}}, hallo!".into()),
+        ),
+        (
+            "ITEMS".to_string(),
+            plural(&[
+                ("one", "{{count}} Element"),
+                ("other", "{{count}} Elemente"),
+            ]),
+        ),
+    ]);
+
+    let xliff = write_xliff("en", &meta(), &source(), Some(("de", &translations)));
+    assert!(xliff.contains("trgLang=\"de\""));
+
+    let (catalog, rejected) = import(&xliff);
+    assert!(rejected.is_empty());
+    assert_eq!(catalog.len(), 2);
+    for (key, entry) in catalog {
+        match (entry, &translations[&key]) {
+            (TranslationEntry::Entry(imported), TranslationEntry::Entry(expected)) => {
+                assert_eq!(&imported, expected)
+            }
+            (TranslationEntry::PluralEntry(imported), TranslationEntry::PluralEntry(expected)) => {
+                assert_eq!(&imported, expected)
+            }
+            _ => panic!("{key} changed between plural and singular"),
+        }
+    }
+}
+
+#[test]
+fn import_rejects_invalid_units() {
+    let xliff = r#"<?xml version="1.0" encoding="UTF-8"?>
+<xliff xmlns="urn:oasis:names:tc:xliff:document:2.0" version="2.0" srcLang="en" trgLang="de">
+  <file id="translations">
+    <unit id="GREETING">
+      <originalData>
+        <data id="d1">{{name}}</data>
+      </originalData>
+      <segment>
+        <source>Hello, <ph id="ph1" dataRef="d1"/>!</source>
+        <target>Hallo, {{nom}}!</target>
+      </segment>
+    </unit>
+    <group id="ITEMS" type="cntp:plural">
+      <unit id="ITEMS.one" name="one">
+        <segment>
+          <source><ph id="ph1" dataRef="d1"/> item</source>
+          <target><ph id="ph1" dataRef="d9"/> Element</target>
+        </segment>
+      </unit>
+      <unit id="ITEMS.other" name="other">
+        <segment>
+          <source>{{count}} items</source>
+          <target>{{count}} Elemente</target>
+        </segment>
+      </unit>
+    </group>
+    <unit id="REMOVED">
+      <segment>
+        <source>Removed</source>
+        <target>Entfernt</target>
+      </segment>
+    </unit>
+  </file>
+</xliff>"#;
+
+    let (catalog, rejected) = import(xliff);
+    assert!(catalog.is_empty());
+    assert_eq!(
+        rejected,
+        vec![
+            (
+                "ITEMS".to_string(),
+                "\"one\" form: inline code ph1 refers to unknown original data \"d9\"".to_string()
+            ),
+            (
+                "GREETING".to_string(),
+                "placeholders differ from the source string: missing {{name}}; unexpected {{nom}}"
+                    .to_string()
+            ),
+            (
+                "REMOVED".to_string(),
+                "key is no longer defined in meta.json".to_string()
+            ),
+        ]
+    );
+}
+
+#[test]
+fn import_keeps_existing_translations() {
+    let xliff = r#"<?xml version="1.0" encoding="UTF-8"?>
+<xliff xmlns="urn:oasis:names:tc:xliff:document:2.0" version="2.0" srcLang="en" trgLang="de">
+  <file id="translations">
+    <unit id="GREETING">
+      <segment>
+        <source>Hello, {{name}} &amp; {{name}}!</source>
+        <target>Hallo, {{nom}}!</target>
+      </segment>
+    </unit>
+    <group id="ITEMS" type="cntp:plural">
+      <unit id="ITEMS.one" name="one">
+        <segment>
+          <source>{{count}} item</source>
+          <target>{{count}} Element</target>
+        </segment>
+      </unit>
+      <unit id="ITEMS.other" name="other">
+        <segment>
+          <source>{{count}} items</source>
+          <target>{{count}} Elemente</target>
+        </segment>
+      </unit>
+    </group>
+  </file>
+</xliff>"#;
+
+    let existing = vec![
+        (
+            "GREETING".to_string(),
+            TranslationEntry::Entry("Hallo, {{name}} & {{name}}!".into()),
+        ),
+        (
+            "ITEMS".to_string(),
+            plural(&[("one", "ein Element"), ("other", "{{count}} Elemente")]),
+        ),
+    ];
+
+    let (imported, rejected) = import(xliff);
+    assert_eq!(rejected.len(), 1);
+
+    let catalog = merge_catalog(existing, imported)
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+    assert_eq!(catalog.len(), 2);
+    match (&catalog["GREETING"], &catalog["ITEMS"]) {
+        (TranslationEntry::Entry(greeting), TranslationEntry::PluralEntry(items)) => {
+            assert_eq!(greeting, "Hallo, {{name}} & {{name}}!");
+            assert_eq!(items["one"], "{{count}} Element");
+        }
+        _ => panic!("the merged catalog has the wrong kind of entries"),
+    }
+}
+
+#[test]
+fn import_rejects_other_documents() {
+    let document = Document::parse("<xliff version=\"1.2\"/>").unwrap();
+    assert!(import_document("de", &document, &meta(), &source()).is_err());
+}
+
+#[test]
+fn import_language_must_be_a_locale() {
+    assert!(validate_import_language("de").is_ok());
+    assert!(validate_import_language("pt-BR").is_ok());
+    assert!(validate_import_language("not a locale").is_err());
+    assert!(validate_import_language("../de").is_err());
+    assert!(validate_import_language("de/../../en").is_err());
+    assert!(validate_import_language("..\\de").is_err());
+}
@@ -0,0 +1,747 @@
+//! XLIFF 2.0 import and export.
+//!
+//! Each string is exported as a `<unit>` whose `id` is the translation key.
+//! The file name, description and location from `meta.json` are exported as
+//! `<note>`s with the categories `context`, `description` and `location`.
+//!
+//! ## Placeholders
+//!
+//! `{{placeholders}}` are exported as `<ph>` inline codes that cannot be
+//! deleted or copied, with the placeholder itself stored in the unit's
+//! `<originalData>`. Translation tools show these as protected tags that the
+//! translator can move but not edit. On import, the codes are replaced with
+//! their original data again, and translations whose placeholders differ from
+//! the source string are rejected.
+//!
+//! ## Plurals
+//!
+//! XLIFF 2.0 has no native representation of plurals, so plural strings are
+//! exported as a `<group>` of type `cntp:plural` whose `id` is the translation
+//! key. The group contains one `<unit>` per plural category of the target
+//! language, with the category as the unit's `name`. Ordinal plural strings
+//! use a group of type `cntp:ordinal`, containing the target language's
+//! ordinal categories instead.
+//!
+//! ## Select strings
+//!
+//! Select strings are exported in the same way, as a `<group>` of type
+//! `cntp:select` containing one `<unit>` per case of the source string, with
+//! the case as the unit's `name`.
+//!
+//! These functions are exposed on the command line as
+//! `cargo cntp-i18n export --format xliff` and `cargo cntp-i18n import`.
+
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    io::{self, ErrorKind},
+    path::Path,
+};
+
+use cntp_i18n_build_core::{
+    config::get_i18n_config,
+    load::{self, TranslationEntry, TranslationMeta},
+};
+use itertools::Itertools;
+use roxmltree::{Document, Node};
+
+use crate::catalog::{
+    PLURAL_CATEGORIES, merge_catalog, ordinal_categories, plural_categories, sorted_cases,
+    sorted_forms, validate_import_language, write_catalog,
+};
+use crate::check::compare_placeholders;
+use crate::exchange::ImportReport;
+
+const XLIFF_NAMESPACE: &str = "urn:oasis:names:tc:xliff:document:2.0";
+
+/// The group type used for plural strings.
+const PLURAL_GROUP_TYPE: &str = "cntp:plural";
+
+/// The group type used for ordinal plural strings.
+const ORDINAL_GROUP_TYPE: &str = "cntp:ordinal";
+
+/// The group type used for select strings.
+const SELECT_GROUP_TYPE: &str = "cntp:select";
+
+/// Export a project's strings as an XLIFF 2.0 document.
+///
+/// If `language` is `None`, a document with no targets is exported.
+/// Otherwise, the targets are filled in from that language's catalog, if it exists.
+///
+/// # Arguments
+///
+/// * `manifest_directory` - Path to the directory containing `Cargo.toml`
+/// * `language` - The target language to export, or `None` to export only the source strings
+///
+/// # Errors
+///
+/// Returns an error if `meta.json` or any catalog cannot be loaded, or if the
+/// language is not a valid locale.
+pub fn export(manifest_directory: &Path, language: Option<&str>) -> io::Result<String> {
+    let config = get_i18n_config(manifest_directory);
+    let meta = load::meta(&config.i18n.translation_meta_file(manifest_directory))?;
+    let source = load::translation(&config.i18n.translation_catalog_file(manifest_directory))?
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+    let source_language = &config.i18n.default_language;
+
+    let Some(language) = language else {
+        return Ok(write_xliff(source_language, &meta, &source, None));
+    };
+
+    if plural_categories(language).is_none() {
+        return Err(io::Error::new(
+            ErrorKind::InvalidInput,
+            format!("{language} is not a valid locale"),
+        ));
+    }
+
+    let catalog_path = config
+        .i18n
+        .translation_directory(manifest_directory)
+        .join(format!("{language}.json"));
+    let translations = if catalog_path.exists() {
+        load::translation(&catalog_path)?.into_iter().collect()
+    } else {
+        HashMap::new()
+    };
+
+    Ok(write_xliff(
+        source_language,
+        &meta,
+        &source,
+        Some((language, &translations)),
+    ))
+}
+
+/// Import an XLIFF 2.0 document into a language's catalog.
+///
+/// The language is taken from the `language` argument if provided, and
+/// otherwise from the `trgLang` attribute of the document. The translated
+/// units of the document are merged into the catalog for that language. Units
+/// that are invalid, change the placeholders of the source string, or are no
+/// longer defined in `meta.json` are rejected and listed in the report, and
+/// keep their existing translation in the catalog.
+///
+/// # Arguments
+///
+/// * `manifest_directory` - Path to the directory containing `Cargo.toml`
+/// * `contents` - The contents of the XLIFF document
+/// * `language` - The language to import into, overriding the document's `trgLang`
+///
+/// # Errors
+///
+/// Returns an error if the document is not valid XLIFF 2.0, the language
+/// cannot be determined, or the catalog cannot be written.
+pub fn import(
+    manifest_directory: &Path,
+    contents: &str,
+    language: Option<&str>,
+) -> io::Result<ImportReport> {
+    let config = get_i18n_config(manifest_directory);
+    let meta = load::meta(&config.i18n.translation_meta_file(manifest_directory))?;
+    let source = load::translation(&config.i18n.translation_catalog_file(manifest_directory))?
+        .into_iter()
+        .collect::<HashMap<_, _>>();
+
+    let document = Document::parse(contents)
+        .map_err(|e| io::Error::new(ErrorKind::InvalidData, format!("invalid XML: {e}")))?;
+
+    let Some(language) = language
+        .map(str::to_string)
+        .or_else(|| {
+            document
+                .root_element()
+                .attribute("trgLang")
+                .map(str::to_string)
+        })
+        .filter(|language| !language.is_empty())
+    else {
+        return Err(io::Error::new(
+            ErrorKind::InvalidInput,
+            "the XLIFF document has no trgLang attribute, so the language must be specified",
+        ));
+    };
+
+    validate_import_language(&language)?;
+    if language == config.i18n.default_language {
+        return Err(io::Error::new(
+            ErrorKind::InvalidInput,
+            "translations cannot be imported into the default language",
+        ));
+    }
+
+    let (imported, report) = import_document(&language, &document, &meta, &source)?;
+
+    let catalog_path = config
+        .i18n
+        .translation_directory(manifest_directory)
+        .join(format!("{language}.json"));
+    let existing = if catalog_path.exists() {
+        load::translation(&catalog_path)?
+    } else {
+        Vec::new()
+    };
+    write_catalog(&catalog_path, &merge_catalog(existing, imported))?;
+
+    Ok(report)
+}
+
+/// Convert the units of a parsed XLIFF document into catalog entries.
+pub(crate) fn import_document(
+    language: &str,
+    document: &Document,
+    meta: &HashMap<String, TranslationMeta>,
+    source: &HashMap<String, TranslationEntry>,
+) -> io::Result<(Vec<(String, TranslationEntry)>, ImportReport)> {
+    let root = document.root_element();
+    if root.tag_name().name() != "xliff"
+        || root.tag_name().namespace() != Some(XLIFF_NAMESPACE)
+        || !root
+            .attribute("version")
+            .is_some_and(|version| version.starts_with("2."))
+    {
+        return Err(io::Error::new(
+            ErrorKind::InvalidData,
+            "the document is not an XLIFF 2.0 document",
+        ));
+    }
+
+    let mut report = ImportReport::new(language);
+    let mut catalog = Vec::new();
+
+    // Collect every unit first, so that plural forms and select cases spread over
+    // several units are validated together.
+    let mut singular = BTreeMap::new();
+    let mut plural = BTreeMap::<String, HashMap<String, String>>::new();
+    let mut ordinal = BTreeMap::<String, HashMap<String, String>>::new();
+    let mut select = BTreeMap::<String, HashMap<String, String>>::new();
+    let mut rejected_groups = HashSet::new();
+    for unit in root.descendants().filter(|node| is_element(node, "unit")) {
+        let group_type = unit
+            .parent_element()
+            .filter(|parent| is_element(parent, "group"))
+            .and_then(|group| Some((group, group.attribute("type")?)))
+            .filter(|(_, group_type)| {
+                [PLURAL_GROUP_TYPE, ORDINAL_GROUP_TYPE, SELECT_GROUP_TYPE].contains(group_type)
+            });
+
+        let (key, category) = match group_type {
+            Some((group, _)) => (
+                group.attribute("id").unwrap_or_default(),
+                Some(unit.attribute("name").unwrap_or_default()),
+            ),
+            None => (unit.attribute("id").unwrap_or_default(), None),
+        };
+
+        let target = match unit_target(&unit) {
+            Ok(Some(target)) => target,
+            Ok(None) => continue,
+            Err(reason) => {
+                // Don't import the remaining forms of a string with a rejected form
+                if category.is_some() {
+                    rejected_groups.insert(key.to_string());
+                }
+                report.reject(
+                    key,
+                    match category {
+                        Some(category) => format!("\"{category}\" form: {reason}"),
+                        None => reason,
+                    },
+                );
+                continue;
+            }
+        };
+
+        match (group_type, category) {
+            (Some((_, SELECT_GROUP_TYPE)), Some(case)) => {
+                select
+                    .entry(key.to_string())
+                    .or_default()
+                    .insert(case.to_string(), target);
+            }
+            (Some((_, ORDINAL_GROUP_TYPE)), Some(category)) => {
+                ordinal
+                    .entry(key.to_string())
+                    .or_default()
+                    .insert(category.to_string(), target);
+            }
+            (_, Some(category)) => {
+                plural
+                    .entry(key.to_string())
+                    .or_default()
+                    .insert(category.to_string(), target);
+            }
+            (_, None) => {
+                singular.insert(key.to_string(), target);
+            }
+        }
+    }
+
+    for (key, target) in singular {
+        let Some(key_meta) = meta.get(&key) else {
+            report.reject(&key, "key is no longer defined in meta.json");
+            continue;
+        };
+
+        if key_meta.plural {
+            report.reject(&key, "source string is plural but the translation is not");
+            continue;
+        }
+
+        if key_meta.select {
+            report.reject(
+                &key,
+                "source string is a select string but the translation is not",
+            );
+            continue;
+        }
+
+        if let Some(issue) = compare_placeholders(source.get(&key), &target, None) {
+            report.reject(&key, issue.description());
+            continue;
+        }
+
+        catalog.push((key, TranslationEntry::Entry(target)));
+        report.imported += 1;
+    }
+
+    let plural = plural
+        .into_iter()
+        .map(|(key, forms)| (key, forms, false))
+        .chain(ordinal.into_iter().map(|(key, forms)| (key, forms, true)));
+    for (key, forms, is_ordinal) in plural {
+        if rejected_groups.contains(&key) {
+            continue;
+        }
+
+        let Some(key_meta) = meta.get(&key) else {
+            report.reject(&key, "key is no longer defined in meta.json");
+            continue;
+        };
+
+        if !key_meta.plural {
+            report.reject(&key, "translation is plural but the source string is not");
+            continue;
+        }
+
+        if key_meta.ordinal != is_ordinal {
+            report.reject(
+                &key,
+                if key_meta.ordinal {
+                    "source string is an ordinal plural but the translation is not"
+                } else {
+                    "translation is an ordinal plural but the source string is not"
+                },
+            );
+            continue;
+        }
+
+        if let Some(category) = forms
+            .keys()
+            .find(|category| !PLURAL_CATEGORIES.contains(&category.as_str()))
+        {
+            report.reject(&key, format!("\"{category}\" is not a plural category"));
+            continue;
+        }
+
+        if !forms.contains_key("other") {
+            report.reject(&key, "plural translation has no \"other\" form");
+            continue;
+        }
+
+        if let Some(issue) = sorted_forms(&forms)
+            .into_iter()
+            .find_map(|(category, form)| {
+                compare_placeholders(source.get(&key), form, Some(category))
+            })
+        {
+            report.reject(&key, issue.description());
+            continue;
+        }
+
+        catalog.push((
+            key,
+            if is_ordinal {
+                TranslationEntry::OrdinalEntry(forms)
+            } else {
+                TranslationEntry::PluralEntry(forms)
+            },
+        ));
+        report.imported += 1;
+    }
+
+    for (key, cases) in select {
+        if rejected_groups.contains(&key) {
+            continue;
+        }
+
+        let Some(key_meta) = meta.get(&key) else {
+            report.reject(&key, "key is no longer defined in meta.json");
+            continue;
+        };
+
+        if !key_meta.select {
+            report.reject(
+                &key,
+                "translation is a select string but the source string is not",
+            );
+            continue;
+        }
+
+        if !cases.contains_key("other") {
+            report.reject(&key, "select translation has no \"other\" case");
+            continue;
+        }
+
+        if let Some(issue) = sorted_cases(&cases)
+            .into_iter()
+            .find_map(|(case, string)| compare_placeholders(source.get(&key), string, Some(case)))
+        {
+            report.reject(&key, issue.description());
+            continue;
+        }
+
+        catalog.push((key, TranslationEntry::SelectEntry(cases)));
+        report.imported += 1;
+    }
+
+    Ok((catalog, report))
+}
+
+/// Get the translated text of a unit, or `None` if the unit has not been translated.
+fn unit_target(unit: &Node) -> Result<Option<String>, String> {
+    let data = unit
+        .children()
+        .filter(|node| is_element(node, "originalData"))
+        .flat_map(|original_data| original_data.children())
+        .filter(|node| is_element(node, "data"))
+        .filter_map(|data| Some((data.attribute("id")?, data.text().unwrap_or_default())))
+        .collect::<HashMap<_, _>>();
+
+    let mut target = String::new();
+    for part in unit
+        .children()
+        .filter(|node| is_element(node, "segment") || is_element(node, "ignorable"))
+    {
+        let text = match part.children().find(|node| is_element(node, "target")) {
+            Some(text) => text,
+            // Ignorable parts, such as whitespace between segments, need not be translated
+            None if is_element(&part, "ignorable") => part
+                .children()
+                .find(|node| is_element(node, "source"))
+                .ok_or("ignorable has no source")?,
+            None => return Ok(None),
+        };
+        target.push_str(&inline_text(&text, &data)?);
+    }
+
+    if target.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(target))
+    }
+}
+
+/// Convert the content of a `<source>` or `<target>` back into a translation string.
+fn inline_text(node: &Node, data: &HashMap<&str, &str>) -> Result<String, String> {
+    let mut text = String::new();
+    for child in node.children() {
+        if child.is_text() {
+            text.push_str(child.text().unwrap_or_default());
+            continue;
+        }
+
+        if !child.is_element() {
+            continue;
+        }
+
+        match child.tag_name().name() {
+            "ph" => {
+                let data_ref = child.attribute("dataRef").unwrap_or_default();
+                let Some(original) = data.get(data_ref) else {
+                    return Err(format!(
+                        "inline code {} refers to unknown original data \"{data_ref}\"",
+                        child.attribute("id").unwrap_or_default()
+                    ));
+                };
+                text.push_str(original);
+            }
+            "mrk" => text.push_str(&inline_text(&child, data)?),
+            "cp" => {
+                let hex = child.attribute("hex").unwrap_or_default();
+                let Some(character) = u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+                else {
+                    return Err(format!("\"{hex}\" is not a valid code point"));
+                };
+                text.push(character);
+            }
+            other => return Err(format!("unsupported inline element <{other}>")),
+        }
+    }
+    Ok(text)
+}
+
+fn is_element(node: &Node, name: &str) -> bool {
+    node.is_element()
+        && node.tag_name().name() == name
+        && node.tag_name().namespace() == Some(XLIFF_NAMESPACE)
+}
+
+/// Write an XLIFF 2.0 document. If `translations` is `None`, no targets are written.
+pub(crate) fn write_xliff(
+    source_language: &str,
+    meta: &HashMap<String, TranslationMeta>,
+    source: &HashMap<String, TranslationEntry>,
+    translations: Option<(&str, &HashMap<String, TranslationEntry>)>,
+) -> String {
+    let mut xliff = String::new();
+    xliff.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xliff.push_str(&format!(
+        "<xliff xmlns=\"{XLIFF_NAMESPACE}\" version=\"2.0\" srcLang=\"{}\"",
+        escape(source_language)
+    ));
+    if let Some((language, _)) = translations {
+        xliff.push_str(&format!(" trgLang=\"{}\"", escape(language)));
+    }
+    xliff.push_str(">\n");
+    xliff.push_str("  <file id=\"translations\">\n");
+
+    for key in meta.keys().sorted() {
+        let Some(source_entry) = source.get(key) else {
+            continue;
+        };
+        let translation = translations.and_then(|(_, translations)| translations.get(key));
+
+        match source_entry {
+            TranslationEntry::Entry(string) => {
+                let target = match translation {
+                    Some(TranslationEntry::Entry(target)) => Some(target.as_str()),
+                    _ => None,
+                };
+                xliff.push_str(&format!("    <unit id=\"{}\">\n", escape(key)));
+                write_notes(&mut xliff, "      ", &meta[key]);
+                write_segment(&mut xliff, "      ", string, target);
+                xliff.push_str("    </unit>\n");
+            }
+            TranslationEntry::PluralEntry(forms) | TranslationEntry::OrdinalEntry(forms) => {
+                let is_ordinal = matches!(source_entry, TranslationEntry::OrdinalEntry(_));
+                let (group_type, categories) = if is_ordinal {
+                    (
+                        ORDINAL_GROUP_TYPE,
+                        translations.and_then(|(language, _)| ordinal_categories(language)),
+                    )
+                } else {
+                    (
+                        PLURAL_GROUP_TYPE,
+                        translations.map(|(language, _)| {
+                            plural_categories(language).unwrap_or_else(|| vec!["one", "other"])
+                        }),
+                    )
+                };
+                let categories = match categories {
+                    Some(categories) => categories,
+                    None => sorted_forms(forms)
+                        .into_iter()
+                        .map(|(category, _)| category)
+                        .collect(),
+                };
+
+                xliff.push_str(&format!(
+                    "    <group id=\"{}\" type=\"{group_type}\">\n",
+                    escape(key)
+                ));
+                write_notes(&mut xliff, "      ", &meta[key]);
+                for category in categories {
+                    let source_form = forms
+                        .get(category)
+                        .or_else(|| forms.get("other"))
+                        .map_or("", String::as_str);
+                    let target = match translation {
+                        Some(TranslationEntry::PluralEntry(translated)) if !is_ordinal => {
+                            translated.get(category).map(String::as_str)
+                        }
+                        Some(TranslationEntry::OrdinalEntry(translated)) if is_ordinal => {
+                            translated.get(category).map(String::as_str)
+                        }
+                        _ => None,
+                    };
+
+                    xliff.push_str(&format!(
+                        "      <unit id=\"{}.{category}\" name=\"{category}\">\n",
+                        escape(key)
+                    ));
+                    write_segment(&mut xliff, "        ", source_form, target);
+                    xliff.push_str("      </unit>\n");
+                }
+                xliff.push_str("    </group>\n");
+            }
+            TranslationEntry::SelectEntry(cases) => {
+                xliff.push_str(&format!(
+                    "    <group id=\"{}\" type=\"{SELECT_GROUP_TYPE}\">\n",
+                    escape(key)
+                ));
+                write_notes(&mut xliff, "      ", &meta[key]);
+                for (case, source_case) in sorted_cases(cases) {
+                    let target = match translation {
+                        Some(TranslationEntry::SelectEntry(translated)) => {
+                            translated.get(case).map(String::as_str)
+                        }
+                        _ => None,
+                    };
+
+                    xliff.push_str(&format!(
+                        "      <unit id=\"{}.{}\" name=\"{}\">\n",
+                        escape(key),
+                        escape(case),
+                        escape(case)
+                    ));
+                    write_segment(&mut xliff, "        ", source_case, target);
+                    xliff.push_str("      </unit>\n");
+                }
+                xliff.push_str("    </group>\n");
+            }
+        }
+    }
+
+    xliff.push_str("  </file>\n");
+    xliff.push_str("</xliff>\n");
+    xliff
+}
+
+fn write_notes(xliff: &mut String, indent: &str, meta: &TranslationMeta) {
+    let notes = [
+        ("context", &meta.context),
+        ("description", &meta.description),
+        ("location", &meta.defined_in),
+    ]
+    .into_iter()
+    .filter_map(|(category, note)| Some((category, note.as_ref()?)))
+    .collect::<Vec<_>>();
+
+    if notes.is_empty() {
+        return;
+    }
+
+    xliff.push_str(&format!("{indent}<notes>\n"));
+    for (category, note) in notes {
+        xliff.push_str(&format!(
+            "{indent}  <note category=\"{category}\">{}</note>\n",
+            escape(note)
+        ));
+    }
+    xliff.push_str(&format!("{indent}</notes>\n"));
+}
+
+/// Write the original data and segment of a unit.
+fn write_segment(xliff: &mut String, indent: &str, source: &str, target: Option<&str>) {
+    let target = target.filter(|target| !target.is_empty());
+
+    // Every distinct placeholder in the unit is stored once as original data
+    let data = spans(source)
+        .into_iter()
+        .chain(target.map(spans).unwrap_or_default())
+        .filter_map(|span| match span {
+            Span::Placeholder(placeholder) => Some(placeholder),
+            Span::Text(_) => None,
+        })
+        .unique()
+        .collect::<Vec<_>>();
+
+    if !data.is_empty() {
+        xliff.push_str(&format!("{indent}<originalData>\n"));
+        for (index, placeholder) in data.iter().enumerate() {
+            xliff.push_str(&format!(
+                "{indent}  <data id=\"d{}\">{}</data>\n",
+                index + 1,
+                escape(placeholder)
+            ));
+        }
+        xliff.push_str(&format!("{indent}</originalData>\n"));
+    }
+
+    let state = match target {
+        Some(_) => "translated",
+        None => "initial",
+    };
+    xliff.push_str(&format!("{indent}<segment state=\"{state}\">\n"));
+    xliff.push_str(&format!(
+        "{indent}  <source xml:space=\"preserve\">{}</source>\n",
+        inline_codes(source, &data)
+    ));
+    if let Some(target) = target {
+        xliff.push_str(&format!(
+            "{indent}  <target xml:space=\"preserve\">{}</target>\n",
+            inline_codes(target, &data)
+        ));
+    }
+    xliff.push_str(&format!("{indent}</segment>\n"));
+}
+
+/// Convert a translation string to XLIFF content, replacing placeholders with inline codes.
+///
+/// The n-th use of a placeholder gets the same inline code id in both the source and
+/// target, so that translation tools can match them up.
+fn inline_codes(string: &str, data: &[&str]) -> String {
+    let mut uses = HashMap::new();
+    spans(string)
+        .into_iter()
+        .map(|span| match span {
+            Span::Text(text) => escape(text),
+            Span::Placeholder(placeholder) => {
+                let data_index = data
+                    .iter()
+                    .position(|data| *data == placeholder)
+                    .unwrap_or_default()
+                    + 1;
+                let use_count = uses.entry(placeholder).or_insert(0);
+                *use_count += 1;
+
+                let id = if *use_count == 1 {
+                    format!("ph{data_index}")
+                } else {
+                    format!("ph{data_index}_{use_count}")
+                };
+                format!(
+                    "<ph id=\"{id}\" dataRef=\"d{data_index}\" canCopy=\"no\" canDelete=\"no\" disp=\"{}\"/>",
+                    escape(placeholder)
+                )
+            }
+        })
+        .collect()
+}
+
+enum Span<'a> {
+    Text(&'a str),
+    /// A placeholder, including its braces.
+    Placeholder(&'a str),
+}
+
+/// Split a translation string into text and `{{placeholders}}`.
+fn spans(string: &str) -> Vec<Span<'_>> {
+    let mut spans = Vec::new();
+    let mut remaining = string;
+    while let Some(start) = remaining.find("{{") {
+        let Some(end) = remaining[start + 2..].find("}}") else {
+            break;
+        };
+        let end = start + 2 + end + 2;
+
+        if start > 0 {
+            spans.push(Span::Text(&remaining[..start]));
+        }
+        spans.push(Span::Placeholder(&remaining[start..end]));
+        remaining = &remaining[end..];
+    }
+    if !remaining.is_empty() {
+        spans.push(Span::Text(remaining));
+    }
+    spans
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -8,8 +8,12 @@
 //!
 //! - [`tr!`] - Translate a simple string
 //! - [`trn!`] - Translate a plural string
+//! - [`trs!`] - Translate a string with a case for each value of a variable
 //! - [`tr_load!`] - Load translations into an [`I18nSource`](cntp_i18n_core::I18nSource)
-//! - [`tr_noop!`] / [`trn_noop!`] - Mark strings for extraction without runtime lookup
+//! - [`tr_watch!`] - Load translations from the filesystem at runtime, for previewing translations
+//! - [`tr_override!`] - Override translations of a library crate with the current crate's translations
+//! - [`tr_noop!`] / [`trn_noop!`] / [`trs_noop!`] - Mark strings for extraction without runtime lookup
+//! - [`macro@allow_hardcoded`] - Exclude an item from the hardcoded text lint
 //!
 //! ## Usage
 //!
@@ -44,7 +48,10 @@ mod config;
 mod parse_raw_string;
 mod tr;
 mod tr_load;
+mod tr_override;
+mod tr_watch;
 mod translation_file_cache;
+mod validate;
 
 /// Returns a translated string for the given key.
 ///
@@ -77,6 +84,7 @@ mod translation_file_cache;
 ///               Overrides the default locale for this call only.
 /// - `#description` - A reference to a string containing a description of the translation.
 ///                    Used by `cntp_i18n_gen` to generate `meta.json`.
+/// - `#markup` - Set to `true` to keep rich text markup in the output. See [Rich text](#rich-text).
 ///
 /// # Variable substitution
 ///
@@ -128,6 +136,24 @@ mod translation_file_cache;
 /// tr!("SAID", "Text using my modifier: {{phrase}}.", phrase:modifiers::MyModifier = some_text);
 /// ```
 ///
+/// # Rich text
+///
+/// Strings can contain bold (`<b>`), italic (`<i>`) and link (`<a href="name">`) markup. See
+/// [`cntp_i18n_core::markup`] for the full syntax. By default, the markup is removed and only
+/// the text is returned. Pass `#markup = true` to keep the markup, so that it can be rendered
+/// with styles, for example by the `rich_text` element in `contemporary`:
+///
+/// ```rust,ignore
+/// tr!(
+///     "ACCEPT_TERMS",
+///     "By continuing, you accept the <a href=\"terms\">terms of service</a>.",
+///     #markup = true
+/// );
+/// ```
+///
+/// When markup is kept, substituted variables are escaped so that they are always displayed
+/// as-is.
+///
 /// # Examples
 ///
 /// ```rust,ignore
@@ -214,6 +240,43 @@ pub fn tr(body: TokenStream) -> TokenStream {
 /// The default language can be changed in the library's configuration - see the documentation
 /// for `cntp_i18n_build_core::config`.
 ///
+/// # Ordinal Plurals
+///
+/// Strings that describe a position, such as "1st", "2nd" and "3rd", use a language's ordinal
+/// plural rules rather than its cardinal plural rules. Pass the `#ordinal = true` meta variable
+/// to select forms using ordinal plural rules. The default strings then correspond to the
+/// default language's ordinal plural categories - for English, these are "one", "two", "few"
+/// and "other":
+///
+/// ```rust,ignore
+/// trn!(
+///     "FINISHED_PLACE",
+///     "You finished in {{count}}st place",
+///     "You finished in {{count}}nd place",
+///     "You finished in {{count}}rd place",
+///     "You finished in {{count}}th place",
+///     count = place,
+///     #ordinal = true
+/// );
+/// // English: "You finished in 1st place", "You finished in 22nd place",
+/// //          "You finished in 11th place"
+/// ```
+///
+/// Ordinal strings are stored in the translation file under an `ordinal` key:
+///
+/// ```json
+/// {
+///     "FINISHED_PLACE": {
+///         "ordinal": {
+///             "one": "You finished in {{count}}st place",
+///             "two": "You finished in {{count}}nd place",
+///             "few": "You finished in {{count}}rd place",
+///             "other": "You finished in {{count}}th place"
+///         }
+///     }
+/// }
+/// ```
+///
 /// # Examples
 ///
 /// ```rust,ignore
@@ -254,6 +317,71 @@ pub fn trn(body: TokenStream) -> TokenStream {
     tr::trn(body)
 }
 
+/// Returns a translated string for the given key, choosing a case based on the value of a
+/// variable.
+///
+/// This is the equivalent of an ICU `select` message. It is used for strings that vary with
+/// something other than a count, such as the grammatical gender of a person, or the noun class
+/// of an object. Each case is written as `case => "string"`, and the case whose name matches
+/// the `select` variable is used.
+///
+/// This is a select version of the [`tr`] macro - for documentation not specific to
+/// select strings, see the documentation for that macro.
+///
+/// # Syntax
+///
+/// ```rust,ignore
+/// trs!("KEY", case => "string", other => "other string", select = value);
+/// trs!("KEY", "case-name" => "string", other => "other string", select = value);
+/// trs!("KEY", case => "{{var}} string", other => "{{var}} other string", select = value, var = value);
+/// ```
+///
+/// # Arguments
+///
+/// The arguments are the same as those for the [`tr!`] macro, with the addition of:
+/// - `case => "string"` - The default text for a case. Case names that are not valid
+///   identifiers can be written as string literals. The `other` case is required.
+/// - `select = value` - **Required.** The value determining which case to use. This can be
+///   any value that implements `Display`, such as a `&str` or an enum.
+///
+/// If no case matches the value of the `select` variable, the `other` case is used.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// trs!(
+///     "PROFILE_UPDATED",
+///     masculine => "{{name}} updated his profile.",
+///     feminine => "{{name}} updated her profile.",
+///     other => "{{name}} updated their profile.",
+///     select = user.gender,
+///     name = user.name
+/// );
+/// // English output for a user with gender "feminine": "Alice updated her profile."
+/// ```
+///
+/// # Translation File Format
+///
+/// The generated translation file will contain:
+///
+/// ```json
+/// {
+///     "PROFILE_UPDATED": {
+///         "select": {
+///             "masculine": "{{name}} updated his profile.",
+///             "feminine": "{{name}} updated her profile.",
+///             "other": "{{name}} updated their profile."
+///         }
+///     }
+/// }
+/// ```
+///
+/// Translators can add cases that their language needs, and remove cases that it doesn't.
+#[proc_macro]
+pub fn trs(body: TokenStream) -> TokenStream {
+    tr::trs(body)
+}
+
 /// Runs a translation modifier.
 ///
 /// This macro runs a translation modifier on an arbitrary value.
@@ -333,6 +461,22 @@ pub fn trn_noop(_: TokenStream) -> TokenStream {
     TokenStream::default()
 }
 
+/// Marks a select string for translation extraction without performing a lookup.
+///
+/// This is the select equivalent of [`tr_noop!`]. It has the same syntax as [`trs!`]
+/// but does not perform any translation lookup at runtime.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// // Mark select strings for extraction
+/// trs_noop!("REPLIED", feminine => "She replied", other => "They replied");
+/// ```
+#[proc_macro]
+pub fn trs_noop(_: TokenStream) -> TokenStream {
+    TokenStream::default()
+}
+
 /// Generates an [`I18nSource`](cntp_i18n_core::I18nSource) from the translation files.
 ///
 /// This macro reads all translation files from the configured translation directory
@@ -351,6 +495,35 @@ pub fn trn_noop(_: TokenStream) -> TokenStream {
 /// match_line_endings = false
 /// ```
 ///
+/// # Outdated translations
+///
+/// Translations made for an older version of their source string are included by default.
+/// To show the string in the default language instead until the translation has been
+/// reviewed, set `stale_translations` in the `i18n.toml` configuration:
+///
+/// ```toml
+/// [i18n]
+/// stale_translations = "fallback"
+/// ```
+///
+/// # Choosing languages
+///
+/// Every language in the translation directory is embedded by default. To leave out
+/// languages, for example in special editions that only ship a few languages or to skip
+/// unfinished translations, use the `[i18n.embed]` section of the `i18n.toml` configuration:
+///
+/// ```toml
+/// [i18n.embed]
+/// exclude = ["eo"]
+/// min_coverage = 80
+/// features = true
+/// ```
+///
+/// Alternatively, `languages` lists the only languages to embed. With `features` set, each
+/// language is only embedded when the cargo feature `lang-<language>` is enabled, so these
+/// features must be declared in `Cargo.toml`. The default language is always embedded, and
+/// a note is printed while building for each language that is left out.
+///
 /// # Example
 ///
 /// ```rust,ignore
@@ -366,7 +539,7 @@ pub fn trn_noop(_: TokenStream) -> TokenStream {
 ///
 /// # Multiple translation sources
 ///
-/// You can load multiple translation sources. They are searched in order:
+/// You can load multiple translation sources. They are searched in reverse order of loading:
 ///
 /// ```rust,ignore
 /// // Load base translations
@@ -376,6 +549,10 @@ pub fn trn_noop(_: TokenStream) -> TokenStream {
 /// I18N_MANAGER.write().unwrap().load_source(custom_translations);
 /// ```
 ///
+/// The generated source only provides translations for strings of the crate that calls this
+/// macro. Library crates that ship their own translations load them with their own call to
+/// `tr_load!`, and applications can replace some of them with [`tr_override!`].
+///
 /// # Compile-time behavior
 ///
 /// This macro embeds all translation data directly into the binary at compile time.
@@ -385,9 +562,97 @@ pub fn trn_noop(_: TokenStream) -> TokenStream {
 /// - Translation files must exist at compile time
 /// - Changes to translation files require recompilation
 ///
-/// If you'd like to load translations at runtime, you can write your own implementation
+/// To preview changes to translation files without recompiling, see [`tr_watch!`]. If you'd
+/// like to load translations from elsewhere at runtime, you can write your own implementation
 /// of [`I18nSource`](cntp_i18n_core::I18nSource).
 #[proc_macro]
 pub fn tr_load(body: TokenStream) -> TokenStream {
     tr_load::tr_load(body)
 }
+
+/// Watches the translation directory for changes at runtime, if enabled.
+///
+/// When the `CNTP_I18N_WATCH_TRANSLATIONS` environment variable is set, this macro loads a
+/// `FilesystemI18nSource` into the [`I18nManager`](cntp_i18n::I18nManager) that reads the
+/// current crate's translation directory at runtime. When a translation file changes, the
+/// affected strings are evicted from the cache and the cache eviction subscribers are
+/// notified, so that applications can re-render with the new translations.
+///
+/// Call this after loading the compiled translations with [`tr_load!`], so that the
+/// translations on disk take priority. When the environment variable is not set, this
+/// macro does nothing.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use cntp_i18n::{I18N_MANAGER, tr_load, tr_watch};
+///
+/// fn main() {
+///     I18N_MANAGER.write().unwrap().load_source(tr_load!());
+///     tr_watch!();
+/// }
+/// ```
+///
+/// Then run the application with the environment variable set:
+///
+/// ```bash
+/// CNTP_I18N_WATCH_TRANSLATIONS=1 cargo run
+/// ```
+#[proc_macro]
+pub fn tr_watch(body: TokenStream) -> TokenStream {
+    tr_watch::tr_watch(body)
+}
+
+/// Overrides translations of another crate with the translations of the current crate.
+///
+/// The first argument is the name of the crate to override, followed by the keys to
+/// override. When that crate looks up one of the keys, the current crate's translations are
+/// searched for the same key first, falling back to the other crate's translations if the
+/// current crate doesn't translate it.
+///
+/// Define the overriding strings in the current crate (for example, with [`tr_noop!`]) so
+/// that they are extracted into its translation files. See
+/// [`I18nManager::override_translations`](cntp_i18n::I18nManager::override_translations)
+/// for the full lookup order.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use cntp_i18n::{I18N_MANAGER, tr_load, tr_noop, tr_override};
+///
+/// tr_noop!("HELP_FILE_BUG", "Report a Problem");
+///
+/// fn main() {
+///     I18N_MANAGER.write().unwrap().load_source(tr_load!());
+///     tr_override!("contemporary", "HELP_FILE_BUG");
+/// }
+/// ```
+#[proc_macro]
+pub fn tr_override(body: TokenStream) -> TokenStream {
+    tr_override::tr_override(body)
+}
+
+/// Allows hardcoded user-visible text in an item.
+///
+/// `cargo cntp-i18n lint` reports string literals passed to methods that display text, such as
+/// `.child("Save")`, because they won't be translated. Mark items that display untranslated
+/// text on purpose with this attribute to exclude them from the lint. The item itself is left
+/// unchanged.
+///
+/// To allow a single string instead, put a `// cntp-i18n: allow-hardcoded` comment at the end
+/// of its line, or on its own line above it.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use cntp_i18n::allow_hardcoded;
+///
+/// #[allow_hardcoded]
+/// fn render_debug_overlay() -> impl IntoElement {
+///     div().child("Frame time")
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn allow_hardcoded(_attribute: TokenStream, item: TokenStream) -> TokenStream {
+    item
+}
@@ -1,14 +1,16 @@
 use crate::config::CURRENT_CRATE;
 use crate::translation_file_cache::variable_list;
+use crate::validate::validate_placeholders;
 use cntp_i18n_parse::trf::TrfMacroInput;
 use cntp_i18n_parse::{
-    MaybeFormattedNamedArg, MaybeNamedFormatterArg, tr::TrMacroInput, trn::TrnMacroInput,
+    MaybeFormattedNamedArg, MaybeNamedFormatterArg, NamedArg, tr::TrMacroInput, trn::TrnMacroInput,
+    trs::TrsMacroInput,
 };
 use proc_macro::TokenStream;
 use quote::quote;
 use std::env::args;
 use std::hash::{Hash, Hasher};
-use syn::{Error, Ident, Path, parse_macro_input, punctuated::Punctuated, token::Comma};
+use syn::{Error, Expr, Ident, Lit, Path, parse_macro_input, punctuated::Punctuated, token::Comma};
 
 pub fn resolve_modifier(path: Path) -> proc_macro2::TokenStream {
     if let Some(ident) = path.get_ident() {
@@ -24,6 +26,23 @@ pub fn resolve_modifier(path: Path) -> proc_macro2::TokenStream {
     }
 }
 
+/// Get the lookup method to call, keeping rich text markup if `#markup = true` was passed.
+fn lookup_method(context: &Punctuated<NamedArg, Comma>) -> Ident {
+    let markup = context.iter().any(|arg| {
+        arg.name == "markup"
+            && matches!(&arg.value, Expr::Lit(lit) if matches!(&lit.lit, Lit::Bool(bool) if bool.value))
+    });
+
+    Ident::new(
+        if markup {
+            "lookup_markup_cached"
+        } else {
+            "lookup_cached"
+        },
+        proc_macro2::Span::call_site(),
+    )
+}
+
 pub fn non_count_variable(
     variable: &MaybeFormattedNamedArg,
     bsmi_decls: &mut Vec<proc_macro2::TokenStream>,
@@ -256,6 +275,14 @@ pub fn tr(body: TokenStream) -> TokenStream {
             }
         });
 
+    let lookup_method = lookup_method(&input.context);
+    let passed_variables = input
+        .variables
+        .iter()
+        .map(|variable| variable.name.to_string())
+        .collect::<Vec<_>>();
+    let placeholder_issues = validate_placeholders(&input.translation_id, &passed_variables);
+
     let mut state = rustc_hash::FxHasher::default();
     input.hash(&mut state);
     let hash = state.finish();
@@ -268,10 +295,11 @@ pub fn tr(body: TokenStream) -> TokenStream {
 
             #( #bsmi_decls )*
             #( #ssmi_decls )*
+            #placeholder_issues
 
             let i18n = I18N_MANAGER.read().unwrap();
 
-            i18n.lookup_cached(#key, &[
+            i18n.#lookup_method(#key, &[
                 #( #variables_token )*
             ], #current_crate, #hash, #locale)
         }
@@ -411,6 +439,185 @@ pub fn trn(body: TokenStream) -> TokenStream {
             }
         });
 
+    let lookup_method = lookup_method(&input.context);
+    let passed_variables = input
+        .variables
+        .iter()
+        .map(|variable| variable.name.to_string())
+        .collect::<Vec<_>>();
+    let placeholder_issues = validate_placeholders(&input.translation_id, &passed_variables);
+
+    let mut state = rustc_hash::FxHasher::default();
+    input.hash(&mut state);
+    let hash = state.finish();
+
+    quote! {
+        {
+            use cntp_i18n::I18N_MANAGER;
+            use cntp_i18n::{Variable, BaseStringModifierInvocation, ErasedStringModifierTransform,
+                SubsequentStringModifierInvocation, StringModifier, LocaleFormattable};
+
+            #( #bsmi_decls )*
+            #( #ssmi_decls )*
+            #placeholder_issues
+
+            let i18n = I18N_MANAGER.read().unwrap();
+
+            i18n.#lookup_method(#key, &[
+                #( #variables_token )*
+            ], #current_crate, #hash, #locale)
+        }
+    }
+    .into()
+}
+
+/// Returns a translated string for the given key, with the case matching the `select` variable.
+///
+/// Examples:
+/// ```rs
+/// trs!(
+///     "PROFILE_UPDATED",
+///     masculine => "{{name}} updated his profile",
+///     feminine => "{{name}} updated her profile",
+///     other => "{{name}} updated their profile",
+///     select = user.gender,
+///     name = user.name
+/// );
+/// ```
+pub fn trs(body: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(body as TrsMacroInput);
+
+    let mut bsmi_decls = Vec::new();
+    let mut ssmi_decls = Vec::new();
+
+    let mut variables_token = Vec::new();
+    let variable_list = variable_list();
+    let Some(required_variables) = variable_list.get(&input.translation_id.value()) else {
+        return Error::new(
+            input.translation_id.span(),
+            format!(
+                "Translation key {} does not have any translations defined",
+                input.translation_id.value()
+            ),
+        )
+        .to_compile_error()
+        .into();
+    };
+
+    if !input.cases.is_empty() && !input.cases.iter().any(|case| case.name == "other") {
+        return Error::new(
+            input.translation_id.span(),
+            format!(
+                "Select string {} does not have an \"other\" case",
+                input.translation_id.value()
+            ),
+        )
+        .to_compile_error()
+        .into();
+    }
+
+    let Some(select) = input.variables.iter().find(|v| v.name == "select") else {
+        return Error::new(
+            input.translation_id.span(),
+            format!(
+                "No select variable specified when rendering key {}",
+                input.translation_id.value()
+            ),
+        )
+        .to_compile_error()
+        .into();
+    };
+
+    // Ensure all passed variables are used
+    for variable in input.variables.iter().filter(|v| v.name != "select") {
+        let is_used = input.cases.iter().any(|case| {
+            case.default_string
+                .value()
+                .contains(format!("{{{{{}}}}}", variable.name).as_str())
+        });
+        if !input.cases.is_empty() && !is_used {
+            return Error::new(
+                variable.name.span(),
+                format!(
+                    "Unused translation variable {} specified when rendering key {}",
+                    variable.name,
+                    input.translation_id.value()
+                ),
+            )
+            .to_compile_error()
+            .into();
+        }
+    }
+
+    for required_variable in required_variables {
+        if required_variable == "select" {
+            // Substituted with the select variable, which is always passed below
+            continue;
+        }
+
+        variables_token.push(
+            match input.variables.iter().find(|v| v.name == required_variable) {
+                Some(variable) => non_count_variable(variable, &mut bsmi_decls, &mut ssmi_decls),
+                None => quote! {
+                    None,
+                },
+            },
+        )
+    }
+
+    let select_expr = &select.value;
+    variables_token.push(quote! {
+        Some(&(
+            "select",
+            {
+                use cntp_i18n::Variable;
+                Variable::Select((#select_expr).to_string())
+            }
+        )),
+    });
+
+    let locale = input
+        .context
+        .iter()
+        .find(|x| x.name == "locale")
+        .map(|x| {
+            let value = &x.value;
+            quote! {
+                Some(#value)
+            }
+        })
+        .unwrap_or_else(|| {
+            quote! {
+                None
+            }
+        });
+
+    let key = input.translation_id.value();
+    let current_crate = input
+        .context
+        .iter()
+        .find(|x| x.name == "crate")
+        .map(|x| {
+            let value = &x.value;
+            quote! {
+                Some(#value)
+            }
+        })
+        .unwrap_or_else(|| {
+            let crate_name = &*CURRENT_CRATE;
+            quote! {
+                #crate_name
+            }
+        });
+
+    let lookup_method = lookup_method(&input.context);
+    let passed_variables = input
+        .variables
+        .iter()
+        .map(|variable| variable.name.to_string())
+        .collect::<Vec<_>>();
+    let placeholder_issues = validate_placeholders(&input.translation_id, &passed_variables);
+
     let mut state = rustc_hash::FxHasher::default();
     input.hash(&mut state);
     let hash = state.finish();
@@ -423,10 +630,11 @@ pub fn trn(body: TokenStream) -> TokenStream {
 
             #( #bsmi_decls )*
             #( #ssmi_decls )*
+            #placeholder_issues
 
             let i18n = I18N_MANAGER.read().unwrap();
 
-            i18n.lookup_cached(#key, &[
+            i18n.#lookup_method(#key, &[
                 #( #variables_token )*
             ], #current_crate, #hash, #locale)
         }
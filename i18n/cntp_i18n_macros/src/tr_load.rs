@@ -1,8 +1,13 @@
 use crate::config::{CURRENT_CRATE, I18N_CONFIG};
 use crate::parse_raw_string::I18nStringPartExtensions;
 use crate::translation_file_cache::{ParsedTranslationEntry, translation_file_cache};
+use cntp_i18n_build_core::{
+    config::{Embed, StaleTranslations},
+    load,
+};
 use proc_macro::TokenStream;
 use quote::quote;
+use std::{env, path::PathBuf};
 
 macro_rules! extract_plural_rule {
     ($items:ident, $key:ident, $n:ident) => {
@@ -29,15 +34,40 @@ macro_rules! extract_plural_rule {
 /// By default, when strings are loaded (at compile time), the line endings are automatically
 /// changed to match the compiled platform's line endings. If you wish to disable this behaviour,
 /// set `match_line_endings` to false in your i18n configuration.
+///
+/// If `stale_translations` is set to `fallback` in your i18n configuration, translations that
+/// were made for an older version of their source string are left out, so that the string is
+/// shown in the default language until the translation is reviewed.
+///
+/// Languages can be left out with the `[i18n.embed]` section of your i18n configuration. Languages
+/// that aren't embedded are left out of the translation file cache, and a note is printed while
+/// building. Languages gated behind a cargo feature are only embedded when it is enabled, and
+/// a warning notes each language whose feature is disabled. The `lang-<language>` features
+/// must be declared in the `Cargo.toml` of the crate calling `tr_load!`.
 pub fn tr_load(_body: TokenStream) -> TokenStream {
     let config = &*I18N_CONFIG;
     let default_language = &config.i18n.default_language;
 
+    let meta = if config.i18n.stale_translations == StaleTranslations::Fallback {
+        let manifest_directory: PathBuf = env::var("CARGO_MANIFEST_DIR")
+            .expect("CARGO_MANIFEST_DIR is not set")
+            .into();
+        load::meta(&config.i18n.translation_meta_file(&manifest_directory)).unwrap_or_default()
+    } else {
+        Default::default()
+    };
+
     let mut language_map = Vec::new();
+    let mut available_languages = Vec::new();
+    let mut disabled_language_notes = Vec::new();
     for (language, decoded_file) in translation_file_cache().iter() {
         let mut strings = Vec::new();
 
         for (key, entry) in decoded_file {
+            if meta.get(key).is_some_and(|meta| meta.is_stale(language)) {
+                continue;
+            }
+
             if let Some(token_stream) = match entry {
                 ParsedTranslationEntry::Entry(string) => {
                     if string.is_empty() {
@@ -52,7 +82,8 @@ pub fn tr_load(_body: TokenStream) -> TokenStream {
                         })
                     }
                 }
-                ParsedTranslationEntry::PluralEntry(items) => {
+                ParsedTranslationEntry::PluralEntry(items)
+                | ParsedTranslationEntry::OrdinalEntry(items) => {
                     if items.iter().all(|(_key, value)| value.is_empty()) {
                         None
                     } else {
@@ -81,10 +112,12 @@ pub fn tr_load(_body: TokenStream) -> TokenStream {
                         let two = extract_plural_rule!(items, key, two);
                         let few = extract_plural_rule!(items, key, few);
                         let many = extract_plural_rule!(items, key, many);
+                        let ordinal = matches!(entry, ParsedTranslationEntry::OrdinalEntry(_));
 
                         Some(quote! {
                             #key => I18nEntry::PluralEntry(I18nPluralStringEntry {
                                 locale: I18nString::Borrowed(#language),
+                                ordinal: #ordinal,
                                 #zero,
                                 #one,
                                 #two,
@@ -95,49 +128,142 @@ pub fn tr_load(_body: TokenStream) -> TokenStream {
                         })
                     }
                 }
+                ParsedTranslationEntry::SelectEntry(items) => {
+                    if items.iter().all(|(_key, value)| value.is_empty()) {
+                        None
+                    } else {
+                        let Some(other) = items.get("other") else {
+                            panic!(
+                                "Translation key {key} for language {language} has select entry \
+                                without other",
+                            )
+                        };
+
+                        let matched_other = other
+                            .iter()
+                            .map(|f| f.calculate_string_part(key).to_tokens())
+                            .collect::<Vec<_>>();
+
+                        let mut cases = items
+                            .iter()
+                            .filter(|(case, value)| *case != "other" && !value.is_empty())
+                            .collect::<Vec<_>>();
+                        cases.sort_by_key(|(case, _)| *case);
+                        let cases = cases.into_iter().map(|(case, value)| {
+                            let matched_string = value
+                                .iter()
+                                .map(|f| f.calculate_string_part(key).to_tokens())
+                                .collect::<Vec<_>>();
+                            quote! {
+                                (I18nString::Borrowed(#case), &[#( #matched_string, )*])
+                            }
+                        });
+
+                        Some(quote! {
+                            #key => I18nEntry::SelectEntry(I18nSelectStringEntry {
+                                cases: &[#( #cases, )*],
+                                other: &[#( #matched_other, )*]
+                            })
+                        })
+                    }
+                }
             } {
                 strings.push(token_stream);
             }
         }
 
+        // Languages other than the default language can be left out by a cargo feature
+        let feature_gate =
+            (config.i18n.embed.features && language != default_language).then(|| {
+                let feature = Embed::feature(language);
+                disabled_language_notes.push(disabled_language_note(language, &feature));
+                quote! { #[cfg(feature = #feature)] }
+            });
+
+        if !strings.is_empty() {
+            available_languages.push((language.clone(), feature_gate.clone()));
+        }
+
         language_map.push(quote! {
-            #language => phf::phf_map! {
-                #( #strings, )*
-            },
+            #feature_gate
+            #language => {
+                static STRINGS: phf::Map<&'static str, I18nEntry<'static>> = phf::phf_map! {
+                    #( #strings, )*
+                };
+                Some((#language, &STRINGS))
+            }
         });
     }
 
+    available_languages.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let available_languages = available_languages
+        .into_iter()
+        .map(|(language, feature_gate)| {
+            quote! {
+                #feature_gate
+                languages.push(#language.to_string());
+            }
+        });
     let current_crate = &*CURRENT_CRATE;
 
     quote! {
         {
-            use cntp_i18n::{I18nEntry, I18nSource, I18nStringEntry, I18nPluralStringEntry, I18nString, Locale, phf};
+            use cntp_i18n::{I18nEntry, I18nSource, I18nStringEntry, I18nPluralStringEntry, I18nSelectStringEntry, I18nString, Locale, phf};
             use std::iter::once;
 
+            #( #disabled_language_notes )*
+
             struct AutogeneratedSource {
                 default_language: &'static str
             };
 
+            impl AutogeneratedSource {
+                fn strings(language: &str) -> Option<(&'static str, &'static phf::Map<&'static str, I18nEntry<'static>>)> {
+                    match language {
+                        #( #language_map )*
+                        _ => None,
+                    }
+                }
+
+                fn lookup_with_language(&self, locale: &Locale, id: &str) -> Option<(&'static str, &'static I18nEntry<'static>)> {
+                    for language in locale.messages.iter().map(|s| s.as_str()).chain(once(self.default_language)) {
+                        if let Some((language, strings)) = Self::strings(language) {
+                            if let Some(entry) = strings.get(id) {
+                                return Some((language, entry));
+                            }
+                        }
+                    }
+
+                    None
+                }
+            }
+
             impl I18nSource for AutogeneratedSource {
                 fn lookup(&self, locale: &Locale, id: &str, lookup_crate: &str) -> Option<&I18nEntry> {
-
                     if lookup_crate != #current_crate {
                         return None;
                     }
 
-                    for language in locale.messages.iter().map(|s| s.as_str()).chain(once(self.default_language)) {
-                        static STRING_MAP: phf::Map<&'static str, phf::Map<&'static str, I18nEntry>> = phf::phf_map! {
-                            #( #language_map )*
-                        };
-
-                        let entry = STRING_MAP.get(language).map(|v| v.get(id)).flatten();
+                    self.lookup_with_language(locale, id).map(|(_, entry)| entry)
+                }
 
-                        if entry.is_some() {
-                            return entry;
-                        }
+                fn lookup_language(&self, locale: &Locale, id: &str, lookup_crate: &str) -> Option<String> {
+                    if lookup_crate != #current_crate {
+                        return None;
                     }
 
-                    None
+                    self.lookup_with_language(locale, id).map(|(language, _)| language.to_string())
+                }
+
+                fn available_languages(&self) -> Option<Vec<String>> {
+                    #[allow(unused_mut)]
+                    let mut languages = Vec::new();
+                    #( #available_languages )*
+                    Some(languages)
+                }
+
+                fn name(&self) -> String {
+                    format!("Compiled translations for {}", #current_crate)
                 }
             }
 
@@ -148,3 +274,24 @@ pub fn tr_load(_body: TokenStream) -> TokenStream {
     }
     .into()
 }
+
+/// Note that a language is left out because its cargo feature is disabled.
+///
+/// Whether the feature is enabled is only known when the calling crate is compiled, so the
+/// note can't be printed like the other languages that are left out. Instead, this uses a
+/// deprecated constant whose deprecation note is the message, which only exists when the
+/// feature is disabled.
+fn disabled_language_note(language: &str, feature: &str) -> proc_macro2::TokenStream {
+    let message = format!(
+        "not embedding the {language} translations, because the `{feature}` feature is disabled"
+    );
+    quote! {
+        #[cfg(not(feature = #feature))]
+        const _: () = {
+            #[deprecated(note = #message)]
+            #[allow(non_upper_case_globals)]
+            const cntp_i18n_language_not_embedded: () = ();
+            cntp_i18n_language_not_embedded
+        };
+    }
+}
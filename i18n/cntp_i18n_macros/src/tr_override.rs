@@ -0,0 +1,32 @@
+use crate::config::CURRENT_CRATE;
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{Error, LitStr, Token, parse_macro_input, punctuated::Punctuated};
+
+/// Generates a call that overrides keys of another crate with the translations of the current
+/// crate.
+pub fn tr_override(body: TokenStream) -> TokenStream {
+    let arguments = parse_macro_input!(body with Punctuated::<LitStr, Token![,]>::parse_terminated);
+
+    let mut arguments = arguments.into_iter();
+    let Some(overridden_crate) = arguments.next() else {
+        return Error::new(
+            Span::call_site(),
+            "Expected the name of the crate to override",
+        )
+        .to_compile_error()
+        .into();
+    };
+    let keys = arguments.collect::<Vec<_>>();
+    let current_crate = &*CURRENT_CRATE;
+
+    quote! {
+        cntp_i18n::I18N_MANAGER.write().unwrap().override_translations(
+            #overridden_crate,
+            #current_crate,
+            [#( #keys, )*]
+        )
+    }
+    .into()
+}
@@ -0,0 +1,23 @@
+use crate::config::{CURRENT_CRATE, I18N_CONFIG};
+use proc_macro::TokenStream;
+use quote::quote;
+use std::{env, path::PathBuf};
+
+/// Generates a call that loads a `FilesystemI18nSource` for the configured translation
+/// directory, if enabled at runtime.
+pub fn tr_watch(_body: TokenStream) -> TokenStream {
+    let manifest_directory: PathBuf = env::var("CARGO_MANIFEST_DIR")
+        .expect("CARGO_MANIFEST_DIR is not set")
+        .into();
+    let translation_directory = I18N_CONFIG
+        .i18n
+        .translation_directory(&manifest_directory)
+        .to_string_lossy()
+        .to_string();
+    let current_crate = &*CURRENT_CRATE;
+
+    quote! {
+        cntp_i18n::FilesystemI18nSource::install_if_enabled(#translation_directory, #current_crate)
+    }
+    .into()
+}
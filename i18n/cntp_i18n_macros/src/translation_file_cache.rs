@@ -6,7 +6,7 @@ use std::sync::Arc;
 use std::{env, iter, path::Path, sync::LazyLock};
 
 use crate::{
-    config::I18N_CONFIG,
+    config::{CURRENT_CRATE, I18N_CONFIG},
     parse_raw_string::{I18nFullStringPart, parse_raw_string},
 };
 
@@ -22,6 +22,8 @@ use crate::{
 pub enum ParsedTranslationEntry {
     Entry(Vec<I18nFullStringPart>),
     PluralEntry(FxHashMap<String, Vec<I18nFullStringPart>>),
+    OrdinalEntry(FxHashMap<String, Vec<I18nFullStringPart>>),
+    SelectEntry(FxHashMap<String, Vec<I18nFullStringPart>>),
 }
 
 fn is_running_under_rust_analyzer() -> bool {
@@ -33,19 +35,56 @@ fn is_running_under_rust_analyzer() -> bool {
     }
 }
 
+/// The keys of every string defined in the source code, used to calculate the coverage of
+/// each catalog.
+fn defined_keys(manifest_dir: &Path) -> Vec<String> {
+    let config = &*I18N_CONFIG;
+    let meta = load::meta(&config.i18n.translation_meta_file(manifest_dir)).unwrap_or_default();
+    if !meta.is_empty() {
+        return meta.into_keys().collect();
+    }
+
+    load::translation(&config.i18n.translation_catalog_file(manifest_dir))
+        .map(|catalog| catalog.into_iter().map(|(key, _)| key).collect())
+        .unwrap_or_default()
+}
+
 fn read_translation_file() -> FxHashMap<String, FxHashMap<String, ParsedTranslationEntry>> {
     let manifest_dir = env::var("CARGO_MANIFEST_DIR").expect("Failed to get CARGO_MANIFEST_DIR");
+    let manifest_dir = Path::new(&manifest_dir);
 
     let config = &*I18N_CONFIG;
-    let catalog_files = config.i18n.catalog_files(Path::new(&manifest_dir));
+    let catalog_files = config.i18n.catalog_files(manifest_dir);
+
+    let keys = if config.i18n.embed.min_coverage.is_some() {
+        defined_keys(manifest_dir)
+    } else {
+        Vec::new()
+    };
+    let keys = keys.iter().map(String::as_str).collect::<Vec<_>>();
 
     catalog_files
         .iter()
-        .map(|file| {
+        .filter_map(|file| {
             let language = file.file_stem().unwrap().to_str().unwrap().to_string();
             let decoded_file = load::translation(file).unwrap();
 
-            (
+            if let Some(reason) = config.i18n.embed.excluded_reason(
+                &language,
+                &config.i18n.default_language,
+                &decoded_file,
+                &keys,
+            ) {
+                if !is_running_under_rust_analyzer() {
+                    eprintln!(
+                        "note: {}: not embedding the {} translations, because {}",
+                        &*CURRENT_CRATE, language, reason
+                    );
+                }
+                return None;
+            }
+
+            Some((
                 language,
                 decoded_file
                     .into_iter()
@@ -64,9 +103,29 @@ fn read_translation_file() -> FxHashMap<String, FxHashMap<String, ParsedTranslat
                                     .collect(),
                             ),
                         ),
+                        TranslationEntry::OrdinalEntry(hash_map) => (
+                            key,
+                            ParsedTranslationEntry::OrdinalEntry(
+                                hash_map
+                                    .into_iter()
+                                    .map(|(plural_group, string)| {
+                                        (plural_group, parse_raw_string(&string))
+                                    })
+                                    .collect(),
+                            ),
+                        ),
+                        TranslationEntry::SelectEntry(hash_map) => (
+                            key,
+                            ParsedTranslationEntry::SelectEntry(
+                                hash_map
+                                    .into_iter()
+                                    .map(|(case, string)| (case, parse_raw_string(&string)))
+                                    .collect(),
+                            ),
+                        ),
                     })
                     .collect(),
-            )
+            ))
         })
         .collect()
 }
@@ -90,7 +149,9 @@ fn create_variable_list() -> FxHashMap<String, Vec<String>> {
             for (key, entry) in strings.iter() {
                 let variables = match entry {
                     ParsedTranslationEntry::Entry(string) => string.clone(),
-                    ParsedTranslationEntry::PluralEntry(hash_map) => hash_map
+                    ParsedTranslationEntry::PluralEntry(hash_map)
+                    | ParsedTranslationEntry::OrdinalEntry(hash_map)
+                    | ParsedTranslationEntry::SelectEntry(hash_map) => hash_map
                         .iter()
                         .flat_map(|(_, string)| string)
                         .cloned()
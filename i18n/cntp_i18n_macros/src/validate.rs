@@ -0,0 +1,120 @@
+use std::collections::BTreeSet;
+
+use cntp_i18n_build_core::config::PlaceholderValidation;
+use proc_macro2::Span;
+use quote::{quote, quote_spanned};
+use syn::{Error, Ident, LitStr};
+
+use crate::config::I18N_CONFIG;
+use crate::parse_raw_string::I18nFullStringPart;
+use crate::translation_file_cache::{ParsedTranslationEntry, translation_file_cache};
+
+/// Get the placeholders used by a translation, or `None` if it hasn't been translated.
+fn entry_placeholders(entry: &ParsedTranslationEntry) -> Option<BTreeSet<String>> {
+    let parts: Vec<&I18nFullStringPart> = match entry {
+        ParsedTranslationEntry::Entry(parts) => parts.iter().collect(),
+        ParsedTranslationEntry::PluralEntry(forms)
+        | ParsedTranslationEntry::OrdinalEntry(forms)
+        | ParsedTranslationEntry::SelectEntry(forms) => forms.values().flatten().collect(),
+    };
+
+    // Empty translations fall back to the next source at runtime
+    if parts.is_empty() {
+        return None;
+    }
+
+    Some(
+        parts
+            .into_iter()
+            .filter_map(|part| match part {
+                I18nFullStringPart::Static(_) => None,
+                I18nFullStringPart::Variable(name) => Some(name.to_string()),
+                I18nFullStringPart::Count => Some("count".to_string()),
+            })
+            .collect(),
+    )
+}
+
+/// Check every translation of a key against the variables passed to the macro.
+///
+/// Depending on the `placeholder_validation` configuration option, each mismatched translation
+/// is reported as a compiler warning or error. Otherwise, these mismatches only show up at
+/// runtime, as a literal `{{placeholder}}` in the translated text.
+pub fn validate_placeholders(
+    translation_id: &LitStr,
+    passed_variables: &[String],
+) -> proc_macro2::TokenStream {
+    let validation = I18N_CONFIG.i18n.placeholder_validation;
+    if validation == PlaceholderValidation::Allow {
+        return quote! {};
+    }
+
+    let key = translation_id.value();
+    let translations = translation_file_cache();
+    let source_placeholders = translations
+        .get(&I18N_CONFIG.i18n.default_language)
+        .and_then(|strings| strings.get(&key))
+        .and_then(entry_placeholders)
+        .unwrap_or_default();
+
+    let mut languages = translations.keys().collect::<Vec<_>>();
+    languages.sort();
+
+    let mut issues = Vec::new();
+    for language in languages {
+        let Some(placeholders) = translations[language]
+            .get(&key)
+            .and_then(entry_placeholders)
+        else {
+            continue;
+        };
+
+        for placeholder in &placeholders {
+            if let Some((variable, modifier)) = placeholder.split_once(':') {
+                issues.push(format!(
+                    "The {language} translation of {key} applies the modifier {modifier} to \
+                    {{{{{variable}}}}}, but modifiers can only be applied when calling the macro"
+                ));
+            } else if !passed_variables.contains(placeholder) {
+                issues.push(format!(
+                    "The {language} translation of {key} references {{{{{placeholder}}}}}, \
+                    which is not passed to the macro"
+                ));
+            }
+        }
+
+        for placeholder in source_placeholders.difference(&placeholders) {
+            // Plural forms commonly leave out the count, such as in "One item"
+            if placeholder != "count" && passed_variables.contains(placeholder) {
+                issues.push(format!(
+                    "The {language} translation of {key} leaves out {{{{{placeholder}}}}}"
+                ));
+            }
+        }
+    }
+
+    let span = translation_id.span();
+    issues
+        .into_iter()
+        .map(|issue| match validation {
+            PlaceholderValidation::Deny => Error::new(span, issue).to_compile_error(),
+            _ => warning(span, &issue),
+        })
+        .collect()
+}
+
+/// Emit a compiler warning.
+///
+/// Procedural macros can't emit warnings on stable Rust, so this uses a deprecated constant
+/// whose deprecation note is the warning.
+fn warning(span: Span, message: &str) -> proc_macro2::TokenStream {
+    let name = Ident::new("cntp_i18n_placeholder_mismatch", span);
+    quote_spanned! {span=>
+        {
+            #[deprecated(note = #message)]
+            #[allow(non_upper_case_globals)]
+            const cntp_i18n_placeholder_mismatch: () = ();
+            let _ = #name;
+        }
+    }
+}
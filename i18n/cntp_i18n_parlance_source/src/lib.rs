@@ -371,6 +371,7 @@ fn entry_translations_to_i18n_entry<'a>(
 
         let plural_entry = I18nPluralStringEntry {
             locale,
+            ordinal: false,
             zero,
             one,
             two,
@@ -414,6 +415,17 @@ impl I18nSource for CntpI18nParlanceSource {
         }
         None
     }
+
+    fn available_languages(&self) -> Option<Vec<String>> {
+        Some(self.entries.read().unwrap().keys().cloned().collect())
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "Parlance project {}/{} at {}",
+            self.project, self.subproject, self.base_url
+        )
+    }
 }
 
 pub async fn install_cntp_i18n_parlance_source(
@@ -1,7 +1,7 @@
 //! # Contemporary i18n Parser
 //!
 //! This crate provides parsing utilities for the Contemporary i18n macro system.
-//! It defines the syntax and parsing logic for `tr!` and `trn!` macro invocations.
+//! It defines the syntax and parsing logic for `tr!`, `trn!` and `trs!` macro invocations.
 //!
 //! ## Overview
 //!
@@ -13,6 +13,7 @@
 //!
 //! - [`tr`] - Parsing for the `tr!` macro (simple translations)
 //! - [`trn`] - Parsing for the `trn!` macro (plural translations)
+//! - [`trs`] - Parsing for the `trs!` macro (select translations)
 //!
 //! ## Syntax Elements
 //!
@@ -22,6 +23,7 @@
 //! - **Formatted arguments**: `name:Modifier = value` or `name:Modifier(arg) = value`
 //! - **Modifier chains**: `name:Quote:Date("YMD") = value`
 //! - **Literal suppression**: `name = !value` (disables locale formatting)
+//! - **Select cases**: `case => "string"` (in `trs!` only)
 //!
 //! ## Example Macro Syntax
 //!
@@ -37,11 +39,15 @@
 //!
 //! // Plural translation
 //! trn!("KEY", "{{count}} item", "{{count}} items", count = item_count);
+//!
+//! // Select translation
+//! trs!("KEY", feminine => "She replied", other => "They replied", select = gender);
 //! ```
 
 pub mod tr;
 pub mod trf;
 pub mod trn;
+pub mod trs;
 
 use syn::{
     Expr, Ident, LitStr, Path, Token, parenthesized,
@@ -0,0 +1,84 @@
+use std::hash::Hash;
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Ident, LitStr, Token};
+
+use crate::{MaybeFormattedNamedArg, NamedArg};
+
+/// A case of a select string.
+///
+/// Represents syntax like `feminine => "{{name}} updated her profile"`. The case
+/// name may also be a string literal, for values that are not valid identifiers.
+pub struct SelectCase {
+    /// The value of the `select` variable this case is used for.
+    pub name: String,
+    /// The default string for this case.
+    pub default_string: LitStr,
+}
+
+impl Parse for SelectCase {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = if input.peek(LitStr) {
+            input.parse::<LitStr>()?.value()
+        } else {
+            Ident::parse_any(input)?.to_string()
+        };
+        input.parse::<Token![=>]>()?;
+        let default_string: LitStr = input.parse()?;
+        Ok(SelectCase {
+            name,
+            default_string,
+        })
+    }
+}
+
+pub struct TrsMacroInput {
+    pub translation_id: LitStr,
+    pub cases: Vec<SelectCase>,
+    pub variables: Punctuated<MaybeFormattedNamedArg, Token![,]>,
+    pub context: Punctuated<NamedArg, Token![,]>,
+}
+
+impl Parse for TrsMacroInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let translation_id: LitStr = input.parse()?;
+
+        let mut variables = Punctuated::new();
+        let mut context = Punctuated::new();
+        let mut cases = Vec::new();
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+
+            if !input.is_empty() {
+                let is_context = input.peek(Token![#]);
+
+                if is_context {
+                    input.parse::<Token![#]>()?;
+                    let parse_result: NamedArg = input.parse()?;
+                    context.push(parse_result);
+                } else if input.peek2(Token![=>]) {
+                    cases.push(input.parse()?);
+                } else {
+                    let parse_result: MaybeFormattedNamedArg = input.parse()?;
+                    variables.push(parse_result);
+                }
+            }
+        }
+
+        Ok(TrsMacroInput {
+            translation_id,
+            cases,
+            variables,
+            context,
+        })
+    }
+}
+
+impl Hash for TrsMacroInput {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.translation_id.hash(state);
+        self.variables.hash(state);
+        self.context.hash(state);
+    }
+}
@@ -63,6 +63,8 @@
 //!
 //! - `chrono` (default): Enables support for formatting `chrono` date/time types with the
 //!   [`modifiers::Date`] modifier.
+//! - `pseudotranslation` (default): Allows the layout direction to be overridden with the
+//!   `CNTP_I18N_TEXT_DIRECTION` environment variable or [`set_layout_direction_override`].
 
 #![warn(missing_docs)]
 
@@ -108,6 +110,19 @@ pub enum LayoutDirection {
     RightToLeft,
 }
 
+#[cfg(feature = "pseudotranslation")]
+static LAYOUT_DIRECTION_OVERRIDE: std::sync::RwLock<Option<LayoutDirection>> =
+    std::sync::RwLock::new(None);
+
+/// Override the layout direction of every locale.
+///
+/// This is used by pseudotranslation to test right-to-left layouts with a left-to-right
+/// locale. Pass `None` to remove the override.
+#[cfg(feature = "pseudotranslation")]
+pub fn set_layout_direction_override(direction: Option<LayoutDirection>) {
+    *LAYOUT_DIRECTION_OVERRIDE.write().unwrap() = direction;
+}
+
 /// Represents the grammar used to join items in a list.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ListFunction {
@@ -642,6 +657,11 @@ impl Locale {
     /// }
     /// ```
     pub fn layout_direction(&self) -> LayoutDirection {
+        #[cfg(feature = "pseudotranslation")]
+        if let Some(direction) = *LAYOUT_DIRECTION_OVERRIDE.read().unwrap() {
+            return direction;
+        }
+
         #[cfg(feature = "pseudotranslation")]
         match std::env::var("CNTP_I18N_TEXT_DIRECTION").as_deref() {
             Ok("rtl") => return LayoutDirection::RightToLeft,
@@ -3,7 +3,7 @@
 
 use crate::actions::{DarkTheme, LightTheme, SystemTheme, register_actions};
 use crate::main_window::MainWindow;
-use cntp_i18n::{I18N_MANAGER, tr, tr_load, tr_noop};
+use cntp_i18n::{I18N_MANAGER, tr, tr_load, tr_noop, tr_watch};
 use cntp_icon_tool_macros::application_icon;
 use contemporary::application::new_contemporary_application;
 use contemporary::macros::application_details;
@@ -41,6 +41,7 @@ fn mane() {
     application_icon!("../dist/baseicon.svg");
     new_contemporary_application().run(|cx: &mut App| {
         I18N_MANAGER.write().unwrap().load_source(tr_load!());
+        tr_watch!();
         let bounds = Bounds::centered(None, size(px(800.0), px(600.0)), cx);
 
         let outer_window: Rc<RefCell<WeakEntity<MainWindow>>> =
@@ -25,4 +25,6 @@ publish_crate icon_tool/cntp_icon_tool_core
 publish_crate icon_tool/cntp_icon_tool_macros
 publish_crate deploy_tool/cntp_bundle_lib
 publish_crate deploy_tool/cargo_cntp_deploy
-publish_crate deploy_tool/cargo_cntp_bundle
\ No newline at end of file
+publish_crate deploy_tool/cargo_cntp_bundle
+publish_crate deploy_tool/cargo_cntp_publish
+publish_crate deploy_tool/cargo_cntp_new